@@ -0,0 +1,13 @@
+//! Runtime animation: samples `AnimationClipAsset`s, N-way blends clips
+//! (plus additive layers) into a pose, and drives playback through a
+//! simple state machine. The output is a flat per-bone pose; turning that
+//! into bone matrices for the compute-skinning pass is the last step
+//! before upload, handled by [`pose::Pose::to_bone_matrices`].
+
+pub mod player;
+pub mod pose;
+pub mod state_machine;
+
+pub use player::{AnimationEvent, AnimationPlayer, LayerBlendMode, PlaybackLayer};
+pub use pose::{BonePose, Pose};
+pub use state_machine::{AnimationState, AnimationStateMachine, Transition, TransitionCondition};
@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+
+use glam::{Quat, Vec3};
+use kiri_assets::animation::{AnimationClipAsset, Keyframe};
+use kiri_assets::AssetRef;
+
+use crate::pose::{BonePose, Pose};
+
+/// How a playback layer combines with the layers below it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayerBlendMode {
+    /// Blends towards this layer's pose by `weight` — the normal mode for
+    /// locomotion, idle-to-walk blends, etc.
+    Override,
+    /// Adds this layer's pose on top, scaled by `weight` — for layers like
+    /// "lean while aiming" that should combine with whatever the base
+    /// layers are already doing rather than replace it.
+    Additive,
+}
+
+/// One clip currently contributing to the player's output pose.
+pub struct PlaybackLayer {
+    pub clip: AssetRef,
+    pub time: f32,
+    pub weight: f32,
+    pub blend_mode: LayerBlendMode,
+    pub looping: bool,
+}
+
+/// A named marker crossed during the last [`AnimationPlayer::advance`] —
+/// footstep, hit frame, and the like. Drained once per frame by whatever
+/// system reacts to them (audio, gameplay).
+#[derive(Clone, Debug)]
+pub struct AnimationEvent {
+    pub clip: AssetRef,
+    pub name: String,
+}
+
+/// Plays back any number of clips simultaneously as stacked layers
+/// (N-way blended base layers plus additive layers on top) and evaluates
+/// them into a single [`Pose`]. One `AnimationPlayer` per skinned
+/// instance; [`crate::state_machine::AnimationStateMachine`] drives layer
+/// weights over time for state transitions.
+pub struct AnimationPlayer {
+    bone_count: usize,
+    layers: Vec<PlaybackLayer>,
+    pending_events: Vec<AnimationEvent>,
+}
+
+impl AnimationPlayer {
+    pub fn new(bone_count: usize) -> Self {
+        Self {
+            bone_count,
+            layers: Vec::new(),
+            pending_events: Vec::new(),
+        }
+    }
+
+    pub fn play(&mut self, clip: AssetRef, blend_mode: LayerBlendMode, weight: f32, looping: bool) {
+        self.layers.push(PlaybackLayer {
+            clip,
+            time: 0.0,
+            weight,
+            blend_mode,
+            looping,
+        });
+    }
+
+    pub fn layers(&self) -> &[PlaybackLayer] {
+        &self.layers
+    }
+
+    pub fn layers_mut(&mut self) -> &mut [PlaybackLayer] {
+        &mut self.layers
+    }
+
+    /// Advances every layer's playback time and queues any events it
+    /// crossed. Looping layers wrap; non-looping layers clamp at
+    /// `clip.duration` and are left for [`AnimationPlayer::remove_finished`]
+    /// to clean up.
+    pub fn advance(&mut self, dt: f32, clips: &HashMap<AssetRef, AnimationClipAsset>) {
+        for layer in &mut self.layers {
+            let Some(clip) = clips.get(&layer.clip) else {
+                continue;
+            };
+
+            let previous_time = layer.time;
+            layer.time += dt;
+
+            if clip.duration > 0.0 && layer.time >= clip.duration {
+                if layer.looping {
+                    layer.time %= clip.duration;
+                } else {
+                    layer.time = clip.duration;
+                }
+            }
+
+            for event in crossed_events(&clip.events, previous_time, layer.time, layer.looping) {
+                self.pending_events.push(AnimationEvent {
+                    clip: layer.clip,
+                    name: event.value.clone(),
+                });
+            }
+        }
+    }
+
+    /// Drops non-looping layers that have reached the end of their clip —
+    /// called after [`AnimationPlayer::advance`] so a one-shot layer (a hit
+    /// reaction, say) doesn't keep contributing its last pose forever.
+    pub fn remove_finished(&mut self, clips: &HashMap<AssetRef, AnimationClipAsset>) {
+        self.layers.retain(|layer| {
+            layer.looping
+                || clips
+                    .get(&layer.clip)
+                    .map(|clip| layer.time < clip.duration)
+                    .unwrap_or(true)
+        });
+    }
+
+    pub fn drain_events(&mut self) -> Vec<AnimationEvent> {
+        std::mem::take(&mut self.pending_events)
+    }
+
+    /// Samples every layer and composites them, in layer order, into one
+    /// [`Pose`].
+    pub fn evaluate(&self, clips: &HashMap<AssetRef, AnimationClipAsset>) -> Pose {
+        let mut pose = Pose::identity(self.bone_count);
+
+        for layer in &self.layers {
+            let Some(clip) = clips.get(&layer.clip) else {
+                continue;
+            };
+            let sampled = sample_clip(clip, layer.time, self.bone_count);
+
+            pose = match layer.blend_mode {
+                LayerBlendMode::Override => blend_poses(&pose, &sampled, layer.weight),
+                LayerBlendMode::Additive => add_pose(&pose, &sampled, layer.weight),
+            };
+        }
+
+        pose
+    }
+}
+
+fn crossed_events(
+    events: &[Keyframe<String>],
+    previous_time: f32,
+    current_time: f32,
+    looping: bool,
+) -> impl Iterator<Item = &Keyframe<String>> {
+    events.iter().filter(move |event| {
+        if looping && current_time < previous_time {
+            // Wrapped around this frame: anything after `previous_time` or
+            // before `current_time` was crossed.
+            event.time > previous_time || event.time <= current_time
+        } else {
+            event.time > previous_time && event.time <= current_time
+        }
+    })
+}
+
+fn sample_clip(clip: &AnimationClipAsset, time: f32, bone_count: usize) -> Pose {
+    let mut pose = Pose::identity(bone_count);
+
+    for track in &clip.tracks {
+        let Some(bone) = pose.bones.get_mut(track.bone_index as usize) else {
+            continue;
+        };
+
+        if let Some(translation) = sample_vec3_curve(&track.translation, time) {
+            bone.translation = translation;
+        }
+        if let Some(rotation) = sample_quat_curve(&track.rotation, time) {
+            bone.rotation = rotation;
+        }
+        if let Some(scale) = sample_vec3_curve(&track.scale, time) {
+            bone.scale = scale;
+        }
+    }
+
+    pose
+}
+
+fn sample_vec3_curve(keys: &[Keyframe<[f32; 3]>], time: f32) -> Option<Vec3> {
+    let (a, b, t) = bracket(keys, time)?;
+    Some(Vec3::from(a.value).lerp(Vec3::from(b.value), t))
+}
+
+fn sample_quat_curve(keys: &[Keyframe<[f32; 4]>], time: f32) -> Option<Quat> {
+    let (a, b, t) = bracket(keys, time)?;
+    Some(Quat::from_array(a.value).slerp(Quat::from_array(b.value), t))
+}
+
+/// Finds the pair of keys bracketing `time` and the interpolation factor
+/// between them. Keys are assumed sorted by time, as the baker emits them.
+fn bracket<T: Copy>(keys: &[Keyframe<T>], time: f32) -> Option<(Keyframe<T>, Keyframe<T>, f32)> {
+    if keys.is_empty() {
+        return None;
+    }
+    if keys.len() == 1 || time <= keys[0].time {
+        return Some((keys[0], keys[0], 0.0));
+    }
+
+    for pair in keys.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        if time <= b.time {
+            let span = (b.time - a.time).max(f32::EPSILON);
+            return Some((a, b, ((time - a.time) / span).clamp(0.0, 1.0)));
+        }
+    }
+
+    let last = keys[keys.len() - 1];
+    Some((last, last, 0.0))
+}
+
+fn blend_poses(base: &Pose, other: &Pose, weight: f32) -> Pose {
+    let mut out = base.clone();
+    for (bone, other_bone) in out.bones.iter_mut().zip(&other.bones) {
+        *bone = BonePose {
+            translation: bone.translation.lerp(other_bone.translation, weight),
+            rotation: bone.rotation.slerp(other_bone.rotation, weight),
+            scale: bone.scale.lerp(other_bone.scale, weight),
+        };
+    }
+    out
+}
+
+fn add_pose(base: &Pose, additive: &Pose, weight: f32) -> Pose {
+    let mut out = base.clone();
+    for (bone, additive_bone) in out.bones.iter_mut().zip(&additive.bones) {
+        *bone = BonePose {
+            translation: bone.translation + additive_bone.translation * weight,
+            rotation: bone.rotation * Quat::IDENTITY.slerp(additive_bone.rotation, weight),
+            scale: bone.scale + (additive_bone.scale - Vec3::ONE) * weight,
+        };
+    }
+    out
+}
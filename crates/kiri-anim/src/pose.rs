@@ -0,0 +1,51 @@
+use glam::{Mat4, Quat, Vec3};
+
+/// A single bone's local transform relative to its parent, in TRS form so
+/// blending can operate on translation/rotation/scale independently
+/// (slerp for rotation, lerp for the rest) rather than on matrices.
+#[derive(Clone, Copy, Debug)]
+pub struct BonePose {
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+impl BonePose {
+    pub fn identity() -> Self {
+        Self {
+            translation: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            scale: Vec3::ONE,
+        }
+    }
+
+    pub fn to_matrix(&self) -> Mat4 {
+        Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
+    }
+}
+
+impl Default for BonePose {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// The output of sampling or blending animation: one local transform per
+/// bone, in skeleton bone-index order. `to_bone_matrices` is what gets
+/// uploaded to the bone-matrix buffer the compute-skinning pass reads.
+#[derive(Clone, Debug)]
+pub struct Pose {
+    pub bones: Vec<BonePose>,
+}
+
+impl Pose {
+    pub fn identity(bone_count: usize) -> Self {
+        Self {
+            bones: vec![BonePose::identity(); bone_count],
+        }
+    }
+
+    pub fn to_bone_matrices(&self) -> Vec<Mat4> {
+        self.bones.iter().map(BonePose::to_matrix).collect()
+    }
+}
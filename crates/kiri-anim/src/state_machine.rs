@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use kiri_assets::AssetRef;
+
+/// One node of an [`AnimationStateMachine`]: which clip plays while the
+/// machine is in this state.
+#[derive(Clone, Debug)]
+pub struct AnimationState {
+    pub name: String,
+    pub clip: AssetRef,
+    pub looping: bool,
+}
+
+/// What has to be true about the machine's parameters for a
+/// [`Transition`] to fire.
+#[derive(Clone, Copy, Debug)]
+pub enum TransitionCondition {
+    ParameterGreaterThan { parameter: &'static str, threshold: f32 },
+    ParameterEquals { parameter: &'static str, value: f32 },
+    /// Fires once the named parameter is non-zero, then resets it to zero
+    /// — the usual way one-shot events (jump pressed, hit landed) drive a
+    /// transition without the caller having to clear the flag itself.
+    Trigger { parameter: &'static str },
+}
+
+#[derive(Clone, Debug)]
+pub struct Transition {
+    pub target: String,
+    pub condition: TransitionCondition,
+    pub blend_time: f32,
+}
+
+/// A minimal animation state machine: named states, each with a list of
+/// outgoing transitions checked against a table of float parameters the
+/// game sets every frame (`speed`, `is_grounded`, trigger flags, ...).
+pub struct AnimationStateMachine {
+    states: HashMap<String, AnimationState>,
+    transitions: HashMap<String, Vec<Transition>>,
+    current_state: String,
+    parameters: HashMap<String, f32>,
+}
+
+impl AnimationStateMachine {
+    pub fn new(initial: AnimationState) -> Self {
+        let current_state = initial.name.clone();
+        let mut states = HashMap::new();
+        states.insert(current_state.clone(), initial);
+
+        Self {
+            states,
+            transitions: HashMap::new(),
+            current_state,
+            parameters: HashMap::new(),
+        }
+    }
+
+    pub fn add_state(&mut self, state: AnimationState) {
+        self.states.insert(state.name.clone(), state);
+    }
+
+    pub fn add_transition(&mut self, from: &str, transition: Transition) {
+        self.transitions
+            .entry(from.to_string())
+            .or_default()
+            .push(transition);
+    }
+
+    pub fn set_parameter(&mut self, name: &str, value: f32) {
+        self.parameters.insert(name.to_string(), value);
+    }
+
+    pub fn current_state(&self) -> &AnimationState {
+        &self.states[&self.current_state]
+    }
+
+    /// Checks the current state's transitions in order and switches to the
+    /// first one whose condition holds. Returns the new state if one
+    /// fired, so the caller can re-trigger playback on the
+    /// [`crate::player::AnimationPlayer`] (this type owns no pose state of
+    /// its own).
+    pub fn update(&mut self) -> Option<&AnimationState> {
+        let transitions = self.transitions.get(&self.current_state)?.clone();
+
+        for transition in transitions {
+            if evaluate_condition(&transition.condition, &self.parameters) {
+                if let TransitionCondition::Trigger { parameter } = transition.condition {
+                    self.parameters.insert(parameter.to_string(), 0.0);
+                }
+                self.current_state = transition.target.clone();
+                return self.states.get(&self.current_state);
+            }
+        }
+
+        None
+    }
+}
+
+fn evaluate_condition(condition: &TransitionCondition, parameters: &HashMap<String, f32>) -> bool {
+    match *condition {
+        TransitionCondition::ParameterGreaterThan { parameter, threshold } => {
+            parameters.get(parameter).copied().unwrap_or(0.0) > threshold
+        }
+        TransitionCondition::ParameterEquals { parameter, value } => {
+            parameters.get(parameter).copied().unwrap_or(0.0) == value
+        }
+        TransitionCondition::Trigger { parameter } => {
+            parameters.get(parameter).copied().unwrap_or(0.0) != 0.0
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use winit::event::{Event, WindowEvent};
+use winit::event_loop::{ControlFlow, EventLoop};
+use winit::window::WindowBuilder;
+
+use kiri_backend::{
+    select_default_physical_device, Device, Instance, PresentMode, Swapchain, SwapchainDesc,
+};
+use kiri_core::time::FixedTimestepLoop;
+
+/// Hooks a game implements to plug into [`run`]. `update` is called at a
+/// fixed rate (`AppConfig::fixed_dt`), `render` once per real frame with
+/// `alpha` carried over from [`FixedTimestepLoop::alpha`] for interpolating
+/// between the last two simulated states.
+pub trait App {
+    fn init(&mut self, device: &Arc<Device>) -> Result<()>;
+
+    fn update(&mut self, dt: Duration);
+
+    fn render(&mut self, device: &Arc<Device>, swapchain: &Swapchain, alpha: f32) -> Result<()>;
+
+    fn shutdown(&mut self) {}
+
+    fn on_resize(&mut self, _width: u32, _height: u32) {}
+
+    /// Called right before `run` tears down and recreates the surface,
+    /// device, and swapchain after a `VK_ERROR_DEVICE_LOST` /
+    /// `VK_ERROR_SURFACE_LOST_KHR` (a GPU hot-plug, driver reset, or the OS
+    /// reclaiming the surface).
+    /// All GPU-resident handles the game is holding (its own pipelines,
+    /// buffers, images) are invalid the moment this is called — drop them
+    /// here rather than in `Drop` impls that might run against a dead
+    /// device. `init` is called again afterwards with the new device so
+    /// the game can re-register everything against it.
+    fn on_device_lost(&mut self) {}
+
+    /// Called when the window is minimized or the OS suspends the app
+    /// (mobile background, laptop sleep). No `update`/`render` calls
+    /// happen between this and the matching [`App::on_resume`] — a good
+    /// place to park streaming systems (asset loading, audio) rather than
+    /// have them spin against a surface that isn't there.
+    fn on_suspend(&mut self) {}
+
+    /// Called when rendering is about to resume after [`App::on_suspend`],
+    /// once the surface has a real size again.
+    fn on_resume(&mut self) {}
+}
+
+#[derive(Clone, Debug)]
+pub struct AppConfig {
+    pub title: String,
+    pub width: u32,
+    pub height: u32,
+    pub fixed_dt: Duration,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            title: "kiri".to_string(),
+            width: 1280,
+            height: 720,
+            fixed_dt: Duration::from_secs_f64(1.0 / 60.0),
+        }
+    }
+}
+
+/// Creates a window, stands up the Vulkan instance/device/swapchain behind
+/// it, and drives `app` until the window is closed. Blocks for the
+/// lifetime of the window: it returns only with an error from setup
+/// (window, instance, surface, device, or the first `init`), since
+/// `EventLoop::run` does not return once the loop has started.
+pub fn run(config: AppConfig, mut app: impl App + 'static) -> Result<()> {
+    let event_loop = EventLoop::new();
+    let window = WindowBuilder::new()
+        .with_title(&config.title)
+        .with_inner_size(winit::dpi::LogicalSize::new(config.width, config.height))
+        .build(&event_loop)?;
+
+    let display_handle = window.raw_display_handle();
+    let window_handle = window.raw_window_handle();
+
+    let instance = Instance::builder()
+        .required_extensions(Instance::required_surface_extensions(display_handle)?)
+        .build()?;
+    let mut surface = instance.create_surface(display_handle, window_handle)?;
+
+    let physical_device = select_default_physical_device(&instance)?;
+    let mut device = Device::create(&physical_device)?;
+
+    let mut swapchain = None;
+    let mut window_minimized = false;
+    let mut os_suspended = false;
+    let mut paused = false;
+    let mut window_size = window.inner_size();
+
+    app.init(&device)?;
+
+    let mut fixed_loop = FixedTimestepLoop::new(config.fixed_dt);
+
+    event_loop.run(move |event, _, control_flow| {
+        control_flow.set_poll();
+
+        match event {
+            Event::WindowEvent { event, .. } => match event {
+                WindowEvent::CloseRequested => control_flow.set_exit(),
+                WindowEvent::Resized(size) => {
+                    window_size = size;
+                    window_minimized = size.width == 0 || size.height == 0;
+                    if !window_minimized {
+                        app.on_resize(size.width, size.height);
+                        swapchain = None;
+                    }
+                    update_paused(&mut app, &mut paused, window_minimized || os_suspended);
+                }
+                _ => {}
+            },
+            Event::Suspended => {
+                os_suspended = true;
+                update_paused(&mut app, &mut paused, true);
+            }
+            Event::Resumed => {
+                os_suspended = false;
+                update_paused(&mut app, &mut paused, window_minimized || os_suspended);
+            }
+            Event::MainEventsCleared => {
+                if paused {
+                    return;
+                }
+
+                fixed_loop.tick(|dt| app.update(dt));
+
+                if swapchain.is_none() {
+                    let desc = SwapchainDesc {
+                        extent: ash::vk::Extent2D {
+                            width: window_size.width,
+                            height: window_size.height,
+                        },
+                        present_mode: PresentMode::Fifo,
+                        image_count: 3,
+                    };
+                    swapchain = device.create_swapchain(&instance, surface, &desc).ok();
+                    if let Some(new_swapchain) = &swapchain {
+                        device.run_on_swapchain_recreate_hooks(new_swapchain);
+                    }
+                }
+
+                if let Some(active_swapchain) = &swapchain {
+                    device.run_begin_frame_hooks();
+                    if let Err(err) = app.render(&device, active_swapchain, fixed_loop.alpha()) {
+                        if is_device_lost(&err) {
+                            log::warn!("Device lost, recreating device and swapchain: {:?}", err);
+                            app.on_device_lost();
+                            swapchain = None;
+                            surface = match instance.create_surface(display_handle, window_handle) {
+                                Ok(surface) => surface,
+                                Err(err) => {
+                                    log::error!("Failed to recreate surface: {:?}", err);
+                                    control_flow.set_exit();
+                                    return;
+                                }
+                            };
+                            device = match Device::create(&physical_device) {
+                                Ok(device) => device,
+                                Err(err) => {
+                                    log::error!("Failed to recreate device: {:?}", err);
+                                    control_flow.set_exit();
+                                    return;
+                                }
+                            };
+                            if let Err(err) = app.init(&device) {
+                                log::error!("Failed to re-register resources after device loss: {:?}", err);
+                                control_flow.set_exit();
+                            }
+                        } else {
+                            log::error!("Render failed: {:?}", err);
+                        }
+                    }
+                }
+            }
+            Event::LoopDestroyed => {
+                app.shutdown();
+            }
+            _ => {}
+        }
+    });
+}
+
+/// Fires `on_suspend`/`on_resume` on the `paused` transition edges only,
+/// so a resize event that doesn't actually change pause state (e.g. two
+/// `Resized` events in a row while still minimized) doesn't re-fire them.
+fn update_paused(app: &mut impl App, paused: &mut bool, should_pause: bool) {
+    if should_pause && !*paused {
+        app.on_suspend();
+    } else if !should_pause && *paused {
+        app.on_resume();
+    }
+    *paused = should_pause;
+}
+
+/// Checks whether `err` (as returned by `App::render`) is wrapping a
+/// [`kiri_backend::BackendError`] that means the device or its surface is
+/// gone, per [`kiri_backend::BackendError::is_device_lost`].
+fn is_device_lost(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<kiri_backend::BackendError>()
+        .map(|err| err.is_device_lost())
+        .unwrap_or(false)
+}
@@ -0,0 +1,267 @@
+//! Developer console: command/cvar registration, history, and
+//! autocomplete — the engine subsystems plug toggles into (wireframe,
+//! freeze culling, streaming budget, ...) via [`Console::register_cvar`]
+//! and [`Console::register_command`].
+//!
+//! What this module deliberately doesn't do: draw anything, or decide
+//! which key opens it. There's no font/text rendering system in
+//! `kiri-backend` yet for it to draw through, and [`crate::run`]'s event
+//! loop doesn't forward raw key events to `App` at all today — a game
+//! wanting an on-screen console reads [`Console::log`]/[`Console::history`]
+//! each frame and draws them with whatever text rendering it has, and
+//! calls [`Console::toggle`] from wherever it already handles input
+//! (its own `WindowEvent::KeyboardInput` match, a `kiri-input` binding,
+//! ...).
+
+use std::collections::{BTreeMap, VecDeque};
+
+/// How many lines of scrollback/history the console keeps before
+/// dropping the oldest — unbounded growth would leak memory in a long
+/// session with the console left open.
+const MAX_LINES: usize = 512;
+
+/// A typed console variable value — the small set of primitives
+/// subsystems actually expose as toggles.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CvarValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+impl CvarValue {
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            CvarValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            CvarValue::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            CvarValue::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            CvarValue::String(value) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    fn format(&self) -> String {
+        match self {
+            CvarValue::Bool(value) => value.to_string(),
+            CvarValue::Int(value) => value.to_string(),
+            CvarValue::Float(value) => value.to_string(),
+            CvarValue::String(value) => value.clone(),
+        }
+    }
+
+    /// Parses `text` into the same variant as `self`, so setting a cvar
+    /// can't silently change its type out from under whichever subsystem
+    /// reads it with `as_bool`/`as_int`/etc.
+    fn parse_like(&self, text: &str) -> Result<CvarValue, String> {
+        match self {
+            CvarValue::Bool(_) => text
+                .parse()
+                .map(CvarValue::Bool)
+                .map_err(|_| format!("expected true/false, got {text:?}")),
+            CvarValue::Int(_) => text
+                .parse()
+                .map(CvarValue::Int)
+                .map_err(|_| format!("expected an integer, got {text:?}")),
+            CvarValue::Float(_) => text
+                .parse()
+                .map(CvarValue::Float)
+                .map_err(|_| format!("expected a number, got {text:?}")),
+            CvarValue::String(_) => Ok(CvarValue::String(text.to_string())),
+        }
+    }
+}
+
+struct Cvar {
+    value: CvarValue,
+    description: String,
+}
+
+type CommandHandler = Box<dyn FnMut(&[&str]) -> Result<String, String> + Send>;
+
+struct Command {
+    handler: CommandHandler,
+    description: String,
+}
+
+/// The console's state: registered commands and cvars, input history,
+/// and printed output — everything a subsystem or a game's console UI
+/// needs, independent of how (or whether) it's drawn.
+#[derive(Default)]
+pub struct Console {
+    commands: BTreeMap<String, Command>,
+    cvars: BTreeMap<String, Cvar>,
+    history: VecDeque<String>,
+    log: VecDeque<String>,
+    open: bool,
+}
+
+impl Console {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a command invoked as `name arg1 arg2 ...`. `handler`
+    /// receives the arguments (not including `name`) and returns either
+    /// output text to log, or an error message to log instead.
+    pub fn register_command(
+        &mut self,
+        name: &str,
+        description: &str,
+        handler: impl FnMut(&[&str]) -> Result<String, String> + Send + 'static,
+    ) {
+        self.commands.insert(
+            name.to_string(),
+            Command {
+                handler: Box::new(handler),
+                description: description.to_string(),
+            },
+        );
+    }
+
+    /// Registers a cvar with its default value — running `name` with no
+    /// arguments prints its current value, `name <value>` sets it.
+    pub fn register_cvar(&mut self, name: &str, default: CvarValue, description: &str) {
+        self.cvars.insert(
+            name.to_string(),
+            Cvar {
+                value: default,
+                description: description.to_string(),
+            },
+        );
+    }
+
+    pub fn cvar(&self, name: &str) -> Option<&CvarValue> {
+        self.cvars.get(name).map(|cvar| &cvar.value)
+    }
+
+    /// Sets `name`'s cvar directly (bypassing text parsing), for callers
+    /// setting it programmatically rather than from a typed console line.
+    /// Fails if the type of `value` doesn't match the cvar's current type.
+    pub fn set_cvar(&mut self, name: &str, value: CvarValue) -> Result<(), String> {
+        let cvar = self
+            .cvars
+            .get_mut(name)
+            .ok_or_else(|| format!("unknown cvar: {name}"))?;
+        if std::mem::discriminant(&cvar.value) != std::mem::discriminant(&value) {
+            return Err(format!("type mismatch setting cvar {name}"));
+        }
+        cvar.value = value;
+        Ok(())
+    }
+
+    /// Parses and runs one console line: `name` alone or `name arg...`
+    /// against registered commands, or `name`/`name <value>` against
+    /// registered cvars. Always appends `line` to history and the result
+    /// to the log, and returns the same output text.
+    pub fn execute(&mut self, line: &str) -> String {
+        push_bounded(&mut self.history, line.to_string());
+        push_bounded(&mut self.log, format!("> {line}"));
+
+        let mut tokens = line.split_whitespace();
+        let output = match tokens.next() {
+            None => String::new(),
+            Some(name) => {
+                let args: Vec<&str> = tokens.collect();
+                self.run(name, &args)
+            }
+        };
+
+        if !output.is_empty() {
+            push_bounded(&mut self.log, output.clone());
+        }
+        output
+    }
+
+    fn run(&mut self, name: &str, args: &[&str]) -> String {
+        if let Some(command) = self.commands.get_mut(name) {
+            return match (command.handler)(args) {
+                Ok(text) => text,
+                Err(err) => format!("error: {err}"),
+            };
+        }
+
+        if let Some(cvar) = self.cvars.get_mut(name) {
+            return match args {
+                [] => format!("{name} = {}", cvar.value.format()),
+                [value] => match cvar.value.parse_like(value) {
+                    Ok(parsed) => {
+                        cvar.value = parsed;
+                        format!("{name} = {}", cvar.value.format())
+                    }
+                    Err(err) => format!("error: {err}"),
+                },
+                _ => "error: expected at most one value".to_string(),
+            };
+        }
+
+        format!("unknown command: {name}")
+    }
+
+    /// Command and cvar names starting with `partial`, sorted, for a
+    /// console UI to offer as completions. Both namespaces are searched
+    /// together since a user typing a prefix doesn't know or care which
+    /// kind it'll turn out to be.
+    pub fn autocomplete(&self, partial: &str) -> Vec<String> {
+        let mut matches: Vec<String> = self
+            .commands
+            .keys()
+            .chain(self.cvars.keys())
+            .filter(|name| name.starts_with(partial))
+            .cloned()
+            .collect();
+        matches.sort();
+        matches
+    }
+
+    /// The registration description of the command or cvar called `name`
+    /// (the command's, if both exist), for a `help` command or console
+    /// autocomplete tooltip to show.
+    pub fn describe(&self, name: &str) -> Option<&str> {
+        if let Some(command) = self.commands.get(name) {
+            return Some(command.description.as_str());
+        }
+        self.cvars.get(name).map(|cvar| cvar.description.as_str())
+    }
+
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.history.iter().map(String::as_str)
+    }
+
+    pub fn log(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.log.iter().map(String::as_str)
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+}
+
+fn push_bounded(lines: &mut VecDeque<String>, line: String) {
+    lines.push_back(line);
+    while lines.len() > MAX_LINES {
+        lines.pop_front();
+    }
+}
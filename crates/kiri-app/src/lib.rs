@@ -0,0 +1,10 @@
+//! Ties window creation, backend device/swapchain setup, and the
+//! fixed-timestep loop from `kiri-core` together behind a single `App`
+//! trait, so a game only has to implement `init`/`update`/`render`
+//! instead of wiring winit and Vulkan itself every time.
+
+mod app;
+mod console;
+
+pub use app::{run, App, AppConfig};
+pub use console::{Console, CvarValue};
@@ -0,0 +1,130 @@
+use glam::{Quat, Vec3};
+use kiri_assets::animation::{AnimationClipAsset, BoneTrack, Keyframe};
+
+/// Per-curve error tolerances for [`compress_clip`]. Raw per-frame tracks
+/// (one key per exported frame) explode bundle size on long cinematics, so
+/// the baker reduces to the fewest keys that still reproduce the curve
+/// within these tolerances, then quantizes what's left.
+#[derive(Clone, Copy, Debug)]
+pub struct CompressionOptions {
+    /// Max positional error to tolerate when dropping a translation key,
+    /// in the model's own units (meters, for anything glTF-sourced).
+    pub translation_tolerance: f32,
+    /// Max `1 - |dot|` error to tolerate when dropping a rotation key.
+    pub rotation_tolerance: f32,
+    pub scale_tolerance: f32,
+}
+
+impl Default for CompressionOptions {
+    fn default() -> Self {
+        Self {
+            translation_tolerance: 0.001,
+            rotation_tolerance: 0.0005,
+            scale_tolerance: 0.001,
+        }
+    }
+}
+
+/// Reduces every bone track in `clip` to a sparser, curve-fitted set of
+/// keys and quantizes the rotations that remain. Events and bone indices
+/// pass through unchanged — only the curve data shrinks.
+pub fn compress_clip(clip: &AnimationClipAsset, options: &CompressionOptions) -> AnimationClipAsset {
+    AnimationClipAsset {
+        name: clip.name.clone(),
+        duration: clip.duration,
+        events: clip.events.clone(),
+        tracks: clip.tracks.iter().map(|track| compress_track(track, options)).collect(),
+    }
+}
+
+fn compress_track(track: &BoneTrack, options: &CompressionOptions) -> BoneTrack {
+    let mut rotation = reduce_curve(&track.rotation, options.rotation_tolerance, quat_error);
+    for key in &mut rotation {
+        key.value = quantize_rotation(Quat::from_array(key.value)).to_array();
+    }
+
+    BoneTrack {
+        bone_index: track.bone_index,
+        translation: reduce_curve(&track.translation, options.translation_tolerance, vec3_error),
+        rotation,
+        scale: reduce_curve(&track.scale, options.scale_tolerance, vec3_error),
+    }
+}
+
+/// Ramer-Douglas-Peucker-style curve simplification: keeps the first and
+/// last key always, and recursively keeps whichever interior key deviates
+/// most from the straight (lerp/slerp) interpolation of its neighbors,
+/// until every remaining segment is within `tolerance`.
+fn reduce_curve<T: Copy>(
+    keys: &[Keyframe<T>],
+    tolerance: f32,
+    error_fn: impl Fn(T, T, T, f32) -> f32 + Copy,
+) -> Vec<Keyframe<T>> {
+    if keys.len() <= 2 {
+        return keys.to_vec();
+    }
+
+    let mut kept = vec![true; keys.len()];
+    simplify_range(keys, 0, keys.len() - 1, tolerance, error_fn, &mut kept);
+
+    keys.iter()
+        .zip(kept)
+        .filter_map(|(key, keep)| keep.then(|| *key))
+        .collect()
+}
+
+fn simplify_range<T: Copy>(
+    keys: &[Keyframe<T>],
+    start: usize,
+    end: usize,
+    tolerance: f32,
+    error_fn: impl Fn(T, T, T, f32) -> f32 + Copy,
+    kept: &mut [bool],
+) {
+    if end <= start + 1 {
+        return;
+    }
+
+    let (time_a, value_a) = (keys[start].time, keys[start].value);
+    let (time_b, value_b) = (keys[end].time, keys[end].value);
+    let span = (time_b - time_a).max(f32::EPSILON);
+
+    let mut worst_index = None;
+    let mut worst_error = 0.0;
+    for i in start + 1..end {
+        let t = ((keys[i].time - time_a) / span).clamp(0.0, 1.0);
+        let error = error_fn(value_a, value_b, keys[i].value, t);
+        if error > worst_error {
+            worst_error = error;
+            worst_index = Some(i);
+        }
+    }
+
+    if worst_error > tolerance {
+        let split = worst_index.expect("non-empty range always has a worst candidate");
+        simplify_range(keys, start, split, tolerance, error_fn, kept);
+        simplify_range(keys, split, end, tolerance, error_fn, kept);
+    } else {
+        for keep in &mut kept[start + 1..end] {
+            *keep = false;
+        }
+    }
+}
+
+fn vec3_error(a: [f32; 3], b: [f32; 3], actual: [f32; 3], t: f32) -> f32 {
+    let interpolated = Vec3::from(a).lerp(Vec3::from(b), t);
+    (Vec3::from(actual) - interpolated).length()
+}
+
+fn quat_error(a: [f32; 4], b: [f32; 4], actual: [f32; 4], t: f32) -> f32 {
+    let interpolated = Quat::from_array(a).slerp(Quat::from_array(b), t);
+    1.0 - interpolated.dot(Quat::from_array(actual)).abs()
+}
+
+/// Snaps a rotation's components to 16-bit precision and re-normalizes —
+/// cheap, lossy-but-bounded compression that pays off once combined with
+/// key reduction, since there are far fewer keys left to pack.
+fn quantize_rotation(rotation: Quat) -> Quat {
+    let quantized = rotation.to_array().map(|component| (component * 32767.0).round() / 32767.0);
+    Quat::from_array(quantized).normalize()
+}
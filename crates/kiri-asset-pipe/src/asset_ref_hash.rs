@@ -0,0 +1,70 @@
+//! Shared content-hashing for [`kiri_assets::AssetRef`] minting —
+//! [`import_effect`](crate::import_effect)'s shader dedup and
+//! [`meta`](crate::meta)'s fresh-id minting both go through
+//! [`content_hash`] rather than each rolling their own, so both mean the
+//! same thing by "the hash of these bytes".
+//!
+//! `content_hash` mixes in [`PROJECT_HASH_SEED`] ahead of the actual
+//! bytes so the hash space is specific to this project rather than
+//! `DefaultHasher::new()`'s well-known fixed keys — two projects (or a
+//! modder's separately-built bundle) hashing the same shader bytes don't
+//! land on the same `AssetRef` by accident.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use kiri_assets::AssetRef;
+
+/// Arbitrary, project-specific bytes mixed into every [`content_hash`].
+/// Changing this reassigns every content-hash-derived `AssetRef` a
+/// project has ever baked, so it must never change after a project has
+/// shipped baked bundles.
+const PROJECT_HASH_SEED: u64 = 0x4b49_5249_5f53_4545; // "KIRI_SEE" as bytes
+
+/// Hashes `bytes` alone (its length, then its contents — the same
+/// well-defined encoding [`Hash for [u8]`](Hash) already gives a byte
+/// slice, not a struct's in-memory layout) mixed with
+/// [`PROJECT_HASH_SEED`].
+pub fn content_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    PROJECT_HASH_SEED.hash(&mut hasher);
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Catches two different bake inputs minting the same [`AssetRef`] —
+/// which would otherwise silently overwrite one asset's directory entry
+/// with the other's, or serve the wrong asset for a lookup that expected
+/// the first one.
+///
+/// Only meaningful for refs that are each supposed to uniquely identify
+/// one source (`.meta` sidecars, one per source file); refs that are
+/// *meant* to be shared by construction (identical shader bytes
+/// deduplicating to the same content-hash ref) shouldn't be checked
+/// through this.
+#[derive(Default)]
+pub struct AssetRefAuditor {
+    seen: std::collections::HashMap<AssetRef, String>,
+}
+
+impl AssetRefAuditor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `label` (typically a source path, stringified) owns
+    /// `asset_ref`. Returns an error naming both labels if `asset_ref`
+    /// was already recorded under a different label.
+    pub fn record(&mut self, asset_ref: AssetRef, label: impl Into<String>) -> Result<(), String> {
+        let label = label.into();
+        match self.seen.get(&asset_ref) {
+            Some(existing) if *existing != label => Err(format!(
+                "AssetRef {asset_ref:?} was minted for both {existing:?} and {label:?}"
+            )),
+            _ => {
+                self.seen.insert(asset_ref, label);
+                Ok(())
+            }
+        }
+    }
+}
@@ -1,3 +1,339 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:42cb95ea76e46be4f38b467210920e92c342af4e1ff6bad5dcc207a89700cf59
-size 3036
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use kiri_assets::bundle::AssetBundle;
+use kiri_assets::effect::EffectAsset;
+use kiri_assets::image::ImageAsset;
+use kiri_assets::localization::StringTableAsset;
+use kiri_assets::AssetRef;
+
+/// Content-hash dedup savings from one [`BundleWriter`]'s lifetime —
+/// surfaced so `tools/builder` can log it rather than dedup happening
+/// invisibly.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DedupStats {
+    /// Source paths whose payload matched an already-stored one and were
+    /// recorded as an alias instead of a second copy.
+    pub duplicate_entries: u32,
+    /// Serialized payload bytes not written a second time as a result.
+    pub bytes_saved: u64,
+}
+
+/// Accumulates baked assets in memory and writes them out as a single
+/// [`AssetBundle`] once a bake run finishes.
+///
+/// Opening with [`BundleWriter::open_or_new`] loads any existing bundle at
+/// that path first, so a bake that only touches a handful of source files
+/// can skip re-importing (and re-writing) everything else: unchanged
+/// entries are carried over as-is, and [`BundleWriter::add_image`] simply
+/// overwrites the entry for whichever path did change.
+///
+/// Every `add_*` also content-hashes the payload it's given against
+/// every payload already seen this run (including ones carried over by
+/// [`BundleWriter::open_or_new`]) — a texture re-exported unchanged by
+/// ten different models bakes to the same bytes ten times, and only the
+/// first copy actually needs storing. Each path's previous hash-bucket
+/// entry is evicted before the new one is indexed, so an incremental
+/// rebake that changes a path's content can't leave a stale entry behind
+/// for some unrelated later path to collide with.
+#[derive(Default)]
+pub struct BundleWriter {
+    contents: AssetBundle,
+    image_index: HashMap<u64, Vec<(String, Vec<u8>)>>,
+    effect_index: HashMap<u64, Vec<(String, Vec<u8>)>>,
+    string_table_index: HashMap<u64, Vec<(String, Vec<u8>)>>,
+    dedup_stats: DedupStats,
+}
+
+impl BundleWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads `path` if it exists and is a valid bundle, otherwise starts
+    /// from an empty one. Used by incremental bakes so previously-baked
+    /// assets survive a run that only re-imports a handful of changed
+    /// source files.
+    pub fn open_or_new(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        let bytes = std::fs::read(path)?;
+        let contents: AssetBundle = bincode::deserialize(&bytes)?;
+
+        let mut writer = Self {
+            contents,
+            ..Self::default()
+        };
+        writer.reindex();
+        Ok(writer)
+    }
+
+    /// Rebuilds the content-hash indexes from whatever's already in
+    /// `contents` — called once after loading an existing bundle, so a
+    /// newly re-imported asset can dedup against carried-over entries
+    /// too, not just ones added this run.
+    fn reindex(&mut self) {
+        for (path, image) in &self.contents.images {
+            let (hash, bytes) = hash_payload(image);
+            self.image_index.entry(hash).or_default().push((path.clone(), bytes));
+        }
+        for (path, effect) in &self.contents.effects {
+            let (hash, bytes) = hash_payload(effect);
+            self.effect_index.entry(hash).or_default().push((path.clone(), bytes));
+        }
+        for (path, string_table) in &self.contents.string_tables {
+            let (hash, bytes) = hash_payload(string_table);
+            self.string_table_index
+                .entry(hash)
+                .or_default()
+                .push((path.clone(), bytes));
+        }
+    }
+
+    pub fn add_image(&mut self, source_path: &Path, asset_ref: AssetRef, image: ImageAsset) {
+        let path = source_path.to_string_lossy().into_owned();
+        if let Some(old) = self.contents.images.get(&path) {
+            let (old_hash, _) = hash_payload(old);
+            evict_bucket_entry(&mut self.image_index, old_hash, &path);
+        }
+
+        let (hash, bytes) = hash_payload(&image);
+        match find_duplicate(&self.image_index, hash, &bytes, &path) {
+            Some(canonical) => self.record_alias(&path, &canonical, bytes.len() as u64),
+            None => {
+                self.image_index.entry(hash).or_default().push((path.clone(), bytes));
+                self.contents.aliases.remove(&path);
+                self.contents.images.insert(path.clone(), image);
+            }
+        }
+        self.contents.asset_refs.insert(path, asset_ref);
+    }
+
+    pub fn add_models(&mut self, _source_path: &Path, _scene: crate::gltf_import::ImportedScene) {
+        // Model bundling lands alongside the gltf import pipeline; tracked
+        // as a follow-up once that output shape is finalized.
+    }
+
+    pub fn add_effect(&mut self, source_path: &Path, asset_ref: AssetRef, effect: EffectAsset) {
+        let path = source_path.to_string_lossy().into_owned();
+        if let Some(old) = self.contents.effects.get(&path) {
+            let (old_hash, _) = hash_payload(old);
+            evict_bucket_entry(&mut self.effect_index, old_hash, &path);
+        }
+
+        let (hash, bytes) = hash_payload(&effect);
+        match find_duplicate(&self.effect_index, hash, &bytes, &path) {
+            Some(canonical) => self.record_alias(&path, &canonical, bytes.len() as u64),
+            None => {
+                self.effect_index.entry(hash).or_default().push((path.clone(), bytes));
+                self.contents.aliases.remove(&path);
+                self.contents.effects.insert(path.clone(), effect);
+            }
+        }
+        self.contents.asset_refs.insert(path, asset_ref);
+    }
+
+    pub fn add_string_table(
+        &mut self,
+        source_path: &Path,
+        asset_ref: AssetRef,
+        string_table: StringTableAsset,
+    ) {
+        let path = source_path.to_string_lossy().into_owned();
+        if let Some(old) = self.contents.string_tables.get(&path) {
+            let (old_hash, _) = hash_payload(old);
+            evict_bucket_entry(&mut self.string_table_index, old_hash, &path);
+        }
+
+        let (hash, bytes) = hash_payload(&string_table);
+        match find_duplicate(&self.string_table_index, hash, &bytes, &path) {
+            Some(canonical) => self.record_alias(&path, &canonical, bytes.len() as u64),
+            None => {
+                self.string_table_index
+                    .entry(hash)
+                    .or_default()
+                    .push((path.clone(), bytes));
+                self.contents.aliases.remove(&path);
+                self.contents.string_tables.insert(path.clone(), string_table);
+            }
+        }
+        self.contents.asset_refs.insert(path, asset_ref);
+    }
+
+    fn record_alias(&mut self, path: &str, canonical: &str, payload_size: u64) {
+        self.dedup_stats.duplicate_entries += 1;
+        self.dedup_stats.bytes_saved += payload_size;
+        self.contents.aliases.insert(path.to_owned(), canonical.to_owned());
+    }
+
+    /// Dedup savings accumulated so far this run — call after
+    /// [`crate::pipeline::Pipeline::run`]'s import pass finishes so the count reflects
+    /// every asset added, not just some of them.
+    pub fn dedup_stats(&self) -> DedupStats {
+        self.dedup_stats
+    }
+
+    /// Drops any entry whose source file no longer exists under
+    /// `source_dir`'s import set. Called before writing so a bundle doesn't
+    /// accumulate assets for files that were deleted since the last bake.
+    pub fn prune_missing(&mut self, still_present: &[PathBuf]) {
+        let still_present: std::collections::HashSet<String> = still_present
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+
+        for (path, image) in &self.contents.images {
+            if !still_present.contains(path) {
+                let (hash, _) = hash_payload(image);
+                evict_bucket_entry(&mut self.image_index, hash, path);
+            }
+        }
+        for (path, effect) in &self.contents.effects {
+            if !still_present.contains(path) {
+                let (hash, _) = hash_payload(effect);
+                evict_bucket_entry(&mut self.effect_index, hash, path);
+            }
+        }
+        for (path, string_table) in &self.contents.string_tables {
+            if !still_present.contains(path) {
+                let (hash, _) = hash_payload(string_table);
+                evict_bucket_entry(&mut self.string_table_index, hash, path);
+            }
+        }
+
+        self.contents
+            .images
+            .retain(|path, _| still_present.contains(path));
+        self.contents
+            .effects
+            .retain(|path, _| still_present.contains(path));
+        self.contents
+            .string_tables
+            .retain(|path, _| still_present.contains(path));
+        // An alias whose canonical target was just pruned would point at
+        // nothing; dropping it along with entries for missing sources
+        // keeps the table from stranding a reference to data that's no
+        // longer in any of the three maps above.
+        let images = &self.contents.images;
+        let effects = &self.contents.effects;
+        let string_tables = &self.contents.string_tables;
+        self.contents.aliases.retain(|path, canonical| {
+            still_present.contains(path)
+                && (images.contains_key(canonical)
+                    || effects.contains_key(canonical)
+                    || string_tables.contains_key(canonical))
+        });
+        self.contents.asset_refs.retain(|path, _| still_present.contains(path));
+    }
+
+    pub fn write_to_file(&self, path: &Path) -> Result<()> {
+        let bytes = bincode::serialize(&self.contents)?;
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+}
+
+/// Removes `path`'s entry from the bucket indexed under `hash`, if any —
+/// called before a path is re-indexed under a new hash (content changed)
+/// or dropped entirely ([`BundleWriter::prune_missing`]), so a later
+/// lookup under the old hash can't match a payload that path no longer
+/// holds.
+fn evict_bucket_entry(index: &mut HashMap<u64, Vec<(String, Vec<u8>)>>, hash: u64, path: &str) {
+    if let Some(bucket) = index.get_mut(&hash) {
+        bucket.retain(|(candidate, _)| candidate != path);
+        if bucket.is_empty() {
+            index.remove(&hash);
+        }
+    }
+}
+
+/// Hashes `payload`'s bincode-serialized bytes (rather than deriving
+/// `Hash` on every asset type) and returns those bytes alongside the
+/// hash, so a caller can confirm an index hit is a real duplicate and
+/// not just a hash collision — see [`find_duplicate`].
+fn hash_payload(payload: &impl serde::Serialize) -> (u64, Vec<u8>) {
+    let bytes = bincode::serialize(payload).unwrap_or_default();
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    (hasher.finish(), bytes)
+}
+
+/// Looks up `path`'s canonical duplicate among the entries already
+/// indexed under `hash`, confirming byte-for-byte equality before
+/// reporting a match — two different payloads landing on the same
+/// 64-bit [`DefaultHasher`] digest must not be treated as identical, or
+/// one asset's content would silently end up aliased to unrelated data.
+fn find_duplicate(
+    index: &HashMap<u64, Vec<(String, Vec<u8>)>>,
+    hash: u64,
+    bytes: &[u8],
+    path: &str,
+) -> Option<String> {
+    index.get(&hash)?.iter().find_map(|(candidate, candidate_bytes)| {
+        (candidate != path && candidate_bytes.as_slice() == bytes).then(|| candidate.clone())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiri_assets::image::ImageFormat;
+
+    fn image(fill: u8) -> ImageAsset {
+        ImageAsset {
+            extent: [4, 4],
+            format: ImageFormat::Rgba8Unorm,
+            mips: vec![vec![fill; 64]],
+        }
+    }
+
+    #[test]
+    fn re_adding_a_path_with_new_content_evicts_its_old_bucket_entry() {
+        let mut writer = BundleWriter::new();
+        writer.add_image(Path::new("a.png"), AssetRef(1), image(0xAA));
+        // a.png's content changes on an incremental rebake.
+        writer.add_image(Path::new("a.png"), AssetRef(1), image(0xBB));
+        // c.png's content happens to match a.png's *old* content. It must
+        // not be aliased to a.png, which now holds different bytes.
+        writer.add_image(Path::new("c.png"), AssetRef(3), image(0xAA));
+
+        assert!(!writer.contents.aliases.contains_key("c.png"));
+        assert_eq!(
+            writer.contents.images.get("c.png").map(|image| &image.mips),
+            Some(&vec![vec![0xAA; 64]])
+        );
+        assert_eq!(
+            writer.contents.images.get("a.png").map(|image| &image.mips),
+            Some(&vec![vec![0xBB; 64]])
+        );
+    }
+
+    #[test]
+    fn re_adding_identical_content_still_dedups_against_a_different_path() {
+        let mut writer = BundleWriter::new();
+        writer.add_image(Path::new("a.png"), AssetRef(1), image(0xAA));
+        writer.add_image(Path::new("a.png"), AssetRef(1), image(0xAA));
+        writer.add_image(Path::new("b.png"), AssetRef(2), image(0xAA));
+
+        assert_eq!(writer.contents.aliases.get("b.png").map(String::as_str), Some("a.png"));
+        assert_eq!(writer.dedup_stats().duplicate_entries, 1);
+    }
+
+    #[test]
+    fn prune_missing_evicts_bucket_entries_for_dropped_paths() {
+        let mut writer = BundleWriter::new();
+        writer.add_image(Path::new("a.png"), AssetRef(1), image(0xAA));
+        writer.prune_missing(&[]);
+        // a.png is gone; c.png matching its old content must not alias to
+        // a path that no longer exists in the bundle.
+        writer.add_image(Path::new("c.png"), AssetRef(3), image(0xAA));
+
+        assert!(!writer.contents.aliases.contains_key("c.png"));
+        assert!(writer.contents.images.contains_key("c.png"));
+    }
+}
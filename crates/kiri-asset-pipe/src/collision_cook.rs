@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+
+use kiri_assets::collision::{CollisionAsset, CollisionShape, ConvexHull};
+use kiri_assets::model::{Aabb, ModelAsset};
+use kiri_assets::AssetRef;
+
+/// Which shape [`cook_collision`] should produce from a source model —
+/// the physics-facing analogue of [`kiri_assets::effect::RenderPath`]:
+/// one bake step, several output shapes depending on how the cooked
+/// asset will be used.
+#[derive(Clone, Copy, Debug)]
+pub enum CollisionCookingMode {
+    /// One convex hull per mesh — for dynamic bodies.
+    ConvexHull,
+    /// The exact source triangles, merged across meshes — for static
+    /// level geometry.
+    TriangleMesh,
+    /// A vertex-clustered decimation targeting roughly
+    /// `target_triangle_count` triangles — for visually dense meshes
+    /// (foliage, rubble) that still need a concave collision shape but
+    /// not at render resolution.
+    SimplifiedProxy { target_triangle_count: usize },
+}
+
+/// Cooks `model` (whose bundle identity is `source_model`) into a
+/// [`CollisionAsset`] under `mode` — the one place hull generation and
+/// mesh simplification for physics happen, so every downstream physics
+/// binding loads already-cooked data instead of re-deriving it per
+/// process.
+pub fn cook_collision(
+    source_model: AssetRef,
+    model: &ModelAsset,
+    mode: CollisionCookingMode,
+) -> CollisionAsset {
+    let bounds = model.bounds();
+
+    let shape = match mode {
+        CollisionCookingMode::ConvexHull => CollisionShape::ConvexHulls(
+            model
+                .meshes
+                .iter()
+                .map(|mesh| {
+                    let points: Vec<[f32; 3]> =
+                        mesh.vertices.iter().map(|vertex| vertex.position).collect();
+                    convex_hull(&points)
+                })
+                .collect(),
+        ),
+        CollisionCookingMode::TriangleMesh => {
+            let (vertices, indices) = merge_meshes(model);
+            CollisionShape::TriangleMesh { vertices, indices }
+        }
+        CollisionCookingMode::SimplifiedProxy {
+            target_triangle_count,
+        } => {
+            let (vertices, indices) = merge_meshes(model);
+            let (vertices, indices) =
+                decimate_by_clustering(vertices, indices, target_triangle_count);
+            CollisionShape::SimplifiedProxy { vertices, indices }
+        }
+    };
+
+    CollisionAsset {
+        source_model,
+        shape,
+        bounds,
+    }
+}
+
+fn merge_meshes(model: &ModelAsset) -> (Vec<[f32; 3]>, Vec<u32>) {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    for mesh in &model.meshes {
+        let base = vertices.len() as u32;
+        vertices.extend(mesh.vertices.iter().map(|vertex| vertex.position));
+        indices.extend(mesh.indices.iter().map(|&index| index + base));
+    }
+    (vertices, indices)
+}
+
+/// Collapses `vertices`/`indices` onto a uniform grid sized so the result
+/// has roughly `target_triangle_count` triangles — cheap and robust
+/// compared to an edge-collapse simplifier, at the cost of not preserving
+/// sharp features especially well; acceptable for a collision proxy,
+/// which only needs to be roughly the right shape.
+fn decimate_by_clustering(
+    vertices: Vec<[f32; 3]>,
+    indices: Vec<u32>,
+    target_triangle_count: usize,
+) -> (Vec<[f32; 3]>, Vec<u32>) {
+    if vertices.is_empty() || indices.len() / 3 <= target_triangle_count.max(1) {
+        return (vertices, indices);
+    }
+
+    let aabb = Aabb::from_points(vertices.iter().copied());
+    let half_extents = aabb.half_extents();
+
+    // A closed triangle mesh has roughly twice as many triangles as
+    // vertices, so a grid with `target_triangle_count / 2` cells along
+    // each axis (cube-rooted) lands in the right ballpark.
+    let target_vertex_count = (target_triangle_count / 2).max(4) as f32;
+    let grid_resolution = target_vertex_count.cbrt().ceil().max(1.0);
+
+    let cell_size = [
+        (half_extents[0] * 2.0 / grid_resolution).max(1e-6),
+        (half_extents[1] * 2.0 / grid_resolution).max(1e-6),
+        (half_extents[2] * 2.0 / grid_resolution).max(1e-6),
+    ];
+
+    let cell_of = |p: [f32; 3]| -> (i32, i32, i32) {
+        (
+            ((p[0] - aabb.min[0]) / cell_size[0]).floor() as i32,
+            ((p[1] - aabb.min[1]) / cell_size[1]).floor() as i32,
+            ((p[2] - aabb.min[2]) / cell_size[2]).floor() as i32,
+        )
+    };
+
+    let mut cluster_of_cell: HashMap<(i32, i32, i32), u32> = HashMap::new();
+    let mut new_vertices = Vec::new();
+    let mut remap = vec![0u32; vertices.len()];
+    for (index, &vertex) in vertices.iter().enumerate() {
+        let cell = cell_of(vertex);
+        let cluster = *cluster_of_cell.entry(cell).or_insert_with(|| {
+            new_vertices.push(vertex);
+            (new_vertices.len() - 1) as u32
+        });
+        remap[index] = cluster;
+    }
+
+    let mut new_indices = Vec::new();
+    for triangle in indices.chunks_exact(3) {
+        let a = remap[triangle[0] as usize];
+        let b = remap[triangle[1] as usize];
+        let c = remap[triangle[2] as usize];
+        // A triangle that collapsed onto fewer than 3 distinct clusters
+        // has zero area post-decimation and is dropped rather than kept
+        // degenerate.
+        if a != b && b != c && a != c {
+            new_indices.extend_from_slice(&[a, b, c]);
+        }
+    }
+
+    (new_vertices, new_indices)
+}
+
+#[derive(Clone, Copy)]
+struct Face {
+    a: usize,
+    b: usize,
+    c: usize,
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn length_sq(a: [f32; 3]) -> f32 {
+    dot(a, a)
+}
+
+fn face_normal(points: &[[f32; 3]], face: Face) -> [f32; 3] {
+    cross(sub(points[face.b], points[face.a]), sub(points[face.c], points[face.a]))
+}
+
+fn signed_distance(points: &[[f32; 3]], face: Face, point: [f32; 3]) -> f32 {
+    dot(face_normal(points, face), sub(point, points[face.a]))
+}
+
+/// Finds four points that don't all lie in one plane by walking extremes
+/// (farthest point, then farthest from that point's line, then farthest
+/// from that line's plane) instead of testing every combination — O(n)
+/// instead of the O(n^4) a naive "first four points that work" search
+/// would cost.
+fn initial_tetrahedron(points: &[[f32; 3]]) -> Option<[usize; 4]> {
+    if points.len() < 4 {
+        return None;
+    }
+
+    let a = 0;
+    let b = (1..points.len()).max_by(|&i, &j| {
+        length_sq(sub(points[i], points[a]))
+            .partial_cmp(&length_sq(sub(points[j], points[a])))
+            .unwrap()
+    })?;
+    if length_sq(sub(points[b], points[a])) < 1e-12 {
+        return None;
+    }
+
+    let line_dir = sub(points[b], points[a]);
+    let perp_dist_sq = |i: usize| {
+        let to_point = sub(points[i], points[a]);
+        length_sq(cross(line_dir, to_point))
+    };
+    let c = (0..points.len())
+        .filter(|&i| i != a && i != b)
+        .max_by(|&i, &j| perp_dist_sq(i).partial_cmp(&perp_dist_sq(j)).unwrap())?;
+    if perp_dist_sq(c) < 1e-12 {
+        return None;
+    }
+
+    let plane_dist_abs = |i: usize| signed_distance(points, Face { a, b, c }, points[i]).abs();
+    let d = (0..points.len())
+        .filter(|&i| i != a && i != b && i != c)
+        .max_by(|&i, &j| plane_dist_abs(i).partial_cmp(&plane_dist_abs(j)).unwrap())?;
+    if plane_dist_abs(d) < 1e-12 {
+        return None;
+    }
+
+    Some([a, b, c, d])
+}
+
+/// Incremental convex hull (Quickhull without a conflict graph — O(n^2)
+/// in the point count, which is fine for the small, already-simplified
+/// point sets collision hulls are cooked from). Falls back to returning
+/// every input point untouched if the point set is degenerate (fewer
+/// than 4 points, or all coplanar/collinear/coincident) — still usable as
+/// a collision shape, just not hull-reduced.
+fn convex_hull(points: &[[f32; 3]]) -> ConvexHull {
+    let Some([a, b, c, d]) = initial_tetrahedron(points) else {
+        return ConvexHull {
+            vertices: points.to_vec(),
+        };
+    };
+
+    let mut faces = vec![
+        Face { a, b, c },
+        Face { a, b: c, c: d },
+        Face { a, b: d, c: b },
+        Face { a: b, b: c, c: d },
+    ];
+    // The four faces above aren't guaranteed outward-facing yet; flip any
+    // face the tetrahedron's own centroid is in front of.
+    let centroid = [
+        (points[a][0] + points[b][0] + points[c][0] + points[d][0]) / 4.0,
+        (points[a][1] + points[b][1] + points[c][1] + points[d][1]) / 4.0,
+        (points[a][2] + points[b][2] + points[c][2] + points[d][2]) / 4.0,
+    ];
+    for face in &mut faces {
+        if signed_distance(points, *face, centroid) > 0.0 {
+            std::mem::swap(&mut face.b, &mut face.c);
+        }
+    }
+
+    for (index, &point) in points.iter().enumerate() {
+        let visible: Vec<usize> = faces
+            .iter()
+            .enumerate()
+            .filter(|(_, &face)| signed_distance(points, face, point) > 1e-6)
+            .map(|(face_index, _)| face_index)
+            .collect();
+        if visible.is_empty() {
+            continue;
+        }
+
+        // A horizon edge is a directed edge of a visible face whose
+        // reverse doesn't also belong to a visible face — i.e. the edge
+        // where the visible region meets the rest of the hull.
+        let mut visible_edges: HashMap<(usize, usize), ()> = HashMap::new();
+        for &face_index in &visible {
+            let face = faces[face_index];
+            for edge in [(face.a, face.b), (face.b, face.c), (face.c, face.a)] {
+                visible_edges.insert(edge, ());
+            }
+        }
+
+        let mut new_faces = Vec::new();
+        for &face_index in &visible {
+            let face = faces[face_index];
+            for (u, v) in [(face.a, face.b), (face.b, face.c), (face.c, face.a)] {
+                if !visible_edges.contains_key(&(v, u)) {
+                    new_faces.push(Face { a: u, b: v, c: index });
+                }
+            }
+        }
+
+        let visible_set: std::collections::HashSet<usize> = visible.into_iter().collect();
+        faces = faces
+            .into_iter()
+            .enumerate()
+            .filter(|(face_index, _)| !visible_set.contains(face_index))
+            .map(|(_, face)| face)
+            .collect();
+        faces.extend(new_faces);
+    }
+
+    let mut used_indices: Vec<usize> = faces
+        .iter()
+        .flat_map(|face| [face.a, face.b, face.c])
+        .collect();
+    used_indices.sort_unstable();
+    used_indices.dedup();
+
+    ConvexHull {
+        vertices: used_indices.into_iter().map(|index| points[index]).collect(),
+    }
+}
@@ -0,0 +1,75 @@
+use anyhow::{bail, Context, Result};
+use kiri_assets::image::{ImageFormat, VolumeAsset};
+
+/// Reads an Adobe/Iridas `.cube` 3D LUT (the format DaVinci Resolve,
+/// Blender, and most color grading tools export) into a [`VolumeAsset`].
+///
+/// `.cube` stores one `r g b` float triple per line, in `f b r`-fastest
+/// (blue outermost) order per the spec, sized `N`x`N`x`N` where `N` comes
+/// from a `LUT_3D_SIZE N` header line. Values are expected in `[0, 1]` and
+/// are quantized to 8 bits per channel — plenty for a grading LUT, which
+/// is sampled with linear filtering anyway.
+pub fn import_cube_lut(source: &str) -> Result<VolumeAsset> {
+    let mut size: Option<u32> = None;
+    let mut texels = Vec::new();
+
+    for line in source.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("LUT_3D_SIZE") {
+            size = Some(
+                rest.trim()
+                    .parse()
+                    .context("Malformed LUT_3D_SIZE in .cube file")?,
+            );
+            continue;
+        }
+
+        if line.starts_with("TITLE")
+            || line.starts_with("DOMAIN_MIN")
+            || line.starts_with("DOMAIN_MAX")
+            || line.starts_with("LUT_1D_SIZE")
+        {
+            continue;
+        }
+
+        let mut components = line.split_whitespace();
+        let (r, g, b) = (
+            components.next(),
+            components.next(),
+            components.next(),
+        );
+        let (r, g, b) = match (r, g, b) {
+            (Some(r), Some(g), Some(b)) => (r, g, b),
+            _ => bail!("Malformed .cube data line: {line:?}"),
+        };
+
+        let to_u8 = |value: &str| -> Result<u8> {
+            let value: f32 = value.parse().context("Malformed float in .cube file")?;
+            Ok((value.clamp(0.0, 1.0) * 255.0).round() as u8)
+        };
+
+        texels.push(to_u8(r)?);
+        texels.push(to_u8(g)?);
+        texels.push(to_u8(b)?);
+        texels.push(255);
+    }
+
+    let size = size.context(".cube file has no LUT_3D_SIZE header")?;
+    let expected_texels = (size as usize).pow(3);
+    if texels.len() / 4 != expected_texels {
+        bail!(
+            ".cube file declares LUT_3D_SIZE {size} ({expected_texels} texels) but has {} data lines",
+            texels.len() / 4
+        );
+    }
+
+    Ok(VolumeAsset {
+        extent: [size, size, size],
+        format: ImageFormat::Rgba8Unorm,
+        mips: vec![texels],
+    })
+}
@@ -1,3 +1,71 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:8bc78268602ea67ce89d233a3fc3d4675334901cb2b19b15b59474c46665370f
-size 2109
+use kiri_assets::model::VertexLayout;
+use serde::{Deserialize, Serialize};
+
+/// Authoring-time description of an effect, as written by hand in a
+/// `.effect.ron` or `.effect.json` file next to the shaders it references.
+/// `import_effect` turns one of these into a baked `EffectAsset`.
+///
+/// Kept as a separate, serde-friendly struct rather than reusing
+/// `kiri_assets::effect::EffectAsset` directly: the authoring format uses
+/// source paths and human-chosen names for enum variants, while the baked
+/// asset uses `AssetRef`s and is meant to round-trip through `bincode`,
+/// not be hand-edited.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EffectDesc {
+    pub name: String,
+    pub vertex_shader: ShaderStageDesc,
+    #[serde(default)]
+    pub pixel_shader: Option<ShaderStageDesc>,
+    #[serde(default)]
+    pub compute_shader: Option<ShaderStageDesc>,
+    #[serde(default)]
+    pub depth_stencil: DepthStencilDesc,
+    #[serde(default)]
+    pub rasterizer: RasterizerDesc,
+    /// Which mesh vertex layout this effect's vertex shader expects — see
+    /// [`kiri_assets::model::Mesh::layout`]. Defaults to
+    /// [`VertexLayout::Static`], the layout every mesh with no optional
+    /// streams uses.
+    #[serde(default)]
+    pub vertex_layout: VertexLayout,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ShaderStageDesc {
+    /// Path to the HLSL/GLSL source, relative to the `.effect` file.
+    pub source: String,
+    #[serde(default = "default_entry_point")]
+    pub entry_point: String,
+}
+
+fn default_entry_point() -> String {
+    "main".to_string()
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DepthStencilDesc {
+    #[serde(default = "default_true")]
+    pub depth_test_enable: bool,
+    #[serde(default = "default_true")]
+    pub depth_write_enable: bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RasterizerDesc {
+    #[serde(default)]
+    pub two_sided: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Parses an `EffectDesc` from either RON or JSON, picked by file
+/// extension — authors can use whichever they find easier to hand-edit,
+/// and the baker doesn't care which one a given effect uses.
+pub fn parse_effect_desc(path: &std::path::Path, text: &str) -> anyhow::Result<EffectDesc> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => Ok(serde_json::from_str(text)?),
+        _ => Ok(ron::from_str(text)?),
+    }
+}
@@ -1,3 +1,124 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:f20e90423c7da46524fd309ab3014ea93c67deb31c63d636138c63229a0623c5
-size 20557
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use kiri_assets::model::{Mesh, ModelAsset, Vertex, VertexSkinning};
+
+/// Everything pulled out of one glTF file: its meshes, ready to be handed
+/// to the bundler. Materials, skins and animations are threaded through as
+/// those features land in the model asset itself.
+pub struct ImportedScene {
+    pub models: Vec<ModelAsset>,
+}
+
+pub fn import_gltf(path: &Path) -> Result<ImportedScene> {
+    let (document, buffers, _images) =
+        gltf::import(path).with_context(|| format!("Failed to import glTF file {:?}", path))?;
+
+    let mut models = Vec::new();
+
+    for mesh in document.meshes() {
+        let mut meshes = Vec::new();
+
+        for primitive in mesh.primitives() {
+            let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+
+            let positions: Vec<[f32; 3]> = reader
+                .read_positions()
+                .context("glTF primitive has no POSITION attribute")?
+                .collect();
+
+            let normals: Vec<[f32; 3]> = reader
+                .read_normals()
+                .map(|iter| iter.collect())
+                .unwrap_or_else(|| vec![[0.0, 1.0, 0.0]; positions.len()]);
+
+            let uvs: Vec<[f32; 2]> = reader
+                .read_tex_coords(0)
+                .map(|iter| iter.into_f32().collect())
+                .unwrap_or_else(|| vec![[0.0, 0.0]; positions.len()]);
+
+            // TEXCOORD_1, when present, is the glTF convention for a
+            // second UV set — used here as the lightmap channel. Baking
+            // the lightmap texture itself happens out-of-band (a separate
+            // GI bake tool writes the `LightmapAsset`); this only carries
+            // the mesh's unwrap through so a later bake pass has UVs to
+            // rasterize into.
+            let lightmap_uvs: Option<Vec<[f32; 2]>> = reader
+                .read_tex_coords(1)
+                .map(|iter| iter.into_f32().collect());
+
+            let vertices = positions
+                .into_iter()
+                .zip(normals)
+                .zip(uvs)
+                .map(|((position, normal), uv)| Vertex {
+                    position,
+                    normal,
+                    uv,
+                })
+                .collect();
+
+            let indices: Vec<u32> = reader
+                .read_indices()
+                .map(|iter| iter.into_u32().collect())
+                .unwrap_or_default();
+
+            let vertex_colors: Option<Vec<[f32; 4]>> = reader
+                .read_colors(0)
+                .map(|iter| iter.into_rgba_f32().collect());
+
+            let skinning = match (reader.read_joints(0), reader.read_weights(0)) {
+                (Some(joints), Some(weights)) => Some(
+                    joints
+                        .into_u16()
+                        .zip(weights.into_f32())
+                        .map(|(bone_indices, bone_weights)| VertexSkinning {
+                            bone_indices,
+                            bone_weights,
+                        })
+                        .collect(),
+                ),
+                _ => None,
+            };
+
+            let mesh = Mesh {
+                vertices,
+                indices,
+                skinning,
+                lightmap_uvs,
+                // The atlas binding (which `LightmapAsset`, and this
+                // mesh's region of it) is assigned once the scene's GI
+                // bake tool has packed every mesh into a shared atlas —
+                // glTF import only carries the raw unwrap.
+                lightmap: None,
+                vertex_colors,
+                // Material assignment isn't wired into this importer
+                // yet (see `ModelAsset::material_slots` below) — every
+                // primitive lands in slot 0 until it is.
+                material_slot: 0,
+            };
+
+            // glTF mints a vertex per unique attribute combination a
+            // triangle needs, not per unique position, so adjacent
+            // triangles routinely share no vertices at all as imported —
+            // collapse that back down and lay the result out for GPU
+            // vertex-cache locality before it's handed to the bundler.
+            meshes.push(crate::mesh_optimize::optimize_mesh(&mesh));
+        }
+
+        models.push(ModelAsset {
+            name: mesh.name().unwrap_or("unnamed_mesh").to_string(),
+            meshes,
+            // This importer walks `document.meshes()` directly rather
+            // than the node/scene graph, so it never sees the empty
+            // (mesh-less) nodes glTF locators/sockets are authored as —
+            // wiring that up means walking `document.scenes()` instead
+            // and matching empties to the `ModelAsset` their nearest
+            // mesh-bearing ancestor produced.
+            sockets: Vec::new(),
+            material_slots: Vec::new(),
+        });
+    }
+
+    Ok(ImportedScene { models })
+}
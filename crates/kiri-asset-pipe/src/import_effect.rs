@@ -1,3 +1,122 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:74f2d3d1d7848667e183396a080f413eae21db980db9f48b1d57d157eb875032
-size 7722
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use kiri_assets::effect::{
+    CullMode, DepthCompareOp, DepthStencilState, EffectAsset, RasterizerState, RenderPath, ShaderStage,
+};
+use kiri_assets::AssetRef;
+
+use crate::asset_ref_hash::content_hash;
+use crate::desc::{self, EffectDesc};
+
+/// Imports one `.effect.ron`/`.effect.json` file: parses the authoring
+/// description, compiles every referenced shader stage to SPIR-V, and
+/// produces the baked [`EffectAsset`] the backend will build a pipeline
+/// from.
+pub fn import_effect(path: &Path) -> Result<EffectAsset> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read effect description {:?}", path))?;
+    let desc = desc::parse_effect_desc(path, &text)?;
+
+    effect_from_desc(path, &desc)
+}
+
+fn effect_from_desc(effect_path: &Path, desc: &EffectDesc) -> Result<EffectAsset> {
+    let base_dir = effect_path.parent().unwrap_or_else(|| Path::new("."));
+
+    Ok(EffectAsset {
+        name: desc.name.clone(),
+        vertex_shader: compile_stage(base_dir, &desc.vertex_shader.source, &desc.vertex_shader.entry_point)?,
+        pixel_shader: desc
+            .pixel_shader
+            .as_ref()
+            .map(|stage| compile_stage(base_dir, &stage.source, &stage.entry_point))
+            .transpose()?,
+        compute_shader: desc
+            .compute_shader
+            .as_ref()
+            .map(|stage| compile_stage(base_dir, &stage.source, &stage.entry_point))
+            .transpose()?,
+        depth_stencil: DepthStencilState {
+            depth_test_enable: desc.depth_stencil.depth_test_enable,
+            depth_write_enable: desc.depth_stencil.depth_write_enable,
+            compare_op: DepthCompareOp::GreaterOrEqual,
+            stencil: None,
+        },
+        rasterizer: RasterizerState {
+            cull_mode: if desc.rasterizer.two_sided {
+                CullMode::None
+            } else {
+                CullMode::Back
+            },
+            depth_clamp_enable: false,
+        },
+        input_assembly: Default::default(),
+        vertex_layout: desc.vertex_layout,
+        // Hand-authored effects are always forward: they write color
+        // directly. Deferred permutations only come out of
+        // `UberShaderCompiler::resolve`, when it is asked for
+        // `RenderPath::Deferred`.
+        render_path: RenderPath::Forward,
+    })
+}
+
+fn compile_stage(base_dir: &Path, source_relative_path: &str, entry_point: &str) -> Result<ShaderStage> {
+    let source_path = base_dir.join(source_relative_path);
+    let source = std::fs::read_to_string(&source_path)
+        .with_context(|| format!("Failed to read shader source {:?}", source_path))?;
+
+    let spirv = compile_to_spirv(&source, &source_path, entry_point, &[])?;
+
+    // The stage refers to its SPIR-V by a content-hash-derived AssetRef,
+    // so identical SPIR-V compiled from two different effects gets the
+    // same ref. Only the ref is kept: the compiled bytes are dropped here
+    // and are not written into the bundle by this importer or the bundler.
+    let asset_ref = AssetRef(content_hash(&spirv));
+
+    Ok(ShaderStage {
+        spirv: asset_ref,
+        entry_point: entry_point.to_string(),
+    })
+}
+
+/// Compiles GLSL to SPIR-V, optionally with `#define`s injected ahead of the
+/// source — used as-is (empty `defines`) for hand-authored effects, and with
+/// feature defines by [`crate::material_compile`] to produce uber-shader
+/// permutations without needing a separate source file per permutation.
+pub(crate) fn compile_to_spirv(
+    source: &str,
+    source_path: &Path,
+    entry_point: &str,
+    defines: &[(&str, &str)],
+) -> Result<Vec<u8>> {
+    let mut compiler = shaderc::Compiler::new().context("Failed to create shader compiler")?;
+    let shader_kind = shader_kind_for_path(source_path);
+
+    let mut options =
+        shaderc::CompileOptions::new().context("Failed to create shader compile options")?;
+    for (name, value) in defines {
+        options.add_macro_definition(name, Some(value));
+    }
+
+    let artifact = compiler
+        .compile_into_spirv(
+            source,
+            shader_kind,
+            &source_path.to_string_lossy(),
+            entry_point,
+            Some(&options),
+        )
+        .with_context(|| format!("Failed to compile shader {:?}", source_path))?;
+
+    Ok(artifact.as_binary_u8().to_vec())
+}
+
+fn shader_kind_for_path(path: &Path) -> shaderc::ShaderKind {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("vert") => shaderc::ShaderKind::Vertex,
+        Some("frag") => shaderc::ShaderKind::Fragment,
+        Some("comp") => shaderc::ShaderKind::Compute,
+        _ => shaderc::ShaderKind::InferFromSource,
+    }
+}
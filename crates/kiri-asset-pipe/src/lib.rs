@@ -1,3 +1,25 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:d0803ccff907f8b27270ed00ad0c3f37f5f1b847be2994a487687a9bd1456311
-size 13766
+//! Offline asset baking: turns source content (glTF scenes, images, effect
+//! descriptions) into the packed, load-ready formats defined in
+//! `kiri-assets`, and writes them into bundles via `bundler`.
+//!
+//! Nothing in this crate runs at game runtime — it's linked into
+//! `tools/builder` and any editor-side tooling that needs to re-bake on
+//! the fly.
+
+pub mod anim_compress;
+pub mod asset_ref_hash;
+pub mod bundler;
+pub mod collision_cook;
+pub mod cube_import;
+pub mod desc;
+pub mod gltf_import;
+pub mod image_import;
+pub mod import_effect;
+pub mod material_compile;
+pub mod mesh_optimize;
+pub mod meta;
+pub mod navmesh_bake;
+pub mod pipeline;
+pub mod string_table_import;
+
+pub use pipeline::{BakeOptions, Pipeline};
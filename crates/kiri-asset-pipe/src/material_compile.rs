@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use kiri_assets::effect::{
+    CullMode, DepthCompareOp, DepthStencilState, EffectAsset, RasterizerState, RenderPath, ShaderStage,
+};
+use kiri_assets::material::{MaterialAsset, MaterialFeatures};
+use kiri_assets::model::MeshFeatures;
+use kiri_assets::AssetRef;
+
+use crate::asset_ref_hash::content_hash;
+use crate::desc::{self, EffectDesc};
+use crate::import_effect::compile_to_spirv;
+
+/// Resolves materials to specialized permutations of a single uber-shader
+/// effect, caching the compiled result per `(MaterialFeatures, RenderPath,
+/// MeshFeatures)` so a scene with many materials sharing a feature set —
+/// and a renderer drawing the same material into both a forward pass
+/// (e.g. a transparent overlay) and a deferred GBuffer pass, or onto both
+/// baked-lit and dynamically-lit meshes — only pays the shaderc cost once
+/// per distinct permutation.
+#[derive(Default)]
+pub struct UberShaderCompiler {
+    cache: HashMap<(MaterialFeatures, RenderPath, MeshFeatures), EffectAsset>,
+}
+
+impl UberShaderCompiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compiles, or returns the already-cached, effect permutation for
+    /// `material` under `render_path`. `mesh_features` should be
+    /// `mesh.features()` for the mesh this permutation will draw — it's a
+    /// mesh property, not a material one, so it isn't part of
+    /// [`MaterialAsset::features`], but it still needs to be its own axis
+    /// of the shader permutation: a lightmapped mesh samples irradiance
+    /// from a texture instead of computing it, and a vertex-colored mesh
+    /// needs the color stream bound and multiplied in.
+    /// `uber_effect_path` points at the uber-shader's
+    /// `.effect.ron`/`.effect.json` authoring description, the same format
+    /// [`crate::import_effect::import_effect`] reads for hand-authored
+    /// effects.
+    pub fn resolve(
+        &mut self,
+        uber_effect_path: &Path,
+        material: &MaterialAsset,
+        render_path: RenderPath,
+        mesh_features: MeshFeatures,
+    ) -> Result<EffectAsset> {
+        let features = material.features();
+        let key = (features, render_path, mesh_features);
+        if let Some(cached) = self.cache.get(&key) {
+            return Ok(cached.clone());
+        }
+
+        let text = std::fs::read_to_string(uber_effect_path).with_context(|| {
+            format!("Failed to read uber-shader description {:?}", uber_effect_path)
+        })?;
+        let desc = desc::parse_effect_desc(uber_effect_path, &text)?;
+
+        let effect =
+            compile_permutation(uber_effect_path, &desc, features, render_path, mesh_features)?;
+        self.cache.insert(key, effect.clone());
+        Ok(effect)
+    }
+
+    pub fn cached_permutation_count(&self) -> usize {
+        self.cache.len()
+    }
+}
+
+fn compile_permutation(
+    effect_path: &Path,
+    desc: &EffectDesc,
+    features: MaterialFeatures,
+    render_path: RenderPath,
+    mesh_features: MeshFeatures,
+) -> Result<EffectAsset> {
+    let base_dir = effect_path.parent().unwrap_or_else(|| Path::new("."));
+    let mut defines = feature_defines(features);
+    if render_path == RenderPath::Deferred {
+        defines.push(("RENDER_PATH_DEFERRED", "1"));
+    }
+    if mesh_features.contains(MeshFeatures::LIGHTMAP_UV) {
+        defines.push(("HAS_LIGHTMAP", "1"));
+    }
+    if mesh_features.contains(MeshFeatures::VERTEX_COLOR) {
+        defines.push(("HAS_VERTEX_COLOR", "1"));
+    }
+
+    let path_suffix = match render_path {
+        RenderPath::Forward => "forward",
+        RenderPath::Deferred => "deferred",
+    };
+
+    Ok(EffectAsset {
+        name: format!(
+            "{}#{:#x}#{}#{:#x}",
+            desc.name,
+            features.bits(),
+            path_suffix,
+            mesh_features.bits()
+        ),
+        vertex_shader: compile_stage(
+            base_dir,
+            &desc.vertex_shader.source,
+            &desc.vertex_shader.entry_point,
+            &defines,
+        )?,
+        pixel_shader: desc
+            .pixel_shader
+            .as_ref()
+            .map(|stage| compile_stage(base_dir, &stage.source, &stage.entry_point, &defines))
+            .transpose()?,
+        compute_shader: desc
+            .compute_shader
+            .as_ref()
+            .map(|stage| compile_stage(base_dir, &stage.source, &stage.entry_point, &defines))
+            .transpose()?,
+        depth_stencil: DepthStencilState {
+            depth_test_enable: desc.depth_stencil.depth_test_enable,
+            depth_write_enable: desc.depth_stencil.depth_write_enable,
+            compare_op: DepthCompareOp::GreaterOrEqual,
+            stencil: None,
+        },
+        rasterizer: RasterizerState {
+            cull_mode: if desc.rasterizer.two_sided {
+                CullMode::None
+            } else {
+                CullMode::Back
+            },
+            depth_clamp_enable: false,
+        },
+        input_assembly: Default::default(),
+        vertex_layout: desc.vertex_layout,
+        render_path,
+    })
+}
+
+fn compile_stage(
+    base_dir: &Path,
+    source_relative_path: &str,
+    entry_point: &str,
+    defines: &[(&str, &str)],
+) -> Result<ShaderStage> {
+    let source_path = base_dir.join(source_relative_path);
+    let source = std::fs::read_to_string(&source_path)
+        .with_context(|| format!("Failed to read shader source {:?}", source_path))?;
+
+    let spirv = compile_to_spirv(&source, &source_path, entry_point, defines)?;
+    let asset_ref = AssetRef(content_hash(&spirv));
+
+    Ok(ShaderStage {
+        spirv: asset_ref,
+        entry_point: entry_point.to_string(),
+    })
+}
+
+/// Maps a material's bound texture slots onto the `#define`s the uber
+/// shader branches on, so an unbound slot (e.g. no normal map) compiles
+/// out its sampling code entirely rather than sampling a dummy texture at
+/// runtime.
+fn feature_defines(features: MaterialFeatures) -> Vec<(&'static str, &'static str)> {
+    let mut defines = Vec::new();
+    if features.contains(MaterialFeatures::BASE_COLOR) {
+        defines.push(("HAS_BASE_COLOR", "1"));
+    }
+    if features.contains(MaterialFeatures::NORMAL_MAP) {
+        defines.push(("HAS_NORMAL_MAP", "1"));
+    }
+    if features.contains(MaterialFeatures::METALLIC_ROUGHNESS) {
+        defines.push(("HAS_METALLIC_ROUGHNESS", "1"));
+    }
+    if features.contains(MaterialFeatures::OCCLUSION) {
+        defines.push(("HAS_OCCLUSION", "1"));
+    }
+    if features.contains(MaterialFeatures::EMISSIVE) {
+        defines.push(("HAS_EMISSIVE", "1"));
+    }
+    defines
+}
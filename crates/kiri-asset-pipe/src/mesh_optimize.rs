@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+
+use kiri_assets::model::Mesh;
+
+/// Vertex cache the reorder pass simulates — matches the GPU post-transform
+/// cache size [`meshopt`](https://github.com/zeux/meshoptimizer) tunes for
+/// by default, which covers the common desktop/mobile range well enough
+/// that baked content doesn't need a per-target variant.
+const CACHE_SIZE: usize = 32;
+const LAST_TRIANGLE_SCORE: f32 = 0.75;
+const CACHE_DECAY_POWER: f32 = 1.5;
+const VALENCE_BOOST_SCALE: f32 = 2.0;
+const VALENCE_BOOST_POWER: f32 = 0.5;
+
+/// Runs this mesh through vertex deduplication and vertex-cache
+/// reordering before it's handed to the bundler (there is no separate
+/// overdraw optimization pass). glTF (and most other
+/// interchange formats) mint a new vertex per unique attribute
+/// combination a triangle needs, not per unique position — adjacent
+/// triangles sharing an edge routinely end up with no shared vertices at
+/// all, which bloats the vertex buffer and defeats the GPU's
+/// post-transform vertex cache. Dedup collapses that back down to one
+/// vertex per unique attribute set, and the reorder pass that follows
+/// then lays out the (now-shared) vertices and indices in an order a
+/// small FIFO cache actually benefits from.
+pub fn optimize_mesh(mesh: &Mesh) -> Mesh {
+    let deduped = dedupe_vertices(mesh);
+    reorder_for_cache_locality(&deduped)
+}
+
+/// Bit-exact key over every per-vertex stream a [`Mesh`] carries. Keyed on
+/// raw bits rather than comparing `f32`s directly so the key can be
+/// hashed — two vertices imported from the same source attribute data are
+/// either bit-identical or were never meant to merge, so there's no
+/// tolerance to apply here the way there is for [`crate::anim_compress`]'s
+/// curve simplification.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct VertexKey {
+    position: [u32; 3],
+    normal: [u32; 3],
+    uv: [u32; 2],
+    skinning: Option<([u16; 4], [u32; 4])>,
+    lightmap_uv: Option<[u32; 2]>,
+    vertex_color: Option<[u32; 4]>,
+}
+
+fn vertex_key(mesh: &Mesh, index: usize) -> VertexKey {
+    let vertex = &mesh.vertices[index];
+    VertexKey {
+        position: vertex.position.map(f32::to_bits),
+        normal: vertex.normal.map(f32::to_bits),
+        uv: vertex.uv.map(f32::to_bits),
+        skinning: mesh.skinning.as_ref().map(|skinning| {
+            let skinning = &skinning[index];
+            (skinning.bone_indices, skinning.bone_weights.map(f32::to_bits))
+        }),
+        lightmap_uv: mesh
+            .lightmap_uvs
+            .as_ref()
+            .map(|uvs| uvs[index].map(f32::to_bits)),
+        vertex_color: mesh
+            .vertex_colors
+            .as_ref()
+            .map(|colors| colors[index].map(f32::to_bits)),
+    }
+}
+
+/// Merges vertices that are bit-identical across every stream, remapping
+/// `indices` onto the deduplicated set.
+fn dedupe_vertices(mesh: &Mesh) -> Mesh {
+    let mut remap: HashMap<VertexKey, u32> = HashMap::with_capacity(mesh.vertices.len());
+    let mut old_to_new = vec![0u32; mesh.vertices.len()];
+
+    let mut vertices = Vec::new();
+    let mut skinning = mesh.skinning.as_ref().map(|_| Vec::new());
+    let mut lightmap_uvs = mesh.lightmap_uvs.as_ref().map(|_| Vec::new());
+    let mut vertex_colors = mesh.vertex_colors.as_ref().map(|_| Vec::new());
+
+    for index in 0..mesh.vertices.len() {
+        let key = vertex_key(mesh, index);
+        let new_index = *remap.entry(key).or_insert_with(|| {
+            let new_index = vertices.len() as u32;
+            vertices.push(mesh.vertices[index]);
+            if let Some(skinning) = skinning.as_mut() {
+                skinning.push(mesh.skinning.as_ref().unwrap()[index]);
+            }
+            if let Some(lightmap_uvs) = lightmap_uvs.as_mut() {
+                lightmap_uvs.push(mesh.lightmap_uvs.as_ref().unwrap()[index]);
+            }
+            if let Some(vertex_colors) = vertex_colors.as_mut() {
+                vertex_colors.push(mesh.vertex_colors.as_ref().unwrap()[index]);
+            }
+            new_index
+        });
+        old_to_new[index] = new_index;
+    }
+
+    let indices = mesh.indices.iter().map(|&old| old_to_new[old as usize]).collect();
+
+    Mesh {
+        vertices,
+        indices,
+        skinning,
+        lightmap_uvs,
+        lightmap: mesh.lightmap,
+        vertex_colors,
+        material_slot: mesh.material_slot,
+    }
+}
+
+/// Tom Forsyth's linear-time vertex cache optimizer: greedily emits
+/// whichever remaining triangle scores highest (a triangle whose vertices
+/// are still sitting in the simulated FIFO cache scores higher; a vertex
+/// with few triangles left scores higher too, so partially-finished
+/// fans get cleared before the walk wanders off to a fresh one), then
+/// renumbers vertices in first-use order so the vertex buffer itself
+/// gains the same locality the index buffer now encodes.
+///
+/// This is the straightforward O(triangle_count²) formulation — it
+/// rescans every unemitted triangle for the best score on every step
+/// rather than maintaining a priority queue — which is fine for the
+/// per-mesh sizes this crate bakes but isn't meant to scale to
+/// million-triangle single meshes.
+fn reorder_for_cache_locality(mesh: &Mesh) -> Mesh {
+    let vertex_count = mesh.vertices.len();
+    let triangle_count = mesh.indices.len() / 3;
+    if triangle_count == 0 {
+        return mesh.clone();
+    }
+
+    let mut vertex_triangles: Vec<Vec<u32>> = vec![Vec::new(); vertex_count];
+    for triangle in 0..triangle_count {
+        for corner in 0..3 {
+            let vertex = mesh.indices[triangle * 3 + corner] as usize;
+            vertex_triangles[vertex].push(triangle as u32);
+        }
+    }
+
+    let mut live_valence: Vec<u32> = vertex_triangles.iter().map(|tris| tris.len() as u32).collect();
+    let mut cache_position: Vec<Option<usize>> = vec![None; vertex_count];
+    let mut vertex_score: Vec<f32> = (0..vertex_count)
+        .map(|vertex| vertex_cache_score(None, live_valence[vertex]))
+        .collect();
+    let mut triangle_emitted = vec![false; triangle_count];
+    let mut triangle_score: Vec<f32> = (0..triangle_count)
+        .map(|triangle| {
+            (0..3)
+                .map(|corner| vertex_score[mesh.indices[triangle * 3 + corner] as usize])
+                .sum()
+        })
+        .collect();
+
+    let mut cache: Vec<u32> = Vec::with_capacity(CACHE_SIZE + 3);
+    let mut new_indices = Vec::with_capacity(mesh.indices.len());
+    let mut first_used: Vec<Option<u32>> = vec![None; vertex_count];
+    let mut next_new_vertex = 0u32;
+
+    for _ in 0..triangle_count {
+        let best = (0..triangle_count)
+            .filter(|&triangle| !triangle_emitted[triangle])
+            .max_by(|&a, &b| triangle_score[a].partial_cmp(&triangle_score[b]).unwrap())
+            .expect("at least one unemitted triangle remains");
+
+        triangle_emitted[best] = true;
+        let corners = [
+            mesh.indices[best * 3] as usize,
+            mesh.indices[best * 3 + 1] as usize,
+            mesh.indices[best * 3 + 2] as usize,
+        ];
+
+        for &vertex in &corners {
+            let new_index = *first_used[vertex].get_or_insert_with(|| {
+                let assigned = next_new_vertex;
+                next_new_vertex += 1;
+                assigned
+            });
+            new_indices.push(new_index);
+            live_valence[vertex] -= 1;
+        }
+
+        for &vertex in &cache {
+            cache_position[vertex as usize] = None;
+        }
+        for &vertex in corners.iter().rev() {
+            cache.retain(|&cached| cached != vertex as u32);
+            cache.insert(0, vertex as u32);
+        }
+        cache.truncate(CACHE_SIZE);
+        for (position, &vertex) in cache.iter().enumerate() {
+            cache_position[vertex as usize] = Some(position);
+        }
+
+        let mut dirty_vertices: Vec<usize> = corners.to_vec();
+        dirty_vertices.extend(cache.iter().map(|&vertex| vertex as usize));
+        dirty_vertices.sort_unstable();
+        dirty_vertices.dedup();
+
+        for &vertex in &dirty_vertices {
+            vertex_score[vertex] = vertex_cache_score(cache_position[vertex], live_valence[vertex]);
+        }
+
+        let mut dirty_triangles: Vec<u32> = dirty_vertices
+            .iter()
+            .flat_map(|&vertex| vertex_triangles[vertex].iter().copied())
+            .collect();
+        dirty_triangles.sort_unstable();
+        dirty_triangles.dedup();
+
+        for triangle in dirty_triangles {
+            let triangle = triangle as usize;
+            if !triangle_emitted[triangle] {
+                triangle_score[triangle] = (0..3)
+                    .map(|corner| vertex_score[mesh.indices[triangle * 3 + corner] as usize])
+                    .sum();
+            }
+        }
+    }
+
+    // Any vertex no triangle referenced (a malformed or already-orphaned
+    // import) keeps its relative order, appended after every referenced
+    // vertex, so optimization never silently drops data.
+    for vertex in 0..vertex_count {
+        first_used[vertex].get_or_insert_with(|| {
+            let assigned = next_new_vertex;
+            next_new_vertex += 1;
+            assigned
+        });
+    }
+
+    let mut vertices = vec![mesh.vertices[0]; next_new_vertex as usize];
+    let mut skinning = mesh.skinning.as_ref().map(|source| vec![source[0]; next_new_vertex as usize]);
+    let mut lightmap_uvs = mesh
+        .lightmap_uvs
+        .as_ref()
+        .map(|source| vec![source[0]; next_new_vertex as usize]);
+    let mut vertex_colors = mesh
+        .vertex_colors
+        .as_ref()
+        .map(|source| vec![source[0]; next_new_vertex as usize]);
+
+    for (old_index, new_index) in first_used.iter().enumerate() {
+        let new_index = new_index.unwrap() as usize;
+        vertices[new_index] = mesh.vertices[old_index];
+        if let Some(skinning) = skinning.as_mut() {
+            skinning[new_index] = mesh.skinning.as_ref().unwrap()[old_index];
+        }
+        if let Some(lightmap_uvs) = lightmap_uvs.as_mut() {
+            lightmap_uvs[new_index] = mesh.lightmap_uvs.as_ref().unwrap()[old_index];
+        }
+        if let Some(vertex_colors) = vertex_colors.as_mut() {
+            vertex_colors[new_index] = mesh.vertex_colors.as_ref().unwrap()[old_index];
+        }
+    }
+
+    Mesh {
+        vertices,
+        indices: new_indices,
+        skinning,
+        lightmap_uvs,
+        lightmap: mesh.lightmap,
+        vertex_colors,
+        material_slot: mesh.material_slot,
+    }
+}
+
+fn vertex_cache_score(cache_position: Option<usize>, valence: u32) -> f32 {
+    let cache_score = match cache_position {
+        Some(position) if position < 3 => LAST_TRIANGLE_SCORE,
+        Some(position) if position < CACHE_SIZE => {
+            let scaler = 1.0 / (CACHE_SIZE - 3) as f32;
+            (1.0 - (position - 3) as f32 * scaler).powf(CACHE_DECAY_POWER)
+        }
+        _ => 0.0,
+    };
+    let valence_score = VALENCE_BOOST_SCALE * (valence.max(1) as f32).powf(-VALENCE_BOOST_POWER);
+    cache_score + valence_score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiri_assets::model::Vertex;
+
+    fn vertex(x: f32, z: f32) -> Vertex {
+        Vertex {
+            position: [x, 0.0, z],
+            normal: [0.0, 1.0, 0.0],
+            uv: [0.0, 0.0],
+        }
+    }
+
+    fn quad_mesh() -> Mesh {
+        // Two triangles sharing an edge, but minted as six independent
+        // vertices the way a glTF import would — exactly what dedup is
+        // supposed to collapse back down to four.
+        Mesh {
+            vertices: vec![
+                vertex(0.0, 0.0),
+                vertex(1.0, 0.0),
+                vertex(1.0, 1.0),
+                vertex(0.0, 0.0),
+                vertex(1.0, 1.0),
+                vertex(0.0, 1.0),
+            ],
+            indices: vec![0, 1, 2, 3, 4, 5],
+            skinning: None,
+            lightmap_uvs: None,
+            lightmap: None,
+            vertex_colors: None,
+            material_slot: 0,
+        }
+    }
+
+    #[test]
+    fn dedupe_vertices_merges_bit_identical_vertices() {
+        let deduped = dedupe_vertices(&quad_mesh());
+        assert_eq!(deduped.vertices.len(), 4);
+        assert_eq!(deduped.indices.len(), 6);
+    }
+
+    #[test]
+    fn dedupe_vertices_preserves_triangle_winding() {
+        let mesh = quad_mesh();
+        let deduped = dedupe_vertices(&mesh);
+        for triangle in 0..2 {
+            let original = [
+                mesh.indices[triangle * 3] as usize,
+                mesh.indices[triangle * 3 + 1] as usize,
+                mesh.indices[triangle * 3 + 2] as usize,
+            ]
+            .map(|index| mesh.vertices[index].position);
+            let remapped = [
+                deduped.indices[triangle * 3],
+                deduped.indices[triangle * 3 + 1],
+                deduped.indices[triangle * 3 + 2],
+            ]
+            .map(|index| deduped.vertices[index as usize].position);
+            assert_eq!(original, remapped);
+        }
+    }
+
+    #[test]
+    fn reorder_for_cache_locality_preserves_triangle_count_and_winding() {
+        let mesh = quad_mesh();
+        let reordered = reorder_for_cache_locality(&mesh);
+        assert_eq!(reordered.indices.len(), mesh.indices.len());
+
+        let triangle_positions = |mesh: &Mesh| -> Vec<[f32; 3]> {
+            let mut positions: Vec<[f32; 3]> = (0..mesh.indices.len() / 3)
+                .flat_map(|triangle| {
+                    (0..3).map(move |corner| mesh.vertices[mesh.indices[triangle * 3 + corner] as usize].position)
+                })
+                .collect();
+            positions.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            positions
+        };
+        assert_eq!(triangle_positions(&mesh), triangle_positions(&reordered));
+    }
+
+    #[test]
+    fn reorder_for_cache_locality_handles_empty_mesh() {
+        let empty = Mesh {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            skinning: None,
+            lightmap_uvs: None,
+            lightmap: None,
+            vertex_colors: None,
+            material_slot: 0,
+        };
+        let reordered = reorder_for_cache_locality(&empty);
+        assert!(reordered.vertices.is_empty());
+        assert!(reordered.indices.is_empty());
+    }
+
+    #[test]
+    fn optimize_mesh_dedupes_and_keeps_triangle_count() {
+        let optimized = optimize_mesh(&quad_mesh());
+        assert_eq!(optimized.vertices.len(), 4);
+        assert_eq!(optimized.indices.len(), 6);
+    }
+}
@@ -0,0 +1,98 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use kiri_assets::AssetRef;
+
+use crate::asset_ref_hash::content_hash;
+
+/// Per-source-file import identity and settings, persisted next to the
+/// source as a `.meta` sidecar (see [`meta_path_for`]) — the mechanism
+/// that lets an [`AssetRef`] survive a source file rename. Without a meta
+/// file, the only rename-stable option is deriving the ref from file
+/// content, which breaks the moment re-exporting a source (same content,
+/// different container/compression settings) changes its bytes; a meta
+/// file's `asset_ref` is written once, at first import, and never
+/// recomputed after that.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AssetMeta {
+    pub asset_ref: AssetRef,
+    /// Importer-specific settings (compression quality, sRGB vs linear,
+    /// import scale, ...) as free-form JSON rather than one struct per
+    /// importer, since every importer's knobs differ and a single sum
+    /// type would need a variant per importer anyway with none of JSON's
+    /// forward-compatibility (an older `kiri-asset-pipe` build can still
+    /// round-trip a newer importer's settings it doesn't understand).
+    #[serde(default)]
+    pub importer_settings: serde_json::Value,
+    /// User-defined tags (e.g. "environment", "hero-character") that
+    /// don't affect baking at all — carried through purely so editor
+    /// tooling can filter/search a project's assets by them.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// `<source>.meta`'s path for `source_path` — e.g. `crate.png.meta` next
+/// to `crate.png`, so an artist renaming or moving a source file sees an
+/// obviously-paired sidecar sitting right next to it in their file
+/// browser, and knows to bring both along together.
+pub fn meta_path_for(source_path: &Path) -> PathBuf {
+    let mut meta_path = source_path.as_os_str().to_owned();
+    meta_path.push(".meta");
+    PathBuf::from(meta_path)
+}
+
+/// Loads `source_path`'s `.meta` sidecar if one exists, otherwise mints a
+/// fresh [`AssetMeta`] with a newly assigned `asset_ref` and writes it
+/// out immediately — so the ref assigned to a brand-new source file is
+/// decided exactly once, at first import, and every later bake (of the
+/// same file, or a renamed copy carrying its sidecar along) resolves to
+/// the same identity instead of re-deriving one from the path.
+pub fn load_or_create_meta(source_path: &Path) -> Result<AssetMeta> {
+    let meta_path = meta_path_for(source_path);
+
+    if meta_path.exists() {
+        let text = std::fs::read_to_string(&meta_path)
+            .with_context(|| format!("Failed to read meta sidecar {:?}", meta_path))?;
+        return ron::from_str(&text)
+            .with_context(|| format!("Failed to parse meta sidecar {:?}", meta_path));
+    }
+
+    let meta = AssetMeta {
+        asset_ref: AssetRef(fresh_asset_ref_bits()),
+        importer_settings: serde_json::Value::Null,
+        tags: Vec::new(),
+    };
+    write_meta(source_path, &meta)?;
+    Ok(meta)
+}
+
+pub fn write_meta(source_path: &Path, meta: &AssetMeta) -> Result<()> {
+    let meta_path = meta_path_for(source_path);
+    let text = ron::ser::to_string_pretty(meta, ron::ser::PrettyConfig::default())
+        .context("Failed to serialize meta sidecar")?;
+    std::fs::write(&meta_path, text)
+        .with_context(|| format!("Failed to write meta sidecar {:?}", meta_path))
+}
+
+/// Mints a new, essentially-unique 64-bit id for a fresh [`AssetMeta`] —
+/// not cryptographically random (this crate has no `rand` dependency),
+/// but time plus an in-process counter is more than enough entropy to
+/// avoid collisions between assets imported in the same bake run, which
+/// is the only place two refs minted this way could ever collide.
+fn fresh_asset_ref_bits() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let counter = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or(0);
+
+    let mut bytes = Vec::with_capacity(16);
+    bytes.extend_from_slice(&nanos.to_le_bytes());
+    bytes.extend_from_slice(&counter.to_le_bytes());
+    content_hash(&bytes)
+}
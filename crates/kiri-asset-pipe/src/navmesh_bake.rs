@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use kiri_assets::collision::{CollisionAsset, CollisionShape};
+use kiri_assets::navmesh::{NavMeshAsset, NavMeshTile, NavPolygon, OffMeshLink};
+
+/// How steep a triangle can be and still count as walkable ground —
+/// the recast convention of a maximum walkable slope expressed as the
+/// minimum upward-facing component of the triangle's normal, rather than
+/// a raw angle, since that's what the per-triangle filter actually tests
+/// against.
+#[derive(Clone, Copy, Debug)]
+pub struct NavMeshBakeOptions {
+    pub max_walkable_slope_normal_y: f32,
+}
+
+impl Default for NavMeshBakeOptions {
+    fn default() -> Self {
+        Self {
+            // A ~50 degree slope limit — steep enough for most ramps and
+            // stairs, shallow enough to reject cliff faces and walls.
+            max_walkable_slope_normal_y: 0.64,
+        }
+    }
+}
+
+/// Bakes a [`NavMeshAsset`] from `collision`'s triangle geometry: filters
+/// to upward-facing (walkable) triangles, then emits one triangle-soup
+/// tile with adjacency built from shared edges. This is a simplified
+/// stand-in for a full recast pipeline's voxelization/contour/polygon
+/// merge — every polygon is a source triangle rather than the coarser
+/// convex polygons recast would merge them into — but it's built from the
+/// same walkable-surface data and exposes the same
+/// [`NavMeshAsset::find_path`]/[`NavMeshAsset::raycast`] query surface,
+/// so a project can swap in a real recast build later without touching
+/// anything downstream of the baked asset.
+///
+/// Off-mesh links aren't generated by this step — jump/ladder/drop
+/// connections require scene-level annotation this function has no input
+/// for, so `off_mesh_links` starts empty and is populated by whichever
+/// tool authors them.
+pub fn bake_navmesh(collision: &CollisionAsset, options: NavMeshBakeOptions) -> NavMeshAsset {
+    let (vertices, indices) = match &collision.shape {
+        CollisionShape::TriangleMesh { vertices, indices } => (vertices.clone(), indices.clone()),
+        CollisionShape::SimplifiedProxy { vertices, indices } => {
+            (vertices.clone(), indices.clone())
+        }
+        // A hull decomposition has already thrown away the concave detail
+        // a navmesh needs to be useful (an agent shouldn't be told it can
+        // walk through the middle of a hollow room because the room's
+        // hull is convex) — nothing sensible to bake from here.
+        CollisionShape::ConvexHulls(_) => (Vec::new(), Vec::new()),
+    };
+
+    let walkable_triangles: Vec<[u32; 3]> = indices
+        .chunks_exact(3)
+        .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+        .filter(|&[a, b, c]| is_walkable(&vertices, a, b, c, options.max_walkable_slope_normal_y))
+        .collect();
+
+    let polygons = build_adjacency(&walkable_triangles);
+
+    NavMeshAsset {
+        tiles: vec![NavMeshTile { vertices, polygons }],
+        off_mesh_links: Vec::<OffMeshLink>::new(),
+    }
+}
+
+fn is_walkable(vertices: &[[f32; 3]], a: u32, b: u32, c: u32, max_slope_normal_y: f32) -> bool {
+    let (pa, pb, pc) = (
+        vertices[a as usize],
+        vertices[b as usize],
+        vertices[c as usize],
+    );
+    let edge1 = [pb[0] - pa[0], pb[1] - pa[1], pb[2] - pa[2]];
+    let edge2 = [pc[0] - pa[0], pc[1] - pa[1], pc[2] - pa[2]];
+    let normal = [
+        edge1[1] * edge2[2] - edge1[2] * edge2[1],
+        edge1[2] * edge2[0] - edge1[0] * edge2[2],
+        edge1[0] * edge2[1] - edge1[1] * edge2[0],
+    ];
+    let length = (normal[0] * normal[0] + normal[1] * normal[1] + normal[2] * normal[2]).sqrt();
+    if length < 1e-8 {
+        return false;
+    }
+    (normal[1] / length) >= max_slope_normal_y
+}
+
+/// Builds one [`NavPolygon`] per walkable triangle, with `neighbors[i]`
+/// set to whichever other triangle shares the undirected edge from vertex
+/// `i` to vertex `(i + 1) % 3` (the convention [`NavPolygon::neighbors`]
+/// documents), or `None` if no walkable triangle shares that edge.
+fn build_adjacency(triangles: &[[u32; 3]]) -> Vec<NavPolygon> {
+    let mut edge_owners: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+    for (triangle_index, &[a, b, c]) in triangles.iter().enumerate() {
+        for (u, v) in [(a, b), (b, c), (c, a)] {
+            edge_owners
+                .entry((u.min(v), u.max(v)))
+                .or_default()
+                .push(triangle_index);
+        }
+    }
+
+    triangles
+        .iter()
+        .enumerate()
+        .map(|(triangle_index, &[a, b, c])| {
+            let neighbors = [(a, b), (b, c), (c, a)]
+                .map(|(u, v)| {
+                    edge_owners[&(u.min(v), u.max(v))]
+                        .iter()
+                        .copied()
+                        .find(|&owner| owner != triangle_index)
+                        .map(|owner| owner as u32)
+                })
+                .to_vec();
+
+            NavPolygon {
+                indices: vec![a, b, c],
+                neighbors,
+            }
+        })
+        .collect()
+}
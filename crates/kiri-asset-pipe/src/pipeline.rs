@@ -1,3 +1,213 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:0ac0e6fa1d79f9ead8f42eead21824933c4b506eb9cc6ec31cfa257154b89869
-size 5419
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::Result;
+use rayon::prelude::*;
+
+use crate::asset_ref_hash::AssetRefAuditor;
+use crate::bundler::BundleWriter;
+use crate::image_import::{self, ImageImportOptions};
+use crate::meta;
+
+/// Top-level knobs for one bake run, collected in one place so
+/// `tools/builder` has a single struct to parse CLI args into.
+#[derive(Clone, Debug)]
+pub struct BakeOptions {
+    pub source_dir: PathBuf,
+    pub output_bundle: PathBuf,
+    pub image_import: ImageImportOptions,
+    /// When true, an existing bundle at `output_bundle` is loaded first
+    /// and only source files newer than it are re-imported; everything
+    /// else is carried over unchanged. When false, every source file is
+    /// re-imported and the bundle is rebuilt from scratch.
+    pub incremental: bool,
+}
+
+/// Drives a full bake: walks `source_dir`, imports everything it
+/// recognizes, and writes the result to `output_bundle`.
+pub struct Pipeline {
+    options: BakeOptions,
+}
+
+/// The result of importing one source file, independent of every other
+/// import task — this is the unit of work the bake task graph schedules.
+enum ImportedAsset {
+    Image(PathBuf, kiri_assets::image::ImageAsset),
+    Models(PathBuf, crate::gltf_import::ImportedScene),
+    Effect(PathBuf, kiri_assets::effect::EffectAsset),
+    StringTable(PathBuf, kiri_assets::localization::StringTableAsset),
+    Skipped,
+}
+
+impl ImportedAsset {
+    /// The source path behind this import, or `None` for
+    /// [`ImportedAsset::Skipped`] — nothing was actually imported for it,
+    /// so it has no `AssetRef` to audit.
+    fn source_path(&self) -> Option<&Path> {
+        match self {
+            ImportedAsset::Image(path, _)
+            | ImportedAsset::Models(path, _)
+            | ImportedAsset::Effect(path, _)
+            | ImportedAsset::StringTable(path, _) => Some(path),
+            ImportedAsset::Skipped => None,
+        }
+    }
+}
+
+impl Pipeline {
+    pub fn new(options: BakeOptions) -> Self {
+        Self { options }
+    }
+
+    /// Imports every recognized source file under `source_dir` in
+    /// parallel (each file is its own independent task; nothing about one
+    /// image import depends on another) and then merges the results into
+    /// the bundle on the calling thread, since `BundleWriter` isn't
+    /// designed to be written to from multiple threads at once.
+    pub fn run(&self) -> Result<()> {
+        let sources = walk_source_files(&self.options.source_dir)?;
+
+        let mut writer = if self.options.incremental {
+            BundleWriter::open_or_new(&self.options.output_bundle)?
+        } else {
+            BundleWriter::new()
+        };
+
+        let output_mtime = self.options.incremental.then(|| mtime_of(&self.options.output_bundle));
+
+        let to_import: Vec<&PathBuf> = sources
+            .iter()
+            .filter(|path| match output_mtime.flatten() {
+                Some(bundle_mtime) => mtime_of(path).map_or(true, |mtime| mtime > bundle_mtime),
+                None => true,
+            })
+            .collect();
+
+        let errors = Mutex::new(Vec::new());
+
+        let imported: Vec<(kiri_assets::AssetRef, ImportedAsset)> = to_import
+            .par_iter()
+            .filter_map(|path| match self.import_one(path) {
+                Ok(asset) => Some(asset),
+                Err(err) => {
+                    errors.lock().unwrap().push(((*path).clone(), err));
+                    None
+                }
+            })
+            .collect();
+
+        let errors = errors.into_inner().unwrap();
+        if let Some((path, err)) = errors.into_iter().next() {
+            return Err(err.context(format!("Failed to bake {:?}", path)));
+        }
+
+        // Every `.meta` sidecar is supposed to uniquely identify one
+        // source file; catch a bake where two different source paths
+        // somehow ended up minted with the same `AssetRef` before it
+        // corrupts the bundle, rather than after.
+        let mut auditor = AssetRefAuditor::new();
+        for (asset_ref, asset) in &imported {
+            if let Some(path) = asset.source_path() {
+                auditor
+                    .record(*asset_ref, path.to_string_lossy())
+                    .map_err(anyhow::Error::msg)?;
+            }
+        }
+
+        for (asset_ref, asset) in imported {
+            match asset {
+                ImportedAsset::Image(path, image) => writer.add_image(&path, asset_ref, image),
+                ImportedAsset::Models(path, scene) => writer.add_models(&path, scene),
+                ImportedAsset::Effect(path, effect) => writer.add_effect(&path, asset_ref, effect),
+                ImportedAsset::StringTable(path, string_table) => {
+                    writer.add_string_table(&path, asset_ref, string_table)
+                }
+                ImportedAsset::Skipped => {}
+            }
+        }
+
+        writer.prune_missing(&sources);
+
+        let dedup_stats = writer.dedup_stats();
+        if dedup_stats.duplicate_entries > 0 {
+            log::info!(
+                "Bundle dedup: {} duplicate payload(s) aliased, {} bytes not re-stored",
+                dedup_stats.duplicate_entries,
+                dedup_stats.bytes_saved,
+            );
+        }
+
+        writer.write_to_file(&self.options.output_bundle)
+    }
+
+    fn import_one(&self, path: &Path) -> Result<(kiri_assets::AssetRef, ImportedAsset)> {
+        // Resolves this source's stable AssetRef (minting and persisting
+        // a `.meta` sidecar on first import) before doing any real
+        // importing work, so the identity is assigned exactly once and
+        // survives later renames.
+        let meta = meta::load_or_create_meta(path)?;
+
+        let asset = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("png") | Some("jpg") | Some("jpeg") | Some("tga") => {
+                let image = image_import::import_image(path, &self.options.image_import)?;
+                ImportedAsset::Image(path.to_path_buf(), image)
+            }
+            Some("gltf") | Some("glb") => {
+                let scene = crate::gltf_import::import_gltf(path)?;
+                ImportedAsset::Models(path.to_path_buf(), scene)
+            }
+            Some("ron") | Some("json") if is_effect_desc(path) => {
+                let effect = crate::import_effect::import_effect(path)?;
+                ImportedAsset::Effect(path.to_path_buf(), effect)
+            }
+            Some("ron") if is_string_table_desc(path) => {
+                let string_table = crate::string_table_import::import_string_table(path)?;
+                ImportedAsset::StringTable(path.to_path_buf(), string_table)
+            }
+            _ => {
+                log::debug!("Skipping unrecognized source file: {:?}", path);
+                ImportedAsset::Skipped
+            }
+        };
+
+        Ok((meta.asset_ref, asset))
+    }
+}
+
+fn mtime_of(path: &Path) -> Option<std::time::SystemTime> {
+    std::fs::metadata(path).ok()?.modified().ok()
+}
+
+/// Distinguishes `foo.effect.ron`/`foo.effect.json` from plain `.ron`/
+/// `.json` source files that might be authoring data for something else
+/// down the line — matched on the `.effect` stem rather than the outer
+/// extension alone.
+fn is_effect_desc(path: &Path) -> bool {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(|stem| stem.ends_with(".effect"))
+        .unwrap_or(false)
+}
+
+/// Distinguishes `foo.strings.ron` from plain `.ron` source files the
+/// same way [`is_effect_desc`] distinguishes `.effect.ron`.
+fn is_string_table_desc(path: &Path) -> bool {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(|stem| stem.ends_with(".strings"))
+        .unwrap_or(false)
+}
+
+fn walk_source_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut out = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            out.extend(walk_source_files(&path)?);
+        } else if path.extension().and_then(|ext| ext.to_str()) != Some("meta") {
+            out.push(path);
+        }
+    }
+    Ok(out)
+}
@@ -0,0 +1,14 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use kiri_assets::localization::StringTableAsset;
+
+/// Imports one `.strings.ron` source file: a [`StringTableAsset`]
+/// authored directly in RON, since — unlike a model or an effect —
+/// there's no compilation step between what a translator writes and what
+/// the runtime reads.
+pub fn import_string_table(path: &Path) -> Result<StringTableAsset> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read string table {:?}", path))?;
+    ron::from_str(&text).with_context(|| format!("Failed to parse string table {:?}", path))
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+/// One sample of a curve: `time` in seconds since the start of the clip.
+/// Curves are sparse (not one key per frame), so sampling always means
+/// finding the bracketing pair and interpolating.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Keyframe<T> {
+    pub time: f32,
+    pub value: T,
+}
+
+/// The translation/rotation/scale curves driving one bone, by index into
+/// the skeleton the clip was baked against. A bone with no motion in a
+/// given clip (e.g. a prop only some clips touch) simply has empty curves
+/// rather than being omitted, so every clip addresses bones the same way.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BoneTrack {
+    pub bone_index: u32,
+    pub translation: Vec<Keyframe<[f32; 3]>>,
+    pub rotation: Vec<Keyframe<[f32; 4]>>,
+    pub scale: Vec<Keyframe<[f32; 3]>>,
+}
+
+/// A baked animation clip: per-bone curves plus the clip's total length.
+/// Sampling, blending and playback state all live in the `kiri-anim`
+/// runtime crate — this is just the data.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnimationClipAsset {
+    pub name: String,
+    pub duration: f32,
+    pub tracks: Vec<BoneTrack>,
+    /// Named markers (footstep, hit frame, ...) the runtime fires as events
+    /// when playback crosses their time.
+    #[serde(default)]
+    pub events: Vec<Keyframe<String>>,
+}
+
+impl AnimationClipAsset {
+    pub fn track_for_bone(&self, bone_index: u32) -> Option<&BoneTrack> {
+        self.tracks.iter().find(|track| track.bone_index == bone_index)
+    }
+}
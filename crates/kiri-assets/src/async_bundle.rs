@@ -0,0 +1,42 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::bundle::AssetBundle;
+use crate::AssetRef;
+
+/// Non-blocking bundle reads, for callers that can't afford to stall a
+/// frame on disk IO (streaming in a texture while the game keeps
+/// rendering, loading the next level in the background, ...).
+///
+/// Today a bundle is still read as one contiguous blob — there's no
+/// seekable per-asset layout on disk yet — so this only moves that one
+/// read off the calling thread and onto tokio's blocking IO pool; once
+/// assets are addressable by byte range on disk directly, `read_asset`
+/// below is where a real partial read would replace the full decode.
+pub struct AsyncBundleReader {
+    bundle: AssetBundle,
+}
+
+impl AsyncBundleReader {
+    pub async fn open(path: &Path) -> Result<Self> {
+        let bytes = tokio::fs::read(path).await?;
+        let bundle: AssetBundle = tokio::task::spawn_blocking(move || bincode::deserialize(&bytes))
+            .await??;
+        bundle
+            .validate()
+            .with_context(|| format!("Bundle at {path:?} failed validation"))?;
+
+        Ok(Self { bundle })
+    }
+
+    /// Returns the payload bytes for `asset_ref`. Already resident in
+    /// memory once [`AsyncBundleReader::open`] has returned, so this does
+    /// not actually await anything today, but keeps the `async fn` shape
+    /// so callers don't need to change when partial reads land.
+    pub async fn read_asset(&self, asset_ref: AssetRef) -> Result<Vec<u8>> {
+        self.bundle
+            .load(asset_ref)
+            .ok_or_else(|| anyhow::anyhow!("AssetRef {:?} not present in bundle", asset_ref))
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// How [`AudioAsset::data`] is encoded. `Pcm16` is sampled directly by the
+/// mixer. `Vorbis` data is kept encoded rather than expanded at load
+/// time; `kiri-audio` has no Vorbis decoder, so its mixer plays such an
+/// asset as silence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AudioFormat {
+    Pcm16,
+    Vorbis,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AudioAsset {
+    pub name: String,
+    pub format: AudioFormat,
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub data: Vec<u8>,
+}
+
+impl AudioAsset {
+    /// Only meaningful for [`AudioFormat::Pcm16`] — `Vorbis` data doesn't
+    /// give its duration without decoding.
+    pub fn pcm16_duration_seconds(&self) -> f32 {
+        debug_assert_eq!(self.format, AudioFormat::Pcm16);
+        let frame_count = self.data.len() / (2 * self.channels as usize);
+        frame_count as f32 / self.sample_rate as f32
+    }
+}
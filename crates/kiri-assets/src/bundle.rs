@@ -1,3 +1,156 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:a7c0aa4771443e4c3200d19b9bb8512b2303792676f55d8487b67b920b8119ec
-size 7309
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::effect::EffectAsset;
+use crate::image::ImageAsset;
+use crate::localization::StringTableAsset;
+use crate::AssetRef;
+
+/// The on-disk/on-wire bundle format: the same shape `kiri-asset-pipe`'s
+/// `BundleWriter` accumulates a bake into and writes out, so there is
+/// exactly one format both ends of the content pipeline agree on.
+///
+/// Assets are stored keyed by their source path rather than by
+/// [`AssetRef`] — that's what lets an incremental bake diff/carry over
+/// unchanged entries without decoding anything (see
+/// `kiri-asset-pipe::bundler::BundleWriter::open_or_new`). `asset_refs`
+/// is the index that lets a runtime lookup by `AssetRef` — what every
+/// other asset actually references — find the path holding its payload
+/// despite that.
+#[derive(Default, Serialize, Deserialize)]
+pub struct AssetBundle {
+    pub images: BTreeMap<String, ImageAsset>,
+    pub effects: BTreeMap<String, EffectAsset>,
+    pub string_tables: BTreeMap<String, StringTableAsset>,
+    /// Paths whose baked payload is byte-identical to another entry
+    /// already stored under `images`/`effects`/`string_tables` — keyed
+    /// by the duplicate's own source path, valued by the path actually
+    /// holding the (shared) payload.
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+    /// Every path's stable `AssetRef`, assigned once at first import by
+    /// `kiri-asset-pipe`'s `.meta` sidecars. A path whose payload is
+    /// aliased still gets its own entry here, pointing the same
+    /// `AssetRef` at the canonical path via [`AssetBundle::load`].
+    #[serde(default)]
+    pub asset_refs: BTreeMap<String, AssetRef>,
+    /// Old-to-new `AssetRef` remapping, consulted before every lookup —
+    /// see [`BundleRemapTable`].
+    #[serde(default)]
+    pub remap: BundleRemapTable,
+}
+
+/// Old-to-new [`AssetRef`] mappings so a bake that reorganizes content
+/// (merging duplicate assets, splitting one asset into several and
+/// keeping only one canonical successor, ...) doesn't strand serialized
+/// scenes and save games that still reference the old ids. Entries are
+/// followed transitively (an id remapped twice resolves through both
+/// hops) with a bounded hop count, so a bad remap chain fails a lookup
+/// instead of looping forever.
+#[derive(Default, Serialize, Deserialize)]
+pub struct BundleRemapTable {
+    entries: BTreeMap<AssetRef, AssetRef>,
+}
+
+/// Remap chains longer than this are treated as broken rather than
+/// followed further — legitimate remaps are one or two bakes deep at
+/// most, so this only ever trips on a cycle or otherwise corrupt table.
+const MAX_REMAP_HOPS: u32 = 16;
+
+impl BundleRemapTable {
+    pub fn insert(&mut self, old_ref: AssetRef, new_ref: AssetRef) {
+        self.entries.insert(old_ref, new_ref);
+    }
+
+    /// Follows `asset_ref` through the remap chain until it reaches an id
+    /// with no further remap entry, returning `asset_ref` unchanged if it
+    /// was never remapped at all, or `None` if the chain is longer than
+    /// [`MAX_REMAP_HOPS`].
+    pub fn resolve(&self, asset_ref: AssetRef) -> Option<AssetRef> {
+        let mut current = asset_ref;
+        for _ in 0..MAX_REMAP_HOPS {
+            match self.entries.get(&current) {
+                Some(&next) => current = next,
+                None => return Some(current),
+            }
+        }
+        (!self.entries.contains_key(&current)).then_some(current)
+    }
+}
+
+/// A bundle whose `asset_refs`/`aliases` index points somewhere that
+/// doesn't actually hold a payload — the thing [`AssetBundle::validate`]
+/// exists to catch up front, so a hand-edited or corrupt bundle fails
+/// with a message naming the offending `AssetRef` instead of surfacing
+/// as a silent [`AssetBundle::load`] miss later.
+#[derive(Debug)]
+pub struct BundleError {
+    pub asset_ref: AssetRef,
+    pub path: String,
+}
+
+impl std::fmt::Display for BundleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "bundle entry {:?} (path {:?}) has no matching image/effect/string_table payload",
+            self.asset_ref, self.path
+        )
+    }
+}
+
+impl std::error::Error for BundleError {}
+
+impl AssetBundle {
+    /// Resolves `asset_ref` (after following any remap) to the path
+    /// actually holding its payload, following one alias hop if the
+    /// path it was first imported under turned out to be a duplicate of
+    /// another.
+    fn canonical_path(&self, asset_ref: AssetRef) -> Option<&str> {
+        let asset_ref = self.remap.resolve(asset_ref)?;
+        let path = self
+            .asset_refs
+            .iter()
+            .find(|(_, candidate)| **candidate == asset_ref)
+            .map(|(path, _)| path.as_str())?;
+        Some(self.aliases.get(path).map(String::as_str).unwrap_or(path))
+    }
+
+    /// True if `asset_ref` names an asset actually present in this bundle.
+    pub fn contains(&self, asset_ref: AssetRef) -> bool {
+        self.canonical_path(asset_ref).is_some()
+    }
+
+    /// Resolves `asset_ref` to its payload, bincode-serialized — the same
+    /// byte shape [`crate::placeholder::deserialize_or_placeholder`]
+    /// expects to decode, regardless of which of `images`/`effects`/
+    /// `string_tables` the payload actually lives in.
+    pub fn load(&self, asset_ref: AssetRef) -> Option<Vec<u8>> {
+        let path = self.canonical_path(asset_ref)?;
+        if let Some(image) = self.images.get(path) {
+            return bincode::serialize(image).ok();
+        }
+        if let Some(effect) = self.effects.get(path) {
+            return bincode::serialize(effect).ok();
+        }
+        if let Some(string_table) = self.string_tables.get(path) {
+            return bincode::serialize(string_table).ok();
+        }
+        None
+    }
+
+    /// Checks that every `asset_refs` entry resolves (through `aliases`
+    /// and `remap`) to an actually-stored payload.
+    pub fn validate(&self) -> Result<(), BundleError> {
+        for (path, asset_ref) in &self.asset_refs {
+            if self.load(*asset_ref).is_none() {
+                return Err(BundleError {
+                    asset_ref: *asset_ref,
+                    path: path.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
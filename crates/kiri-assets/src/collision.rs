@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::Aabb;
+use crate::AssetRef;
+
+/// Physics-ready collision geometry cooked at bake time from a source
+/// [`crate::model::ModelAsset`] — kept as its own bundle asset rather than
+/// generated at load time by whichever physics engine binding (rapier,
+/// PhysX, ...) ends up consuming it, so cooking (hull generation, mesh
+/// simplification) happens once at bake time and every runtime just loads
+/// the result.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CollisionAsset {
+    /// The model this was cooked from — tracked so an incremental bake
+    /// can tell whether re-cooking is needed when the source model
+    /// changes, the same direction [`crate::model::LightmapBinding::lightmap`]
+    /// tracks its dependency in reverse (a mesh points at its lightmap;
+    /// here the collision asset points at its source model).
+    pub source_model: AssetRef,
+    pub shape: CollisionShape,
+    /// Object-space bounds, cooked alongside `shape` rather than
+    /// recomputed at load time — a broad-phase physics query can reject a
+    /// body without ever touching `shape`'s (possibly much larger)
+    /// vertex/index data.
+    pub bounds: Aabb,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum CollisionShape {
+    /// One convex hull per source mesh — the cheap, common case for
+    /// dynamic bodies (a barrel, a crate) where an exact concave shape
+    /// isn't needed and a physics engine's hull-vs-hull test is far
+    /// cheaper than mesh-vs-mesh.
+    ConvexHulls(Vec<ConvexHull>),
+    /// The exact source triangles, merged across every mesh in the model
+    /// — for static level geometry a dynamic body can rest on or collide
+    /// against but that never itself needs a hull decomposition.
+    TriangleMesh {
+        vertices: Vec<[f32; 3]>,
+        indices: Vec<u32>,
+    },
+    /// A coarser stand-in (fewer triangles than the source) for objects
+    /// whose visual mesh is too dense to cook directly into a
+    /// `TriangleMesh` (foliage, rubble, greeble) but still needs a
+    /// concave shape rather than a hull.
+    SimplifiedProxy {
+        vertices: Vec<[f32; 3]>,
+        indices: Vec<u32>,
+    },
+}
+
+/// One convex hull's vertices, wound however the cooking step that
+/// produced it left them — consumers pass this straight to their physics
+/// engine's own hull type rather than this crate committing to one
+/// engine's winding/format convention.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConvexHull {
+    pub vertices: Vec<[f32; 3]>,
+}
@@ -1,3 +1,212 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:ab3cdd98299cd305c4d5204efc5721ddd775f928aaf9bd68aa0972548723a3e6
-size 4906
+use serde::{Deserialize, Serialize};
+
+use crate::model::VertexLayout;
+use crate::AssetRef;
+
+/// A baked description of a graphics pipeline: shader stages plus the
+/// fixed-function state needed to build a `vk::Pipeline` from them.
+///
+/// `EffectAsset`s are produced by `kiri-asset-pipe::import_effect` from
+/// authoring-time descriptions and consumed directly by `kiri-backend`;
+/// nothing about shader compilation happens at runtime.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EffectAsset {
+    pub name: String,
+    pub vertex_shader: ShaderStage,
+    pub pixel_shader: Option<ShaderStage>,
+    pub compute_shader: Option<ShaderStage>,
+    pub depth_stencil: DepthStencilState,
+    pub rasterizer: RasterizerState,
+    /// Defaults to an indexed triangle list with no primitive restart —
+    /// older baked bundles predate this field and load fine under that
+    /// assumption.
+    #[serde(default)]
+    pub input_assembly: InputAssemblyState,
+    /// Which of [`crate::model::Mesh::layout`]'s tags this effect's vertex
+    /// shader expects its vertex buffer bound in — checked against a
+    /// mesh's actual layout before a draw, instead of the mismatch only
+    /// showing up as garbled attributes on screen.
+    #[serde(default)]
+    pub vertex_layout: VertexLayout,
+    /// Which render path this permutation's pixel shader was compiled
+    /// for — [`RenderPath::Forward`] shades and writes color directly,
+    /// [`RenderPath::Deferred`] instead writes GBuffer attachments for a
+    /// later lighting-resolve pass to shade. Distinct permutations, not a
+    /// runtime branch, since the two write entirely different outputs.
+    pub render_path: RenderPath,
+}
+
+/// Which pass topology an [`EffectAsset`] was compiled to slot into.
+///
+/// A `kiri-backend` renderer picks one path per frame (or per view, for
+/// e.g. a forward-rendered UI overlay atop a deferred main view) and only
+/// draws with effects compiled for that path — mixing them isn't
+/// meaningful, since a forward pixel shader has nowhere to write a GBuffer
+/// and a deferred one has no lighting result to write to color.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RenderPath {
+    #[default]
+    Forward,
+    Deferred,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ShaderStage {
+    pub spirv: AssetRef,
+    pub entry_point: String,
+}
+
+/// Depth/stencil fixed-function state for one effect.
+///
+/// `compare_op` defaults to [`DepthCompareOp::GreaterOrEqual`] rather than
+/// the textbook `Less`: kiri renders with a reverse-Z depth buffer (1.0 at
+/// the near plane, 0.0 at the far plane) for better precision distribution
+/// with a floating point depth format, so "closer" means "greater".
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct DepthStencilState {
+    pub depth_test_enable: bool,
+    pub depth_write_enable: bool,
+    pub compare_op: DepthCompareOp,
+    pub stencil: Option<StencilState>,
+}
+
+impl Default for DepthStencilState {
+    fn default() -> Self {
+        Self {
+            depth_test_enable: true,
+            depth_write_enable: true,
+            compare_op: DepthCompareOp::GreaterOrEqual,
+            stencil: None,
+        }
+    }
+}
+
+/// Stencil test/write state, shared between the front and back face in
+/// line with every other fixed-function toggle in this file — kiri has no
+/// use case yet for asymmetric front/back stencil ops.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct StencilState {
+    pub compare_op: DepthCompareOp,
+    pub fail_op: StencilOp,
+    pub pass_op: StencilOp,
+    pub depth_fail_op: StencilOp,
+    pub compare_mask: u32,
+    pub write_mask: u32,
+    pub reference: u32,
+}
+
+impl Default for StencilState {
+    fn default() -> Self {
+        Self {
+            compare_op: DepthCompareOp::Always,
+            fail_op: StencilOp::Keep,
+            pass_op: StencilOp::Replace,
+            depth_fail_op: StencilOp::Keep,
+            compare_mask: 0xff,
+            write_mask: 0xff,
+            reference: 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StencilOp {
+    Keep,
+    Zero,
+    Replace,
+    IncrementClamp,
+    DecrementClamp,
+    Invert,
+    IncrementWrap,
+    DecrementWrap,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DepthCompareOp {
+    Never,
+    Less,
+    Equal,
+    LessOrEqual,
+    Greater,
+    NotEqual,
+    GreaterOrEqual,
+    Always,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct RasterizerState {
+    pub cull_mode: CullMode,
+    pub depth_clamp_enable: bool,
+}
+
+impl Default for RasterizerState {
+    fn default() -> Self {
+        Self {
+            cull_mode: CullMode::Back,
+            depth_clamp_enable: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CullMode {
+    None,
+    Front,
+    Back,
+}
+
+/// Input-assembly fixed-function state for one effect: which primitive
+/// topology its indices assemble into, and whether a reserved index value
+/// restarts the current primitive instead of connecting to it (see
+/// `kiri-backend`'s index-packing helpers for how that reserved value is
+/// chosen). Defaults to the overwhelmingly common case — an indexed
+/// triangle list, no restart marker in use.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputAssemblyState {
+    #[serde(default)]
+    pub topology: PrimitiveTopology,
+    #[serde(default)]
+    pub primitive_restart_enable: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PrimitiveTopology {
+    #[default]
+    TriangleList,
+    TriangleStrip,
+    LineList,
+    LineStrip,
+    PointList,
+}
+
+impl EffectAsset {
+    /// A `compare_op`/write-mask combination suited to a depth pre-pass:
+    /// write depth, test with reverse-Z defaults, and skip the pixel
+    /// shader entirely since pre-pass effects only care about depth.
+    pub fn depth_prepass(vertex_shader: ShaderStage) -> Self {
+        Self {
+            name: "depth_prepass".to_string(),
+            vertex_shader,
+            pixel_shader: None,
+            compute_shader: None,
+            depth_stencil: DepthStencilState::default(),
+            rasterizer: RasterizerState::default(),
+            input_assembly: InputAssemblyState::default(),
+            vertex_layout: VertexLayout::default(),
+            render_path: RenderPath::Forward,
+        }
+    }
+
+    /// The depth/stencil state a main color pass should use once a depth
+    /// pre-pass has already populated the depth buffer: still test against
+    /// it (to skip shaded-but-occluded pixels), but don't write, since the
+    /// pre-pass values are already final.
+    pub fn depth_equal_no_write() -> DepthStencilState {
+        DepthStencilState {
+            depth_test_enable: true,
+            depth_write_enable: false,
+            compare_op: DepthCompareOp::Equal,
+            stencil: None,
+        }
+    }
+}
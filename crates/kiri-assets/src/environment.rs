@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::image::ImageFormat;
+
+/// A baked cubemap: one mip chain per face, roughness-prefiltered so a
+/// PBR specular lookup can sample straight from `mips[roughness_mip]`
+/// with no runtime convolution. Face order matches Vulkan's cube array
+/// layer convention: +X, -X, +Y, -Y, +Z, -Z.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EnvironmentAsset {
+    /// Edge length of the base (mip 0) face.
+    pub face_size: u32,
+    pub format: ImageFormat,
+    /// `faces[mip][face]`, largest mip first — the same tightly-packed
+    /// per-level convention as [`crate::image::ImageAsset::mips`], just
+    /// with an extra dimension for the six faces.
+    pub faces: Vec<[Vec<u8>; 6]>,
+}
+
+impl EnvironmentAsset {
+    pub fn mip_count(&self) -> usize {
+        self.faces.len()
+    }
+}
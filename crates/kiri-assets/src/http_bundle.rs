@@ -0,0 +1,41 @@
+use anyhow::{Context, Result};
+
+use crate::bundle::AssetBundle;
+use crate::AssetRef;
+
+/// Loads a bundle served over HTTP(S), for CDN-hosted content (DLC,
+/// live-ops updates shipped without a client patch) rather than one
+/// bundled into the installed build.
+///
+/// Like [`crate::async_bundle::AsyncBundleReader`], this reads the whole
+/// bundle response body up front; range-request partial fetches are a
+/// natural extension once the on-disk/on-wire format supports seeking by
+/// byte range instead of a single opaque payload blob.
+pub struct HttpBundleReader {
+    bundle: AssetBundle,
+}
+
+impl HttpBundleReader {
+    pub async fn fetch(url: &str) -> Result<Self> {
+        let response = reqwest::get(url)
+            .await
+            .with_context(|| format!("Failed to request bundle at {url}"))?
+            .error_for_status()
+            .with_context(|| format!("Bundle server returned an error status for {url}"))?;
+
+        let bytes = response.bytes().await?;
+        let bundle: AssetBundle = bincode::deserialize(&bytes)
+            .with_context(|| format!("Failed to decode bundle fetched from {url}"))?;
+        bundle
+            .validate()
+            .with_context(|| format!("Bundle fetched from {url} failed validation"))?;
+
+        Ok(Self { bundle })
+    }
+
+    pub fn read_asset(&self, asset_ref: AssetRef) -> Result<Vec<u8>> {
+        self.bundle
+            .load(asset_ref)
+            .ok_or_else(|| anyhow::anyhow!("AssetRef {:?} not present in fetched bundle", asset_ref))
+    }
+}
@@ -1,3 +1,60 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:38558e290490382600462e83e43c410fbd54cb4e75ce17ff464b4d1cfdc35e21
-size 2105
+use serde::{Deserialize, Serialize};
+
+/// A baked texture: raw pixel data for every mip level, already in the
+/// pixel format the backend will upload it as. No decoding happens at
+/// load time — whatever `kiri-asset-pipe` wrote is what gets uploaded.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ImageAsset {
+    pub extent: [u32; 2],
+    pub format: ImageFormat,
+    /// One entry per mip level, largest first, tightly packed.
+    pub mips: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImageFormat {
+    Rgba8Unorm,
+    Rgba8Srgb,
+    Bc7Unorm,
+    Bc7Srgb,
+    Astc4x4Unorm,
+    Astc4x4Srgb,
+}
+
+impl ImageFormat {
+    pub fn is_compressed(self) -> bool {
+        !matches!(self, ImageFormat::Rgba8Unorm | ImageFormat::Rgba8Srgb)
+    }
+
+    pub fn is_srgb(self) -> bool {
+        matches!(
+            self,
+            ImageFormat::Rgba8Srgb | ImageFormat::Bc7Srgb | ImageFormat::Astc4x4Srgb
+        )
+    }
+}
+
+impl ImageAsset {
+    pub fn mip_count(&self) -> usize {
+        self.mips.len()
+    }
+}
+
+/// A baked volume texture: same layout convention as [`ImageAsset`] (raw
+/// pixel data per mip, largest first, tightly packed), but with a 3D
+/// extent for `TYPE_3D` images — color-grading LUTs and froxel fog media,
+/// mainly, which are small enough that compression is rarely worth it in
+/// practice but isn't ruled out by this format.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VolumeAsset {
+    pub extent: [u32; 3],
+    pub format: ImageFormat,
+    /// One entry per mip level, largest first, tightly packed.
+    pub mips: Vec<Vec<u8>>,
+}
+
+impl VolumeAsset {
+    pub fn mip_count(&self) -> usize {
+        self.mips.len()
+    }
+}
@@ -1,3 +1,54 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:32a836dcf86e4fe38bd6bfcc85151820b0ae4ffd4ba3a49f8a016015446cc9e1
-size 3645
+pub mod animation;
+pub mod async_bundle;
+pub mod audio;
+pub mod bundle;
+pub mod collision;
+pub mod effect;
+pub mod environment;
+pub mod http_bundle;
+pub mod image;
+pub mod lightmap;
+pub mod localization;
+pub mod material;
+pub mod mip_streaming;
+pub mod model;
+pub mod navmesh;
+pub mod picking;
+pub mod placeholder;
+pub mod reflection_probe;
+pub mod residency;
+pub mod skeleton;
+
+pub use animation::AnimationClipAsset;
+pub use audio::AudioAsset;
+pub use bundle::AssetBundle;
+pub use collision::CollisionAsset;
+pub use effect::EffectAsset;
+pub use environment::EnvironmentAsset;
+pub use image::{ImageAsset, VolumeAsset};
+pub use lightmap::LightmapAsset;
+pub use localization::StringTableAsset;
+pub use material::MaterialAsset;
+pub use model::ModelAsset;
+pub use navmesh::NavMeshAsset;
+pub use reflection_probe::ReflectionProbeAsset;
+pub use skeleton::{RetargetMapAsset, SkeletonAsset};
+
+use serde::{Deserialize, Serialize};
+
+/// A stable, content-independent reference to an asset inside a bundle.
+///
+/// `AssetRef`s are what gets serialized into other assets (a material
+/// pointing at its textures, a model pointing at its materials, ...)
+/// rather than indices into a particular bundle's table, so baked data
+/// keeps working if assets get reordered or repacked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct AssetRef(pub u64);
+
+impl AssetRef {
+    pub const NULL: AssetRef = AssetRef(0);
+
+    pub fn is_null(&self) -> bool {
+        self.0 == 0
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// A baked lightmap: precomputed diffuse irradiance for static geometry,
+/// produced by an offline GI bake and sampled through a mesh's lightmap
+/// UV channel ([`crate::model::Mesh::lightmap_uvs`]) instead of computed
+/// at runtime.
+///
+/// Stored as tightly packed RGB9E5-equivalent half floats rather than
+/// `Rgba8*` like [`crate::image::ImageAsset`] — baked bounce light
+/// routinely exceeds the `[0, 1]` range an LDR format can hold, and a
+/// lightmap has no alpha channel to spend on anything else.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LightmapAsset {
+    pub extent: [u32; 2],
+    /// `extent[0] * extent[1] * 3` half-float components (r, g, b per
+    /// texel), tightly packed, largest (and only) mip first — a lightmap
+    /// is sampled bilinearly at native resolution, so there's no
+    /// mip-chain use case the way there is for a material texture viewed
+    /// at varying distance.
+    pub texels: Vec<u16>,
+}
+
+impl LightmapAsset {
+    pub fn texel_count(&self) -> usize {
+        self.extent[0] as usize * self.extent[1] as usize
+    }
+}
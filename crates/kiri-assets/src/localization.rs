@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// CLDR's plural categories, simplified to the set actually distinct
+/// across the languages this project ships in — enough to pick the right
+/// form for "1 item" vs "5 items" without pulling in a full ICU plural
+/// rules table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+/// One localized string, either fixed or plural-sensitive. `{name}`-style
+/// placeholders inside either form are substituted by
+/// [`StringTableAsset::format`]; this type itself only stores the raw
+/// template text.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum StringEntry {
+    Simple(String),
+    Plural(HashMap<PluralCategory, String>),
+}
+
+/// Localized UI text, keyed by language then by string key, baked from
+/// source string tables the same way every other asset is baked so
+/// translators' work lands through the same review/bake pipeline as art
+/// and code.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct StringTableAsset {
+    /// Outer key is a language tag (`"en-US"`, `"ja"`, ...), matching
+    /// what [`StringTableService::set_language`] is given.
+    pub languages: HashMap<String, HashMap<String, StringEntry>>,
+}
+
+impl StringTableAsset {
+    /// Looks up `key`'s [`StringEntry::Simple`] text for `language`.
+    /// Returns `None` for a missing key, a missing language, or a key
+    /// that's actually a [`StringEntry::Plural`] — callers wanting plural
+    /// forms should use [`StringTableAsset::get_plural`].
+    pub fn get(&self, language: &str, key: &str) -> Option<&str> {
+        match self.languages.get(language)?.get(key)? {
+            StringEntry::Simple(text) => Some(text.as_str()),
+            StringEntry::Plural(_) => None,
+        }
+    }
+
+    /// Looks up the plural form of `key` matching `count`, falling back
+    /// to [`PluralCategory::Other`] if `count`'s exact category has no
+    /// translation (translators don't always provide every category, and
+    /// `Other` is required by CLDR to always be present).
+    pub fn get_plural(&self, language: &str, key: &str, count: u64) -> Option<&str> {
+        let forms = match self.languages.get(language)?.get(key)? {
+            StringEntry::Plural(forms) => forms,
+            StringEntry::Simple(_) => return None,
+        };
+        forms
+            .get(&plural_category_for(count))
+            .or_else(|| forms.get(&PluralCategory::Other))
+            .map(String::as_str)
+    }
+
+    /// Substitutes `{placeholder}` occurrences in `template` with values
+    /// from `args`, leaving unmatched placeholders untouched so a missing
+    /// arg shows up as an obvious `{typo}` in-game rather than silently
+    /// disappearing.
+    pub fn format(template: &str, args: &[(&str, &str)]) -> String {
+        let mut out = template.to_string();
+        for (name, value) in args {
+            out = out.replace(&format!("{{{name}}}"), value);
+        }
+        out
+    }
+}
+
+/// English-like pluralization: exactly one is [`PluralCategory::One`],
+/// everything else is [`PluralCategory::Other`]. This is wrong for many
+/// languages (Slavic few/many splits, no plural distinction in Japanese,
+/// ...) — real per-language rules are a follow-up once a language needing
+/// them actually ships; until then `get_plural`'s fallback to `Other`
+/// keeps every language's plural lookups working, just without the
+/// nuance those languages need.
+fn plural_category_for(count: u64) -> PluralCategory {
+    if count == 1 {
+        PluralCategory::One
+    } else {
+        PluralCategory::Other
+    }
+}
+
+/// Runtime string lookup with hot-swappable language selection — the
+/// thing UI code actually holds onto, as opposed to [`StringTableAsset`]
+/// which is just the baked data. Swapping [`StringTableService::language`]
+/// takes effect on the next lookup; nothing needs to be reloaded or
+/// re-baked, since every language's strings are already resident in the
+/// same asset.
+pub struct StringTableService {
+    table: StringTableAsset,
+    language: String,
+    /// Used when `language` has no translation for a requested key, so a
+    /// partially-translated language still shows text instead of a blank
+    /// label.
+    fallback_language: String,
+}
+
+impl StringTableService {
+    pub fn new(table: StringTableAsset, language: impl Into<String>) -> Self {
+        let language = language.into();
+        Self {
+            table,
+            fallback_language: language.clone(),
+            language,
+        }
+    }
+
+    pub fn set_language(&mut self, language: impl Into<String>) {
+        self.language = language.into();
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Looks up `key`, falling back to [`StringTableService::fallback_language`]
+    /// and finally to `key` itself so missing translations are visibly
+    /// wrong rather than invisible.
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.table
+            .get(&self.language, key)
+            .or_else(|| self.table.get(&self.fallback_language, key))
+            .unwrap_or(key)
+    }
+
+    pub fn get_plural<'a>(&'a self, key: &'a str, count: u64) -> &'a str {
+        self.table
+            .get_plural(&self.language, key, count)
+            .or_else(|| self.table.get_plural(&self.fallback_language, key, count))
+            .unwrap_or(key)
+    }
+
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        StringTableAsset::format(self.get(key), args)
+    }
+}
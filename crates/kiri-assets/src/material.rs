@@ -1,3 +1,111 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:3b7f19978f0cadf445d7276e22cb22d8493b40a7dc04ffc3102d8fb3114696fb
-size 4760
+use serde::{Deserialize, Serialize};
+
+use crate::AssetRef;
+
+/// Which optional texture slots of a [`MaterialAsset`] are actually bound.
+///
+/// Computed once via [`MaterialAsset::features`] and then used both by the
+/// baker (to pick or generate the right uber-shader permutation) and, once
+/// loaded, by the renderer (to decide which descriptor slots a draw needs
+/// bound) — so the bit layout is part of the runtime contract, not just an
+/// implementation detail of baking.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MaterialFeatures(u32);
+
+impl MaterialFeatures {
+    pub const NONE: MaterialFeatures = MaterialFeatures(0);
+    pub const BASE_COLOR: MaterialFeatures = MaterialFeatures(1 << 0);
+    pub const NORMAL_MAP: MaterialFeatures = MaterialFeatures(1 << 1);
+    pub const METALLIC_ROUGHNESS: MaterialFeatures = MaterialFeatures(1 << 2);
+    pub const OCCLUSION: MaterialFeatures = MaterialFeatures(1 << 3);
+    pub const EMISSIVE: MaterialFeatures = MaterialFeatures(1 << 4);
+
+    pub const fn empty() -> MaterialFeatures {
+        MaterialFeatures(0)
+    }
+
+    pub fn contains(&self, other: MaterialFeatures) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+}
+
+impl std::ops::BitOr for MaterialFeatures {
+    type Output = MaterialFeatures;
+
+    fn bitor(self, rhs: MaterialFeatures) -> MaterialFeatures {
+        MaterialFeatures(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for MaterialFeatures {
+    fn bitor_assign(&mut self, rhs: MaterialFeatures) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// A baked PBR material: texture slots plus the scalar factors that
+/// multiply them (or stand in for them, when a slot is unbound).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MaterialAsset {
+    pub name: String,
+
+    pub base_color: Option<AssetRef>,
+    pub normal: Option<AssetRef>,
+    pub metallic_roughness: Option<AssetRef>,
+    pub occlusion: Option<AssetRef>,
+    pub emissive: Option<AssetRef>,
+
+    pub base_color_factor: [f32; 4],
+    pub metallic_factor: f32,
+    pub roughness_factor: f32,
+    pub emissive_factor: [f32; 3],
+
+    pub two_sided: bool,
+}
+
+impl MaterialAsset {
+    /// Which of this material's optional texture slots are actually bound
+    /// to a real asset (as opposed to `None`, meaning "use the factor").
+    /// Drives which uber-shader permutation it needs.
+    pub fn features(&self) -> MaterialFeatures {
+        let mut features = MaterialFeatures::empty();
+        if self.base_color.is_some() {
+            features |= MaterialFeatures::BASE_COLOR;
+        }
+        if self.normal.is_some() {
+            features |= MaterialFeatures::NORMAL_MAP;
+        }
+        if self.metallic_roughness.is_some() {
+            features |= MaterialFeatures::METALLIC_ROUGHNESS;
+        }
+        if self.occlusion.is_some() {
+            features |= MaterialFeatures::OCCLUSION;
+        }
+        if self.emissive.is_some() {
+            features |= MaterialFeatures::EMISSIVE;
+        }
+        features
+    }
+}
+
+impl Default for MaterialAsset {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            base_color: None,
+            normal: None,
+            metallic_roughness: None,
+            occlusion: None,
+            emissive: None,
+            base_color_factor: [1.0, 1.0, 1.0, 1.0],
+            metallic_factor: 1.0,
+            roughness_factor: 1.0,
+            emissive_factor: [0.0, 0.0, 0.0],
+            two_sided: false,
+        }
+    }
+}
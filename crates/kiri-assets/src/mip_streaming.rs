@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use crate::image::ImageAsset;
+use crate::residency::ResidencyTracker;
+use crate::AssetRef;
+
+/// How many of a texture's mips are currently resident, counted from the
+/// smallest (mip tail) up — a texture is never partially missing its
+/// coarsest mip, only its finest ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResidencyLevel {
+    pub resident_mip_count: u32,
+    pub total_mip_count: u32,
+}
+
+impl ResidencyLevel {
+    pub fn is_fully_resident(&self) -> bool {
+        self.resident_mip_count == self.total_mip_count
+    }
+
+    /// Index of the finest mip currently resident, counting from 0 at the
+    /// full-resolution mip — what a sampler's `minLod` should be clamped
+    /// to until more mips stream in.
+    pub fn finest_resident_mip(&self) -> u32 {
+        self.total_mip_count - self.resident_mip_count
+    }
+}
+
+/// Tracks, per streamed texture, how many mips are currently resident, and
+/// decides which mip to stream in next under a shared memory budget.
+pub struct MipStreamer {
+    levels: HashMap<AssetRef, ResidencyLevel>,
+    /// How many of the coarsest mips get loaded immediately and never
+    /// evicted — cheap insurance against a texture popping in as solid
+    /// grey while its first real mip streams.
+    guaranteed_mip_count: u32,
+}
+
+impl MipStreamer {
+    pub fn new(guaranteed_mip_count: u32) -> Self {
+        Self {
+            levels: HashMap::new(),
+            guaranteed_mip_count,
+        }
+    }
+
+    pub fn register(&mut self, asset_ref: AssetRef, image: &ImageAsset) {
+        let total_mip_count = image.mip_count() as u32;
+        self.levels.insert(
+            asset_ref,
+            ResidencyLevel {
+                resident_mip_count: self.guaranteed_mip_count.min(total_mip_count),
+                total_mip_count,
+            },
+        );
+    }
+
+    pub fn level(&self, asset_ref: AssetRef) -> Option<ResidencyLevel> {
+        self.levels.get(&asset_ref).copied()
+    }
+
+    /// Requests one more mip of residency for `asset_ref`, subject to the
+    /// shared `budget`. Returns the byte size of the mip that should now
+    /// be uploaded, or `None` if the texture is already fully resident or
+    /// the budget has no room (callers should evict via
+    /// [`ResidencyTracker::candidates_to_reach`] and retry).
+    pub fn request_next_mip(
+        &mut self,
+        asset_ref: AssetRef,
+        image: &ImageAsset,
+        budget: &ResidencyTracker,
+    ) -> Option<u64> {
+        let level = self.levels.get_mut(&asset_ref)?;
+        if level.is_fully_resident() {
+            return None;
+        }
+
+        let next_mip_index = level.total_mip_count - level.resident_mip_count - 1;
+        let mip_bytes = image.mips.get(next_mip_index as usize)?.len() as u64;
+
+        if budget.would_exceed_budget(mip_bytes) {
+            return None;
+        }
+
+        level.resident_mip_count += 1;
+        Some(mip_bytes)
+    }
+
+    /// Drops the most recently streamed-in mip, e.g. after the residency
+    /// budget forced an eviction pass. Never drops below
+    /// `guaranteed_mip_count`.
+    pub fn evict_finest_mip(&mut self, asset_ref: AssetRef) {
+        if let Some(level) = self.levels.get_mut(&asset_ref) {
+            if level.resident_mip_count > self.guaranteed_mip_count {
+                level.resident_mip_count -= 1;
+            }
+        }
+    }
+}
+
+/// Extracts this image's coarsest `mip_count` mips into a standalone
+/// [`ImageAsset`], suitable for uploading synchronously and sampling from
+/// immediately while the rest of `image` streams in behind it — the
+/// "serve a blurry texture instead of hitching" half of progressive
+/// loading; `MipStreamer` above is the "decide which finer mip to stream
+/// in next" half. `mip_count` is clamped to at least 1 and at most
+/// `image.mip_count()`, so asking for more mips than exist just returns
+/// every mip unchanged. A zero-mip `image` — a malformed or fuzzed asset,
+/// not something a real bake should ever produce — is returned as-is
+/// rather than clamped up to a mip that doesn't exist.
+pub fn tail_image(image: &ImageAsset, mip_count: u32) -> ImageAsset {
+    if image.mips.is_empty() {
+        return image.clone();
+    }
+
+    let kept = (mip_count as usize).clamp(1, image.mips.len());
+    let dropped = image.mips.len() - kept;
+
+    ImageAsset {
+        extent: [
+            (image.extent[0] >> dropped).max(1),
+            (image.extent[1] >> dropped).max(1),
+        ],
+        format: image.format,
+        mips: image.mips[dropped..].to_vec(),
+    }
+}
+
+/// How many of a texture's finest mips a viewer at `distance` from its
+/// owning surface actually needs, given the surface subtends roughly
+/// `reference_distance` world units per texel at mip 0 — one mip coarser
+/// for each doubling of distance past that reference, the same falloff a
+/// GPU sampler's own mip selection uses for minification. Callers derive
+/// `distance` from a proxy's world-space bounds (e.g.
+/// `kiri-backend`'s `render_world::transform_bounds` applied to a
+/// [`crate::model::Mesh::bounds`]) minus the bounding sphere's radius, so
+/// a large object doesn't get penalized just because its center is far
+/// away while its near face is still close to the camera.
+pub fn desired_mip_count_for_distance(
+    distance: f32,
+    reference_distance: f32,
+    total_mip_count: u32,
+) -> u32 {
+    if distance <= reference_distance || reference_distance <= 0.0 {
+        return total_mip_count;
+    }
+
+    let mips_to_drop = (distance / reference_distance).log2().floor().max(0.0) as u32;
+    total_mip_count.saturating_sub(mips_to_drop).max(1)
+}
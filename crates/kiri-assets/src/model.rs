@@ -1,3 +1,327 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:40137a7aa09c0ce3455f8a68e5a7092202100555625417cce31afcdbc38654b3
-size 4549
+use serde::{Deserialize, Serialize};
+
+use crate::AssetRef;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Vertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub uv: [f32; 2],
+}
+
+/// An axis-aligned bounding box in the space its points were given in —
+/// object space for [`Mesh::bounds`]/[`ModelAsset::bounds`], world space
+/// once a renderer transforms it by a proxy's transform. The one shape
+/// this crate keeps bounds in; a bounding sphere, when one is needed for a
+/// cheaper cull test, is derived from it on demand via
+/// [`Aabb::bounding_sphere`] rather than stored and risking drifting out
+/// of sync with the box.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Aabb {
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+}
+
+impl Aabb {
+    /// The empty bounding box: `merge`-ing anything into it yields the
+    /// other box unchanged, so it's the correct starting accumulator for
+    /// [`ModelAsset::bounds`].
+    pub const EMPTY: Aabb = Aabb {
+        min: [f32::INFINITY; 3],
+        max: [f32::NEG_INFINITY; 3],
+    };
+
+    pub fn from_points(points: impl IntoIterator<Item = [f32; 3]>) -> Aabb {
+        let mut aabb = Aabb::EMPTY;
+        for point in points {
+            aabb = aabb.include(point);
+        }
+        aabb
+    }
+
+    pub fn include(&self, point: [f32; 3]) -> Aabb {
+        Aabb {
+            min: [
+                self.min[0].min(point[0]),
+                self.min[1].min(point[1]),
+                self.min[2].min(point[2]),
+            ],
+            max: [
+                self.max[0].max(point[0]),
+                self.max[1].max(point[1]),
+                self.max[2].max(point[2]),
+            ],
+        }
+    }
+
+    pub fn merge(&self, other: Aabb) -> Aabb {
+        Aabb {
+            min: [
+                self.min[0].min(other.min[0]),
+                self.min[1].min(other.min[1]),
+                self.min[2].min(other.min[2]),
+            ],
+            max: [
+                self.max[0].max(other.max[0]),
+                self.max[1].max(other.max[1]),
+                self.max[2].max(other.max[2]),
+            ],
+        }
+    }
+
+    pub fn center(&self) -> [f32; 3] {
+        [
+            (self.min[0] + self.max[0]) * 0.5,
+            (self.min[1] + self.max[1]) * 0.5,
+            (self.min[2] + self.max[2]) * 0.5,
+        ]
+    }
+
+    pub fn half_extents(&self) -> [f32; 3] {
+        [
+            (self.max[0] - self.min[0]) * 0.5,
+            (self.max[1] - self.min[1]) * 0.5,
+            (self.max[2] - self.min[2]) * 0.5,
+        ]
+    }
+
+    /// A sphere centered on [`Aabb::center`] with radius large enough to
+    /// enclose every corner — a looser fit than the tightest enclosing
+    /// sphere, but cheap enough to derive on every call instead of
+    /// caching, and precise enough for the coarse reject culling and
+    /// streaming distance heuristics actually need it for.
+    pub fn bounding_sphere(&self) -> ([f32; 3], f32) {
+        let half_extents = self.half_extents();
+        let radius = (half_extents[0] * half_extents[0]
+            + half_extents[1] * half_extents[1]
+            + half_extents[2] * half_extents[2])
+            .sqrt();
+        (self.center(), radius)
+    }
+}
+
+/// Bone indices and weights for one vertex, parallel to that vertex in
+/// [`Mesh::vertices`]. Kept as a separate stream rather than folded into
+/// [`Vertex`] since most meshes have none of this data at all, and the
+/// compute-skinning pass only binds it when `Mesh::skinning` is `Some`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct VertexSkinning {
+    pub bone_indices: [u16; 4],
+    pub bone_weights: [f32; 4],
+}
+
+/// Where in an atlased [`crate::LightmapAsset`] a mesh's baked irradiance
+/// lives. Most bakers pack many meshes into one shared lightmap atlas, so
+/// [`Mesh::lightmap_uvs`] alone (typically `[0, 1]` per mesh) isn't enough
+/// to address the right region — `uv_scale`/`uv_offset` remap it into the
+/// mesh's slice of the atlas.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct LightmapBinding {
+    pub lightmap: AssetRef,
+    pub uv_scale: [f32; 2],
+    pub uv_offset: [f32; 2],
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Mesh {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+    /// One entry per vertex, present only for skinned meshes.
+    #[serde(default)]
+    pub skinning: Option<Vec<VertexSkinning>>,
+    /// One entry per vertex, present only for meshes with baked GI — a
+    /// second UV channel distinct from [`Vertex::uv`], since a lightmap
+    /// atlas needs a non-overlapping unwrap that a material's tiling UVs
+    /// generally don't provide.
+    #[serde(default)]
+    pub lightmap_uvs: Option<Vec<[f32; 2]>>,
+    #[serde(default)]
+    pub lightmap: Option<LightmapBinding>,
+    /// One entry per vertex, present only for meshes with authored vertex
+    /// colors (vertex-painted foliage tint, baked AO, decal blend masks).
+    /// Kept at full `f32` precision here — `kiri-backend`'s vertex-format
+    /// registry quantizes to `u8` per channel at upload time, so the
+    /// asset itself doesn't lose precision a re-bake at a different
+    /// quality target might have wanted.
+    #[serde(default)]
+    pub vertex_colors: Option<Vec<[f32; 4]>>,
+    /// Index into the owning [`ModelAsset::material_slots`] this mesh
+    /// draws with — an indirection rather than an [`AssetRef`] embedded
+    /// directly here, so several meshes can share a slot and a runtime
+    /// override (team color, damage state) only has to touch the one
+    /// slot instead of every mesh using that material.
+    #[serde(default)]
+    pub material_slot: u32,
+}
+
+impl Mesh {
+    pub fn is_skinned(&self) -> bool {
+        self.skinning.is_some()
+    }
+
+    /// This mesh's object-space bounds, computed from its vertex positions
+    /// on each call — cheap enough to recompute here rather than
+    /// cache, so an importer or a future edit tool that mutates
+    /// `vertices` in place can't leave a stale box behind.
+    pub fn bounds(&self) -> Aabb {
+        Aabb::from_points(self.vertices.iter().map(|vertex| vertex.position))
+    }
+
+    pub fn has_baked_lighting(&self) -> bool {
+        self.lightmap.is_some() && self.lightmap_uvs.is_some()
+    }
+
+    /// Which of this mesh's optional vertex streams are populated —
+    /// consumed by `kiri-asset-pipe::material_compile::UberShaderCompiler`
+    /// to pick a shader permutation, the same way
+    /// [`crate::material::MaterialAsset::features`] drives permutation
+    /// selection for texture slots. Kept separate from
+    /// [`crate::material::MaterialFeatures`] since these are properties of
+    /// the mesh being drawn, not the material drawing it — a lightmapped
+    /// mesh needs a permutation that samples baked irradiance regardless
+    /// of which material it's rendered with.
+    pub fn features(&self) -> MeshFeatures {
+        let mut features = MeshFeatures::empty();
+        if self.has_baked_lighting() {
+            features |= MeshFeatures::LIGHTMAP_UV;
+        }
+        if self.vertex_colors.is_some() {
+            features |= MeshFeatures::VERTEX_COLOR;
+        }
+        features
+    }
+
+    /// Which [`VertexLayout`] this mesh's populated streams correspond to.
+    ///
+    /// Skinning takes precedence over the other optional streams when a
+    /// mesh has more than one: kiri's registry has no entry yet for, say,
+    /// a skinned mesh with vertex colors, and skinning is the stream that
+    /// changes draw behavior (it feeds a compute pre-skin pass, not just
+    /// the vertex-input layout) rather than just adding an attribute, so
+    /// it wins the tag.
+    pub fn layout(&self) -> VertexLayout {
+        if self.is_skinned() {
+            VertexLayout::Skinned
+        } else if self.vertex_colors.is_some() {
+            VertexLayout::StaticColor
+        } else if self.lightmap_uvs.is_some() {
+            VertexLayout::StaticUv2
+        } else {
+            VertexLayout::Static
+        }
+    }
+}
+
+/// Which of kiri's fixed vertex-buffer layouts a mesh's data lines up
+/// with — the vocabulary [`crate::effect::EffectAsset::vertex_layout`] and
+/// `kiri-backend`'s vertex-input state builder both speak, declared once
+/// here so a mesh's data and the pipeline drawing it can't silently
+/// disagree about what's actually in each vertex.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VertexLayout {
+    #[default]
+    Static,
+    Skinned,
+    StaticUv2,
+    StaticColor,
+}
+
+/// Which optional vertex streams a [`Mesh`] actually has populated — see
+/// [`Mesh::features`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MeshFeatures(u32);
+
+impl MeshFeatures {
+    pub const NONE: MeshFeatures = MeshFeatures(0);
+    pub const LIGHTMAP_UV: MeshFeatures = MeshFeatures(1 << 0);
+    pub const VERTEX_COLOR: MeshFeatures = MeshFeatures(1 << 1);
+
+    pub const fn empty() -> MeshFeatures {
+        MeshFeatures(0)
+    }
+
+    pub fn contains(&self, other: MeshFeatures) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+}
+
+impl std::ops::BitOr for MeshFeatures {
+    type Output = MeshFeatures;
+
+    fn bitor(self, rhs: MeshFeatures) -> MeshFeatures {
+        MeshFeatures(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for MeshFeatures {
+    fn bitor_assign(&mut self, rhs: MeshFeatures) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// A named attachment point on a [`ModelAsset`], baked from an empty or
+/// locator node in the source scene — a weapon's muzzle, a character's
+/// weapon-hand socket, a vehicle's seat. `local_translation`/`local_rotation`/
+/// `local_scale` are relative to `parent_bone_index`'s bone if set, so the
+/// socket follows that bone's animated transform, or to the model's own
+/// origin otherwise, for a socket fixed to the mesh itself.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SocketInfo {
+    pub name: String,
+    /// Index into the skeleton baked alongside this model, if this
+    /// socket is bone-relative rather than model-relative.
+    pub parent_bone_index: Option<u32>,
+    pub local_translation: [f32; 3],
+    pub local_rotation: [f32; 4],
+    pub local_scale: [f32; 3],
+}
+
+/// A baked model: one or more meshes (glTF primitives, in practice) that
+/// share a transform and a name; each mesh picks its material through
+/// [`Mesh::material_slot`], an index into [`ModelAsset::material_slots`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModelAsset {
+    pub name: String,
+    pub meshes: Vec<Mesh>,
+    /// Attachment points baked from the source scene's empties/locators.
+    /// Defaults to empty — older baked bundles predate this field.
+    #[serde(default)]
+    pub sockets: Vec<SocketInfo>,
+    /// This model's material slot table — [`Mesh::material_slot`] indexes
+    /// into it. Indirection rather than a material `AssetRef` embedded
+    /// directly on each `Mesh`, so a runtime instance can override one
+    /// slot (team color, damage state) via `kiri-backend`'s
+    /// `RenderProxy::material_overrides` without touching this asset or
+    /// any other instance sharing it.
+    #[serde(default)]
+    pub material_slots: Vec<AssetRef>,
+}
+
+impl ModelAsset {
+    pub fn material_refs(&self) -> Vec<AssetRef> {
+        self.material_slots.clone()
+    }
+
+    /// The object-space bounds of every mesh in this model merged
+    /// together — the aggregate a scene importer stamps onto its
+    /// instance's transform-hierarchy node, and what a renderer transforms
+    /// by that instance's world transform to get the box culling and
+    /// streaming actually test against.
+    pub fn bounds(&self) -> Aabb {
+        self.meshes
+            .iter()
+            .fold(Aabb::EMPTY, |acc, mesh| acc.merge(mesh.bounds()))
+    }
+
+    /// Looks up a socket by name — weapons/props attaching at runtime do
+    /// this once on equip rather than hardcoding a bone index, so
+    /// rearranging or renaming bones in a later re-import doesn't break
+    /// attachment.
+    pub fn socket(&self, name: &str) -> Option<&SocketInfo> {
+        self.sockets.iter().find(|socket| socket.name == name)
+    }
+}
@@ -0,0 +1,318 @@
+use std::collections::{BinaryHeap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+/// One convex polygon within a [`NavMeshTile`], wound counter-clockwise
+/// when viewed from above (+Y up) — the winding [`NavMeshAsset::raycast`]
+/// and [`NavMeshAsset::find_path`]'s edge-crossing tests assume.
+/// `neighbors[i]` is the polygon sharing the edge from `indices[i]` to
+/// `indices[(i + 1) % indices.len()]`, or `None` if that edge is a tile
+/// boundary (no walkable geometry on the other side, or an unstitched
+/// adjacent tile — see [`NavMeshTile`]'s doc comment).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NavPolygon {
+    pub indices: Vec<u32>,
+    pub neighbors: Vec<Option<u32>>,
+}
+
+/// One recast-style tile: a chunk of baked walkable surface, small enough
+/// that a streaming world can load/unload tiles independently of the rest
+/// of the navmesh. [`NavPolygon::neighbors`] only links polygons within
+/// the same tile — connecting polygons across a tile boundary is what a
+/// full recast/detour pipeline calls tile stitching, and isn't
+/// implemented by this bake step yet; two tiles that should be walkable
+/// into one another need an explicit [`OffMeshLink`] until it is.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NavMeshTile {
+    pub vertices: Vec<[f32; 3]>,
+    pub polygons: Vec<NavPolygon>,
+}
+
+/// A baked connection between two points not reachable by walking across
+/// polygon edges — a jump, a ladder, a drop-down — authored or generated
+/// separately from the walkable-surface bake, since it doesn't correspond
+/// to any triangle in the source geometry.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct OffMeshLink {
+    pub start: [f32; 3],
+    pub end: [f32; 3],
+    pub radius: f32,
+    pub bidirectional: bool,
+}
+
+/// Baked walkable-surface data for AI navigation, generated from scene or
+/// [`crate::collision::CollisionAsset`] geometry. Pathfinding logic
+/// (steering, avoidance, agent-specific costs) lives in game code, per
+/// this asset's own scope — what's here is just enough runtime query
+/// support ([`NavMeshAsset::find_path`], [`NavMeshAsset::raycast`]) that a
+/// gameplay team doesn't also have to write a navmesh polygon walker
+/// before they can use the baked data at all.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NavMeshAsset {
+    pub tiles: Vec<NavMeshTile>,
+    pub off_mesh_links: Vec<OffMeshLink>,
+}
+
+/// Addresses one polygon within a [`NavMeshAsset`] — a tile index plus a
+/// polygon index within that tile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NavPolyRef {
+    pub tile: u32,
+    pub polygon: u32,
+}
+
+fn polygon_vertices<'a>(tile: &'a NavMeshTile, polygon: &'a NavPolygon) -> Vec<[f32; 3]> {
+    polygon
+        .indices
+        .iter()
+        .map(|&i| tile.vertices[i as usize])
+        .collect()
+}
+
+fn polygon_centroid(vertices: &[[f32; 3]]) -> [f32; 3] {
+    let sum = vertices
+        .iter()
+        .fold([0.0, 0.0, 0.0], |acc, v| [acc[0] + v[0], acc[1] + v[1], acc[2] + v[2]]);
+    let count = vertices.len().max(1) as f32;
+    [sum[0] / count, sum[1] / count, sum[2] / count]
+}
+
+/// Point-in-convex-polygon test in the XZ plane (Y is a navmesh's height
+/// field, not part of its horizontal extent) — walkable-surface polygons
+/// are baked convex, so a half-plane test against every edge is enough,
+/// no ear-clipping or winding-number machinery needed.
+fn point_in_polygon_xz(point: [f32; 3], vertices: &[[f32; 3]]) -> bool {
+    vertices.iter().enumerate().all(|(i, &a)| {
+        let b = vertices[(i + 1) % vertices.len()];
+        let edge = (b[0] - a[0], b[2] - a[2]);
+        let to_point = (point[0] - a[0], point[2] - a[2]);
+        edge.0 * to_point.1 - edge.1 * to_point.0 >= -1e-5
+    })
+}
+
+/// Where segment `p1->p2` crosses segment `p3->p4` in the XZ plane, as
+/// `(t, point)` with `t` the fraction along `p1->p2`, or `None` if the
+/// segments don't cross within their own bounds.
+fn segment_intersect_xz(
+    p1: [f32; 3],
+    p2: [f32; 3],
+    p3: [f32; 3],
+    p4: [f32; 3],
+) -> Option<(f32, [f32; 3])> {
+    let (x1, z1) = (p1[0], p1[2]);
+    let (x2, z2) = (p2[0], p2[2]);
+    let (x3, z3) = (p3[0], p3[2]);
+    let (x4, z4) = (p4[0], p4[2]);
+
+    let denom = (x1 - x2) * (z3 - z4) - (z1 - z2) * (x3 - x4);
+    if denom.abs() < 1e-9 {
+        return None;
+    }
+
+    let t = ((x1 - x3) * (z3 - z4) - (z1 - z3) * (x3 - x4)) / denom;
+    let u = ((x1 - x3) * (z1 - z2) - (z1 - z3) * (x1 - x2)) / denom;
+    if !(0.0..=1.0).contains(&t) || !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let y = p1[1] + t * (p2[1] - p1[1]);
+    Some((t, [x1 + t * (x2 - x1), y, z1 + t * (z2 - z1)]))
+}
+
+#[derive(PartialEq)]
+struct ScoredPoly {
+    cost: f32,
+    poly: NavPolyRef,
+}
+
+impl Eq for ScoredPoly {}
+
+impl Ord for ScoredPoly {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Reversed so `BinaryHeap` (a max-heap) pops the lowest cost first.
+        other.cost.partial_cmp(&self.cost).unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+
+impl PartialOrd for ScoredPoly {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl NavMeshAsset {
+    /// The polygon containing `point` (tested in the XZ plane, so `point`
+    /// only needs to be roughly at the right height — a caller with a
+    /// precise 3D position should project it onto the ground first).
+    pub fn locate_polygon(&self, point: [f32; 3]) -> Option<NavPolyRef> {
+        for (tile_index, tile) in self.tiles.iter().enumerate() {
+            for (polygon_index, polygon) in tile.polygons.iter().enumerate() {
+                let vertices = polygon_vertices(tile, polygon);
+                if point_in_polygon_xz(point, &vertices) {
+                    return Some(NavPolyRef {
+                        tile: tile_index as u32,
+                        polygon: polygon_index as u32,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn tile(&self, poly_ref: NavPolyRef) -> &NavMeshTile {
+        &self.tiles[poly_ref.tile as usize]
+    }
+
+    fn polygon(&self, poly_ref: NavPolyRef) -> &NavPolygon {
+        &self.tile(poly_ref).polygons[poly_ref.polygon as usize]
+    }
+
+    /// Polygons directly reachable from `poly_ref` — its in-tile edge
+    /// neighbors plus any [`OffMeshLink`] whose `start` falls inside it.
+    fn neighbors(&self, poly_ref: NavPolyRef) -> Vec<NavPolyRef> {
+        let tile = self.tile(poly_ref);
+        let polygon = self.polygon(poly_ref);
+        let mut out: Vec<NavPolyRef> = polygon
+            .neighbors
+            .iter()
+            .filter_map(|neighbor| *neighbor)
+            .map(|polygon_index| NavPolyRef {
+                tile: poly_ref.tile,
+                polygon: polygon_index,
+            })
+            .collect();
+
+        for link in &self.off_mesh_links {
+            if point_in_polygon_xz(link.start, &polygon_vertices(tile, polygon)) {
+                if let Some(target) = self.locate_polygon(link.end) {
+                    out.push(target);
+                }
+            }
+        }
+
+        out
+    }
+
+    fn polygon_position(&self, poly_ref: NavPolyRef) -> [f32; 3] {
+        polygon_centroid(&polygon_vertices(self.tile(poly_ref), self.polygon(poly_ref)))
+    }
+
+    fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+        let d = [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+        (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt()
+    }
+
+    /// A* over the polygon adjacency graph from `start` to `end`, returned
+    /// as a coarse path through polygon centroids (`start`, one point per
+    /// polygon crossed, `end`) — not funnel-smoothed to the shortest taut
+    /// string the way a full detour query would be, so callers doing
+    /// precise steering should still raycast-simplify the result. Returns
+    /// `None` if either point isn't over the navmesh, or no polygon chain
+    /// connects them.
+    pub fn find_path(&self, start: [f32; 3], end: [f32; 3]) -> Option<Vec<[f32; 3]>> {
+        let start_poly = self.locate_polygon(start)?;
+        let end_poly = self.locate_polygon(end)?;
+
+        if start_poly == end_poly {
+            return Some(vec![start, end]);
+        }
+
+        let mut open = BinaryHeap::new();
+        let mut came_from: HashMap<NavPolyRef, NavPolyRef> = HashMap::new();
+        let mut cost_so_far: HashMap<NavPolyRef, f32> = HashMap::new();
+
+        cost_so_far.insert(start_poly, 0.0);
+        open.push(ScoredPoly {
+            cost: 0.0,
+            poly: start_poly,
+        });
+
+        while let Some(ScoredPoly { poly: current, .. }) = open.pop() {
+            if current == end_poly {
+                let mut path = vec![end];
+                let mut node = current;
+                while let Some(&previous) = came_from.get(&node) {
+                    path.push(self.polygon_position(node));
+                    node = previous;
+                }
+                path.push(start);
+                path.reverse();
+                return Some(path);
+            }
+
+            let current_cost = cost_so_far[&current];
+            for neighbor in self.neighbors(current) {
+                let step_cost = Self::distance(self.polygon_position(current), self.polygon_position(neighbor));
+                let new_cost = current_cost + step_cost;
+                if new_cost < *cost_so_far.get(&neighbor).unwrap_or(&f32::INFINITY) {
+                    cost_so_far.insert(neighbor, new_cost);
+                    came_from.insert(neighbor, current);
+                    let heuristic = Self::distance(self.polygon_position(neighbor), end);
+                    open.push(ScoredPoly {
+                        cost: new_cost + heuristic,
+                        poly: neighbor,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Walks the straight line from `start` to `end` across polygon
+    /// boundaries, the way a detour raycast query does: as long as the
+    /// segment stays over polygons connected to each other, it keeps
+    /// walking; the moment it would cross a boundary edge with no
+    /// neighbor (or run off the navmesh entirely), that crossing point is
+    /// the hit. Returns `None` if the line reaches `end` without ever
+    /// leaving walkable surface.
+    pub fn raycast(&self, start: [f32; 3], end: [f32; 3]) -> Option<[f32; 3]> {
+        let mut current_poly = self.locate_polygon(start)?;
+        let mut current_point = start;
+
+        // Bounded by total polygon count: a correct walk visits each
+        // polygon at most once before either reaching `end` or exiting
+        // through a boundary.
+        let max_steps = self.tiles.iter().map(|tile| tile.polygons.len()).sum::<usize>() + 1;
+
+        for _ in 0..max_steps {
+            let tile = self.tile(current_poly);
+            let polygon = self.polygon(current_poly);
+            let vertices = polygon_vertices(tile, polygon);
+
+            if point_in_polygon_xz(end, &vertices) {
+                return None;
+            }
+
+            let mut exit: Option<(f32, [f32; 3], Option<u32>)> = None;
+            for (i, &a) in vertices.iter().enumerate() {
+                let b = vertices[(i + 1) % vertices.len()];
+                if let Some((t, point)) = segment_intersect_xz(current_point, end, a, b) {
+                    if exit.map_or(true, |(closest_t, _, _)| t < closest_t) {
+                        exit = Some((t, point, polygon.neighbors[i]));
+                    }
+                }
+            }
+
+            let Some((_, point, neighbor)) = exit else {
+                // The segment never leaves this polygon but also never
+                // reaches `end` inside it — a degenerate/collinear case;
+                // report the current point as the hit rather than loop.
+                return Some(current_point);
+            };
+
+            match neighbor {
+                Some(neighbor_index) => {
+                    current_poly = NavPolyRef {
+                        tile: current_poly.tile,
+                        polygon: neighbor_index,
+                    };
+                    current_point = point;
+                }
+                None => return Some(point),
+            }
+        }
+
+        Some(current_point)
+    }
+}
@@ -0,0 +1,149 @@
+use crate::model::{Aabb, Mesh, ModelAsset};
+
+/// A ray in whatever space the geometry being tested is expressed in —
+/// object space against a [`Mesh`]/[`ModelAsset`] directly, or world space
+/// once a caller has transformed it by a proxy's inverse transform.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray {
+    pub origin: [f32; 3],
+    pub direction: [f32; 3],
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Slab-method ray/AABB test, returning the ray parameter `t` of the
+/// nearest intersection (clamped to 0 if the origin is already inside the
+/// box), or `None` if the ray misses entirely. Used as a cheap reject
+/// before [`ray_mesh_intersect`] bothers walking triangles.
+pub fn ray_aabb_intersect(ray: &Ray, aabb: &Aabb) -> Option<f32> {
+    let mut t_min = 0.0f32;
+    let mut t_max = f32::INFINITY;
+
+    for axis in 0..3 {
+        let origin = ray.origin[axis];
+        let direction = ray.direction[axis];
+        let min = aabb.min[axis];
+        let max = aabb.max[axis];
+
+        if direction.abs() < f32::EPSILON {
+            if origin < min || origin > max {
+                return None;
+            }
+            continue;
+        }
+
+        let inv_direction = 1.0 / direction;
+        let mut t0 = (min - origin) * inv_direction;
+        let mut t1 = (max - origin) * inv_direction;
+        if t0 > t1 {
+            std::mem::swap(&mut t0, &mut t1);
+        }
+
+        t_min = t_min.max(t0);
+        t_max = t_max.min(t1);
+        if t_min > t_max {
+            return None;
+        }
+    }
+
+    Some(t_min)
+}
+
+/// Möller–Trumbore ray/triangle test, returning the ray parameter `t` of
+/// the intersection point, or `None` for a miss or a triangle seen exactly
+/// edge-on. Backface triangles are still reported — picking wants to hit
+/// whatever's under the cursor regardless of winding, unlike a rasterizer.
+pub fn ray_triangle_intersect(ray: &Ray, a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> Option<f32> {
+    let edge1 = sub(b, a);
+    let edge2 = sub(c, a);
+    let p = cross(ray.direction, edge2);
+    let determinant = dot(edge1, p);
+
+    if determinant.abs() < f32::EPSILON {
+        return None;
+    }
+
+    let inv_determinant = 1.0 / determinant;
+    let t_vec = sub(ray.origin, a);
+    let u = dot(t_vec, p) * inv_determinant;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let q = cross(t_vec, edge1);
+    let v = dot(ray.direction, q) * inv_determinant;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    let t = dot(edge2, q) * inv_determinant;
+    if t < 0.0 {
+        return None;
+    }
+
+    Some(t)
+}
+
+/// Closest ray/[`Mesh`] intersection, tested in object space directly
+/// against [`Mesh::vertices`] — position is never quantized, even by
+/// `kiri-backend`'s vertex-format registry, so picking doesn't need to
+/// decode a GPU vertex stream to get exact hit points. Returns the index
+/// of the first vertex of the hit triangle (into [`Mesh::vertices`]) and
+/// the ray parameter `t` of the closest hit, or `None` if nothing in the
+/// mesh is hit.
+pub fn ray_mesh_intersect(ray: &Ray, mesh: &Mesh) -> Option<(u32, f32)> {
+    if ray_aabb_intersect(ray, &mesh.bounds()).is_none() {
+        return None;
+    }
+
+    let mut closest: Option<(u32, f32)> = None;
+    for triangle in mesh.indices.chunks_exact(3) {
+        let [i0, i1, i2] = [triangle[0], triangle[1], triangle[2]];
+        let a = mesh.vertices[i0 as usize].position;
+        let b = mesh.vertices[i1 as usize].position;
+        let c = mesh.vertices[i2 as usize].position;
+
+        if let Some(t) = ray_triangle_intersect(ray, a, b, c) {
+            if closest.map_or(true, |(_, closest_t)| t < closest_t) {
+                closest = Some((i0, t));
+            }
+        }
+    }
+
+    closest
+}
+
+/// Closest ray/[`ModelAsset`] intersection across every mesh, in the same
+/// object space as [`Mesh::vertices`]. Returns the index of the hit mesh
+/// within [`ModelAsset::meshes`], the index of the hit triangle's first
+/// vertex, and the ray parameter `t` of the hit — the CPU-side half of
+/// picking; a caller mapping this back to a scene needs to also know which
+/// proxy's transform the ray was brought into this model's object space
+/// with.
+pub fn ray_model_intersect(ray: &Ray, model: &ModelAsset) -> Option<(usize, u32, f32)> {
+    let mut closest: Option<(usize, u32, f32)> = None;
+
+    for (mesh_index, mesh) in model.meshes.iter().enumerate() {
+        if let Some((vertex_index, t)) = ray_mesh_intersect(ray, mesh) {
+            if closest.map_or(true, |(_, _, closest_t)| t < closest_t) {
+                closest = Some((mesh_index, vertex_index, t));
+            }
+        }
+    }
+
+    closest
+}
@@ -0,0 +1,146 @@
+//! Placeholder assets substituted when a load or deserialize fails, so
+//! one corrupt or missing asset degrades visually — a magenta checker
+//! texture, a flat-magenta material, a unit cube standing in for a
+//! model — instead of propagating an `io::Error`/`bincode::Error` up the
+//! stack and taking down whatever was loading a whole scene. Pairs with
+//! [`AssetLoadError`], which every fallback via
+//! [`deserialize_or_placeholder`] raises on the caller's
+//! `kiri_core::event_bus::EventBus`, so the failure still surfaces
+//! somewhere (a log line, an editor warning panel) instead of only as
+//! an oddly magenta object in-game.
+
+use kiri_core::event_bus::EventWriter;
+use serde::de::DeserializeOwned;
+
+use crate::image::{ImageAsset, ImageFormat};
+use crate::material::MaterialAsset;
+use crate::model::{Mesh, ModelAsset, Vertex};
+use crate::AssetRef;
+
+/// Raised once per fallback by [`deserialize_or_placeholder`] — `message`
+/// is the deserialize/lookup error's `Display` text, kept as a `String`
+/// rather than the original error type so this can cross an
+/// [`kiri_core::event_bus::EventBus`] without forcing every event
+/// consumer to depend on `bincode`/`anyhow`.
+#[derive(Clone, Debug)]
+pub struct AssetLoadError {
+    pub asset_ref: AssetRef,
+    pub message: String,
+}
+
+const CHECKER_EXTENT: u32 = 64;
+const CHECKER_TILE: u32 = 8;
+
+/// An 8px-tile magenta/black checkerboard, the conventional "this texture
+/// failed to load" placeholder — loud and unmistakable rather than
+/// blending into whatever it's mapped onto, unlike a flat mid-gray would.
+pub fn checker_texture() -> ImageAsset {
+    let mut pixels = Vec::with_capacity((CHECKER_EXTENT * CHECKER_EXTENT * 4) as usize);
+    for y in 0..CHECKER_EXTENT {
+        for x in 0..CHECKER_EXTENT {
+            let tile_on = (x / CHECKER_TILE + y / CHECKER_TILE) % 2 == 0;
+            let color: [u8; 4] = if tile_on { [255, 0, 255, 255] } else { [0, 0, 0, 255] };
+            pixels.extend_from_slice(&color);
+        }
+    }
+
+    ImageAsset {
+        extent: [CHECKER_EXTENT, CHECKER_EXTENT],
+        format: ImageFormat::Rgba8Unorm,
+        mips: vec![pixels],
+    }
+}
+
+/// Flat magenta, unlit-looking (zero metallic, full roughness) stand-in
+/// for a material that failed to load. Uses `base_color_factor` rather
+/// than binding [`checker_texture`] through `base_color` — the factor
+/// alone is enough to read as "this is wrong" at a glance, and leaving
+/// every texture slot `None` means this placeholder has no [`AssetRef`]
+/// of its own to keep valid.
+pub fn error_material() -> MaterialAsset {
+    MaterialAsset {
+        name: "placeholder_error".to_string(),
+        base_color: None,
+        normal: None,
+        metallic_roughness: None,
+        occlusion: None,
+        emissive: None,
+        base_color_factor: [1.0, 0.0, 1.0, 1.0],
+        metallic_factor: 0.0,
+        roughness_factor: 1.0,
+        emissive_factor: [0.0, 0.0, 0.0],
+        two_sided: true,
+    }
+}
+
+/// A 1x1x1 cube centered on the origin, flat-shaded (4 duplicated
+/// vertices per face so each face keeps its own normal) — the stand-in
+/// for a model that failed to load, since dropping the draw call
+/// entirely would hide that anything was supposed to be there at all.
+pub fn unit_cube_model() -> ModelAsset {
+    const FACES: [([f32; 3], [[f32; 3]; 4]); 6] = [
+        ([1.0, 0.0, 0.0], [[0.5, -0.5, -0.5], [0.5, 0.5, -0.5], [0.5, 0.5, 0.5], [0.5, -0.5, 0.5]]),
+        ([-1.0, 0.0, 0.0], [[-0.5, -0.5, 0.5], [-0.5, 0.5, 0.5], [-0.5, 0.5, -0.5], [-0.5, -0.5, -0.5]]),
+        ([0.0, 1.0, 0.0], [[-0.5, 0.5, -0.5], [-0.5, 0.5, 0.5], [0.5, 0.5, 0.5], [0.5, 0.5, -0.5]]),
+        ([0.0, -1.0, 0.0], [[-0.5, -0.5, 0.5], [-0.5, -0.5, -0.5], [0.5, -0.5, -0.5], [0.5, -0.5, 0.5]]),
+        ([0.0, 0.0, 1.0], [[-0.5, -0.5, 0.5], [0.5, -0.5, 0.5], [0.5, 0.5, 0.5], [-0.5, 0.5, 0.5]]),
+        ([0.0, 0.0, -1.0], [[0.5, -0.5, -0.5], [-0.5, -0.5, -0.5], [-0.5, 0.5, -0.5], [0.5, 0.5, -0.5]]),
+    ];
+    const FACE_UVS: [[f32; 2]; 4] = [[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]];
+
+    let mut vertices = Vec::with_capacity(24);
+    let mut indices = Vec::with_capacity(36);
+    for (normal, corners) in FACES {
+        let base = vertices.len() as u32;
+        for (corner, uv) in corners.iter().zip(FACE_UVS) {
+            vertices.push(Vertex {
+                position: *corner,
+                normal,
+                uv,
+            });
+        }
+        indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+
+    ModelAsset {
+        name: "placeholder_unit_cube".to_string(),
+        meshes: vec![Mesh {
+            vertices,
+            indices,
+            skinning: None,
+            lightmap_uvs: None,
+            lightmap: None,
+            vertex_colors: None,
+            material_slot: 0,
+        }],
+        sockets: Vec::new(),
+        material_slots: Vec::new(),
+    }
+}
+
+/// Deserializes `bytes` as `T`, or raises an [`AssetLoadError`] on
+/// `errors` and returns `placeholder()` instead — `bytes` is `None` for
+/// an `asset_ref` the bundle never had an entry for at all, treated the
+/// same as a present-but-corrupt payload since either way the caller
+/// gets back a usable `T`. The caller passes whichever of
+/// [`checker_texture`]/[`error_material`]/[`unit_cube_model`] matches
+/// the asset kind it's loading as the `placeholder` closure.
+pub fn deserialize_or_placeholder<T: DeserializeOwned>(
+    bytes: Option<&[u8]>,
+    asset_ref: AssetRef,
+    errors: &EventWriter<AssetLoadError>,
+    placeholder: impl FnOnce() -> T,
+) -> T {
+    let decoded = match bytes {
+        Some(bytes) => bincode::deserialize(bytes).map_err(|err| err.to_string()),
+        None => Err(format!("AssetRef {:?} not present in bundle", asset_ref)),
+    };
+
+    match decoded {
+        Ok(value) => value,
+        Err(message) => {
+            errors.send(AssetLoadError { asset_ref, message });
+            placeholder()
+        }
+    }
+}
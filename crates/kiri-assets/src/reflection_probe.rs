@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use crate::AssetRef;
+
+/// The influence volume a reflection probe blends within, and (for `Box`)
+/// parallax-corrects against. `Sphere` has no meaningful parallax
+/// correction shape of its own — probes placed in roughly spherical spaces
+/// (a domed room, an open courtyard) use it and skip the box intersection
+/// math entirely.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum ProbeInfluence {
+    Box { half_extents: [f32; 3] },
+    Sphere { radius: f32 },
+}
+
+/// A placed reflection probe: where it was baked from, what volume it
+/// influences, and the parallax-correction box (when different from the
+/// influence volume — a probe's influence often fades out well before the
+/// room's walls, which is what the correction box should actually match).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReflectionProbeAsset {
+    pub position: [f32; 3],
+    pub environment: AssetRef,
+    pub influence: ProbeInfluence,
+    /// `None` disables parallax correction (the baked cubemap is sampled
+    /// as if from an infinitely distant environment) — appropriate for a
+    /// large outdoor probe where the correction wouldn't be noticeable
+    /// anyway and isn't worth the extra ray-box test.
+    pub parallax_correction: Option<ParallaxCorrectionBox>,
+    /// Blend priority when a shading point falls in more than one probe's
+    /// influence volume — higher wins more weight. Lets a small, precise
+    /// probe (inside one room) dominate over a large enclosing one (the
+    /// building it's in) without relying on volume size alone as a proxy
+    /// for specificity.
+    pub priority: i32,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct ParallaxCorrectionBox {
+    pub center: [f32; 3],
+    pub half_extents: [f32; 3],
+}
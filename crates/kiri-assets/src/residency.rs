@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use kiri_core::cvar::{CvarRange, CvarRegistry, CvarValue};
+
+use crate::AssetRef;
+
+/// Tracks how many bytes of CPU/GPU memory each loaded asset currently
+/// holds, and refuses new residency once a configured budget is hit
+/// until the caller evicts something.
+///
+/// This is bookkeeping only — it doesn't itself free GPU resources or
+/// decide *which* asset to evict; callers (the texture streaming system,
+/// the model cache, ...) ask [`ResidencyTracker::would_exceed_budget`]
+/// before loading, and report evictions back through
+/// [`ResidencyTracker::remove`] once they've actually torn something down.
+pub struct ResidencyTracker {
+    budget_bytes: u64,
+    resident: HashMap<AssetRef, u64>,
+    total_bytes: u64,
+}
+
+impl ResidencyTracker {
+    pub fn new(budget_bytes: u64) -> Self {
+        Self {
+            budget_bytes,
+            resident: HashMap::new(),
+            total_bytes: 0,
+        }
+    }
+
+    pub fn would_exceed_budget(&self, additional_bytes: u64) -> bool {
+        self.total_bytes + additional_bytes > self.budget_bytes
+    }
+
+    pub fn insert(&mut self, asset_ref: AssetRef, size_bytes: u64) {
+        if let Some(previous) = self.resident.insert(asset_ref, size_bytes) {
+            self.total_bytes -= previous;
+        }
+        self.total_bytes += size_bytes;
+    }
+
+    pub fn remove(&mut self, asset_ref: AssetRef) {
+        if let Some(size_bytes) = self.resident.remove(&asset_ref) {
+            self.total_bytes -= size_bytes;
+        }
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    pub fn budget_bytes(&self) -> u64 {
+        self.budget_bytes
+    }
+
+    pub fn set_budget_bytes(&mut self, budget_bytes: u64) {
+        self.budget_bytes = budget_bytes;
+    }
+
+    /// Registers `streaming.budget_bytes` on `registry` at this
+    /// tracker's current budget. `ResidencyTracker` has no per-frame hook
+    /// of its own to poll a registry from — a caller applies a changed
+    /// value the same way it already applies anything else it tunes once
+    /// per frame, via `tracker.set_budget_bytes(registry.get(...))`.
+    pub fn register_cvar(&self, registry: &mut CvarRegistry) {
+        registry.register(
+            "streaming.budget_bytes",
+            CvarValue::Int(self.budget_bytes as i64),
+            Some(CvarRange::Int { min: 0, max: i64::MAX }),
+            "Maximum combined CPU/GPU memory streamed assets may occupy, in bytes.",
+        );
+    }
+
+    pub fn utilization(&self) -> f32 {
+        self.total_bytes as f32 / self.budget_bytes.max(1) as f32
+    }
+
+    /// Picks eviction candidates (largest first, as a simple stand-in for
+    /// an LRU policy) until removing them would bring usage back under
+    /// `target_bytes`. Returns the refs to evict; does not remove them —
+    /// callers must call [`ResidencyTracker::remove`] once they've
+    /// actually freed each one.
+    pub fn candidates_to_reach(&self, target_bytes: u64) -> Vec<AssetRef> {
+        let mut entries: Vec<(AssetRef, u64)> =
+            self.resident.iter().map(|(&r, &size)| (r, size)).collect();
+        entries.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+
+        let mut freed = 0;
+        let mut out = Vec::new();
+        for (asset_ref, size) in entries {
+            if self.total_bytes - freed <= target_bytes {
+                break;
+            }
+            freed += size;
+            out.push(asset_ref);
+        }
+        out
+    }
+}
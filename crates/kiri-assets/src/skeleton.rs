@@ -0,0 +1,118 @@
+//! Skeleton identity and retargeting. Bones are addressed purely by
+//! index elsewhere in this crate ([`crate::model::VertexSkinning::bone_indices`],
+//! [`crate::animation::BoneTrack::bone_index`]) with nothing tying that
+//! index back to a human-readable name or to another skeleton's
+//! compatible index — a [`SkeletonAsset`] is that missing piece: a bone
+//! name table plus a hierarchy, and a [`SkeletonAsset::signature`] for
+//! asking "is a clip baked against skeleton A safe to play on skeleton
+//! B unchanged". [`RetargetMapAsset`] is for the case where it isn't.
+
+use kiri_core::name::Name;
+use serde::{Deserialize, Serialize};
+
+/// One bone's identity within a [`SkeletonAsset`]. Kept as plain text
+/// rather than just a [`Name`] since this is baked asset data read once
+/// at load (an editor bone picker, a retargeting map build), not a
+/// per-frame hot path — [`SkeletonAsset::bone_index`] hashes it on the
+/// way in instead of storing the hash redundantly alongside.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BoneInfo {
+    pub name: String,
+    /// Index into the same [`SkeletonAsset::bones`] array, or `None` for
+    /// a root bone. Always less than this bone's own index — bones are
+    /// baked in hierarchy order, parents before children.
+    pub parent_index: Option<u32>,
+}
+
+/// A skeleton's bone hierarchy and name table, baked alongside whichever
+/// [`crate::ModelAsset`]/[`crate::AnimationClipAsset`]s were authored
+/// against it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SkeletonAsset {
+    pub bones: Vec<BoneInfo>,
+}
+
+impl SkeletonAsset {
+    /// Index of the bone named `name`, or `None` if this skeleton has
+    /// none by that name. Compares [`Name`] hashes rather than the raw
+    /// strings, so building a [`RetargetMapAsset`] over a skeleton with
+    /// hundreds of bones doesn't do a full string compare per candidate.
+    pub fn bone_index(&self, name: &str) -> Option<u32> {
+        let target = Name::new(name);
+        self.bones
+            .iter()
+            .position(|bone| Name::new(&bone.name) == target)
+            .map(|index| index as u32)
+    }
+
+    /// A hash of this skeleton's bone names (in order) and parent
+    /// indices. Two skeletons with the same signature have identical
+    /// topology and naming, so a clip or [`RetargetMapAsset`] baked
+    /// against one is guaranteed to apply to the other's indices
+    /// unchanged — this says nothing about the rest pose, so two
+    /// differently-posed but structurally identical skeletons still
+    /// share a signature.
+    pub fn signature(&self) -> u64 {
+        let mut hash = 0xcbf2_9ce4_8422_2325u64;
+        for bone in &self.bones {
+            hash = fold(hash, Name::new(&bone.name).hash());
+            hash = fold(hash, bone.parent_index.map_or(u32::MAX, |index| index) as u64);
+        }
+        hash
+    }
+}
+
+fn fold(hash: u64, value: u64) -> u64 {
+    (hash ^ value).wrapping_mul(0x100_0000_01b3)
+}
+
+/// Maps bone indices from a source skeleton (the one a clip was authored
+/// against) to a structurally different target skeleton, so the clip can
+/// drive a compatible character without being re-baked — built once,
+/// then just an index lookup at runtime like any other bone index in
+/// this crate.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RetargetMapAsset {
+    pub source_signature: u64,
+    pub target_signature: u64,
+    /// `target_bone_indices[i]` is where source bone `i` maps to on the
+    /// target skeleton, or `None` if the target has no corresponding
+    /// bone — an accessory or twist bone the target rig lacks, say —
+    /// and that source bone's motion is simply dropped when retargeting.
+    pub target_bone_indices: Vec<Option<u32>>,
+}
+
+impl RetargetMapAsset {
+    /// Builds a retargeting map by matching `source`'s and `target`'s
+    /// bones by name — the common case where both rigs share a naming
+    /// convention and differ only in which bones each has, or their
+    /// order.
+    pub fn from_matching_names(source: &SkeletonAsset, target: &SkeletonAsset) -> Self {
+        let target_bone_indices = source
+            .bones
+            .iter()
+            .map(|bone| target.bone_index(&bone.name))
+            .collect();
+
+        Self {
+            source_signature: source.signature(),
+            target_signature: target.signature(),
+            target_bone_indices,
+        }
+    }
+
+    /// Whether this map still applies to `source`/`target` — a map
+    /// loaded alongside skeletons that have since changed (a bone
+    /// renamed, reordered, or added) should be rebuilt rather than
+    /// silently misapplied.
+    pub fn matches(&self, source: &SkeletonAsset, target: &SkeletonAsset) -> bool {
+        self.source_signature == source.signature() && self.target_signature == target.signature()
+    }
+
+    pub fn map_bone_index(&self, source_bone_index: u32) -> Option<u32> {
+        self.target_bone_indices
+            .get(source_bone_index as usize)
+            .copied()
+            .flatten()
+    }
+}
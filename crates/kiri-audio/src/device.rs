@@ -0,0 +1,101 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use kiri_core::cvar::{CvarRange, CvarRegistry, CvarValue};
+
+use crate::listener::Listener;
+use crate::mixer::Mixer;
+use crate::voice::PlayCommand;
+
+/// Owns the `cpal` output stream and the [`Mixer`] driving it. Dropping an
+/// `AudioDevice` stops the stream; there is deliberately no way to get the
+/// stream back out, since nothing outside this module should ever touch
+/// `cpal` types directly.
+pub struct AudioDevice {
+    stream: cpal::Stream,
+    listener: Arc<Mutex<Listener>>,
+    master_volume: Arc<Mutex<f32>>,
+}
+
+impl AudioDevice {
+    /// Opens the host's default output device and starts the mixer
+    /// running on it. Returns the device (keep it alive for as long as
+    /// audio should play) and the sender half of the command queue the
+    /// game thread uses to start voices.
+    pub fn open() -> Result<(AudioDevice, crossbeam_channel::Sender<PlayCommand>)> {
+        let host = cpal::default_host();
+        let device = host
+            .default_output_device()
+            .context("No default audio output device")?;
+        let config = device
+            .default_output_config()
+            .context("Failed to query default output config")?;
+
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let mut mixer = Mixer::new(receiver);
+        let listener = Arc::new(Mutex::new(Listener::new([0.0, 0.0, 0.0])));
+        let listener_for_callback = listener.clone();
+        let master_volume = Arc::new(Mutex::new(1.0f32));
+        let master_volume_for_callback = master_volume.clone();
+
+        let stream = device.build_output_stream(
+            &config.into(),
+            move |data: &mut [f32], _info: &cpal::OutputCallbackInfo| {
+                let listener = *listener_for_callback.lock().unwrap();
+                let master_volume = *master_volume_for_callback.lock().unwrap();
+                mixer.mix_into(data, &listener, master_volume);
+            },
+            |err| log::error!("Audio output stream error: {err}"),
+            None,
+        )?;
+        stream.play()?;
+
+        Ok((
+            AudioDevice {
+                stream,
+                listener,
+                master_volume,
+            },
+            sender,
+        ))
+    }
+
+    /// Updates the listener transform the mixer spatializes 3D voices
+    /// against. Cheap to call every frame from the game thread — it just
+    /// takes a brief lock, the callback never holds it for longer than
+    /// copying a few floats.
+    pub fn set_listener(&self, listener: Listener) {
+        *self.listener.lock().unwrap() = listener;
+    }
+
+    pub fn set_master_volume(&self, volume: f32) {
+        *self.master_volume.lock().unwrap() = volume;
+    }
+
+    /// Registers `audio.master_volume` on `registry` and wires it so
+    /// changing it (from the console, a config file, or directly) takes
+    /// effect on the next mix callback — the same cross-thread hand-off
+    /// [`AudioDevice::set_listener`] uses, just driven by the cvar
+    /// registry's change callback instead of a direct call.
+    pub fn bind_master_volume_cvar(&self, registry: &mut CvarRegistry) {
+        registry.register(
+            "audio.master_volume",
+            CvarValue::Float(1.0),
+            Some(CvarRange::Float { min: 0.0, max: 1.0 }),
+            "Overall output volume multiplier, applied on top of every bus.",
+        );
+
+        let master_volume = self.master_volume.clone();
+        let _ = registry.on_change("audio.master_volume", move |value| {
+            if let Some(volume) = value.as_float() {
+                *master_volume.lock().unwrap() = volume as f32;
+            }
+        });
+    }
+}
+
+// `cpal::Stream` is not `Send` on every platform; kiri only ever creates
+// and drops it from the thread that owns audio playback, so this is safe
+// in practice but not something the type system can verify for us.
+unsafe impl Send for AudioDevice {}
@@ -0,0 +1,16 @@
+//! Runtime audio: opens an output device through `cpal`, mixes active
+//! voices on volume-controlled buses, and spatializes 3D voices against a
+//! listener transform. Game threads start voices through a lock-free
+//! command queue; the listener transform and master volume are shared
+//! with the audio callback through mutexes that each side holds only long
+//! enough to copy a few floats.
+
+pub mod device;
+pub mod listener;
+pub mod mixer;
+pub mod voice;
+
+pub use device::AudioDevice;
+pub use listener::Listener;
+pub use mixer::{Bus, BusId, Mixer};
+pub use voice::{PlayCommand, Voice, VoiceId};
@@ -0,0 +1,55 @@
+/// The camera (or ears-equivalent) position/orientation 3D voices are
+/// spatialized against. Updated once per frame from the game thread and
+/// read by the mixer on the audio callback thread.
+#[derive(Clone, Copy, Debug)]
+pub struct Listener {
+    pub position: [f32; 3],
+    pub forward: [f32; 3],
+    pub right: [f32; 3],
+}
+
+impl Listener {
+    pub fn new(position: [f32; 3]) -> Self {
+        Self {
+            position,
+            forward: [0.0, 0.0, -1.0],
+            right: [1.0, 0.0, 0.0],
+        }
+    }
+
+    /// Returns `(pan, attenuation)` for a source at `world_position`: `pan`
+    /// is -1 (full left) to 1 (full right), `attenuation` is a 0..1 volume
+    /// multiplier from distance falloff. The mixer applies `pan` as a
+    /// linear balance (the opposite channel's gain scaled by `1 - |pan|`),
+    /// not equal-power panning or an HRTF.
+    pub fn pan_and_attenuation(&self, world_position: [f32; 3]) -> (f32, f32) {
+        let to_source = sub(world_position, self.position);
+        let distance = length(to_source).max(0.0001);
+
+        let pan = dot(normalize(to_source), self.right).clamp(-1.0, 1.0);
+
+        const MIN_DISTANCE: f32 = 1.0;
+        const MAX_DISTANCE: f32 = 40.0;
+        let attenuation = (MIN_DISTANCE / distance.max(MIN_DISTANCE)).clamp(0.0, 1.0);
+        let attenuation = if distance > MAX_DISTANCE { 0.0 } else { attenuation };
+
+        (pan, attenuation)
+    }
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn length(v: [f32; 3]) -> f32 {
+    dot(v, v).sqrt()
+}
+
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let len = length(v).max(0.0001);
+    [v[0] / len, v[1] / len, v[2] / len]
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use kiri_assets::audio::{AudioAsset, AudioFormat};
+use kiri_assets::AssetRef;
+
+use crate::listener::Listener;
+use crate::voice::{PlayCommand, Voice, VoiceId};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BusId(pub u32);
+
+/// A named group of voices sharing one volume control (music, SFX, voice
+/// chat, ...), so the game can duck SFX during a cutscene without
+/// touching every individual voice.
+#[derive(Clone, Copy, Debug)]
+pub struct Bus {
+    pub volume: f32,
+}
+
+impl Default for Bus {
+    fn default() -> Self {
+        Self { volume: 1.0 }
+    }
+}
+
+/// Mixes every active [`Voice`] into a stereo output buffer once per audio
+/// callback. Lives entirely on the audio thread; game threads only ever
+/// reach it through the [`PlayCommand`] queue drained each callback, so
+/// the mixer's own state has no lock between the game's update loop and
+/// audio rendering.
+pub struct Mixer {
+    buses: HashMap<BusId, Bus>,
+    voices: Vec<Voice>,
+    assets: HashMap<AssetRef, Arc<AudioAsset>>,
+    commands: crossbeam_channel::Receiver<PlayCommand>,
+}
+
+impl Mixer {
+    pub fn new(commands: crossbeam_channel::Receiver<PlayCommand>) -> Self {
+        Self {
+            buses: HashMap::new(),
+            voices: Vec::new(),
+            assets: HashMap::new(),
+            commands,
+        }
+    }
+
+    pub fn set_bus_volume(&mut self, bus: BusId, volume: f32) {
+        self.buses.entry(bus).or_default().volume = volume;
+    }
+
+    /// Makes `asset` available for playback by [`AssetRef`]. Commands that
+    /// reference an unregistered asset are silently dropped — the mixer
+    /// has no way to report the error back across the audio-thread
+    /// boundary, so callers should register before sending the command.
+    pub fn register_asset(&mut self, asset_ref: AssetRef, asset: Arc<AudioAsset>) {
+        self.assets.insert(asset_ref, asset);
+    }
+
+    pub fn stop(&mut self, voice: VoiceId) {
+        if let Some(v) = self.voices.iter_mut().find(|v| v.id == voice) {
+            v.finished = true;
+        }
+    }
+
+    /// Drains queued [`PlayCommand`]s, advances every active voice by the
+    /// number of frames in `output`, and sums their contribution into it.
+    /// `output` is interleaved stereo; `listener` is only consulted for
+    /// voices with a `world_position`. `master_volume` scales the whole
+    /// mixed result, on top of every bus's own volume.
+    pub fn mix_into(&mut self, output: &mut [f32], listener: &Listener, master_volume: f32) {
+        for sample in output.iter_mut() {
+            *sample = 0.0;
+        }
+
+        while let Ok(command) = self.commands.try_recv() {
+            self.voices.push(Voice::from_command(command));
+        }
+
+        let frame_count = output.len() / 2;
+
+        for voice in &mut self.voices {
+            let Some(asset) = self.assets.get(&voice.asset) else {
+                voice.finished = true;
+                continue;
+            };
+
+            let bus_volume = self.buses.get(&voice.bus).copied().unwrap_or_default().volume;
+            let (pan, attenuation) = match voice.world_position {
+                Some(position) => listener.pan_and_attenuation(position),
+                None => (0.0, 1.0),
+            };
+            let left_gain = voice.volume * bus_volume * attenuation * (1.0 - pan.max(0.0));
+            let right_gain = voice.volume * bus_volume * attenuation * (1.0 + pan.min(0.0));
+
+            for frame in 0..frame_count {
+                let Some(sample) = sample_mono(asset, voice.cursor_frames) else {
+                    break;
+                };
+
+                output[frame * 2] += sample * left_gain;
+                output[frame * 2 + 1] += sample * right_gain;
+
+                voice.cursor_frames += 1;
+                if voice.cursor_frames >= frame_count_of(asset) {
+                    if voice.looping {
+                        voice.cursor_frames = 0;
+                    } else {
+                        voice.finished = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.voices.retain(|voice| !voice.finished);
+
+        for sample in output.iter_mut() {
+            *sample *= master_volume;
+        }
+    }
+}
+
+fn frame_count_of(asset: &AudioAsset) -> usize {
+    match asset.format {
+        AudioFormat::Pcm16 => asset.data.len() / (2 * asset.channels.max(1) as usize),
+        // This mixer has no Vorbis decoder, so the length is unknown
+        // here and treated as unbounded; such a voice never reaches the
+        // end-of-asset check.
+        AudioFormat::Vorbis => usize::MAX,
+    }
+}
+
+/// Reads one mono sample (channels averaged down) at `frame_index`,
+/// scaled to `-1.0..=1.0`. Only implemented for PCM16; returns `None` for
+/// Vorbis, which this crate does not decode, so a Vorbis voice mixes in
+/// as silence and stays active until stopped.
+fn sample_mono(asset: &AudioAsset, frame_index: usize) -> Option<f32> {
+    match asset.format {
+        AudioFormat::Pcm16 => {
+            let channels = asset.channels.max(1) as usize;
+            let frame_start = frame_index * channels * 2;
+            if frame_start + channels * 2 > asset.data.len() {
+                return None;
+            }
+
+            let mut sum = 0.0;
+            for channel in 0..channels {
+                let offset = frame_start + channel * 2;
+                let sample = i16::from_le_bytes([asset.data[offset], asset.data[offset + 1]]);
+                sum += sample as f32 / i16::MAX as f32;
+            }
+            Some(sum / channels as f32)
+        }
+        AudioFormat::Vorbis => None,
+    }
+}
@@ -0,0 +1,50 @@
+use kiri_assets::AssetRef;
+
+use crate::mixer::BusId;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VoiceId(pub u64);
+
+/// Sent from game threads to the mixer through a lock-free queue to start
+/// a new voice. Everything about how it plays (volume, looping, whether
+/// it's spatialized) is decided up front — voices have no other way to be
+/// reconfigured once started except [`crate::mixer::Mixer::stop`].
+#[derive(Clone, Debug)]
+pub struct PlayCommand {
+    pub voice: VoiceId,
+    pub asset: AssetRef,
+    pub bus: BusId,
+    pub volume: f32,
+    pub looping: bool,
+    /// `None` for 2D/UI sounds that skip spatialization entirely.
+    pub world_position: Option<[f32; 3]>,
+}
+
+/// One in-flight playback of an [`kiri_assets::AudioAsset`], tracked by
+/// the mixer between the command that started it and the frame it
+/// finishes (or is stopped).
+pub struct Voice {
+    pub id: VoiceId,
+    pub asset: AssetRef,
+    pub bus: BusId,
+    pub cursor_frames: usize,
+    pub volume: f32,
+    pub looping: bool,
+    pub world_position: Option<[f32; 3]>,
+    pub finished: bool,
+}
+
+impl Voice {
+    pub fn from_command(command: PlayCommand) -> Self {
+        Self {
+            id: command.voice,
+            asset: command.asset,
+            bus: command.bus,
+            cursor_frames: 0,
+            volume: command.volume,
+            looping: command.looping,
+            world_position: command.world_position,
+            finished: false,
+        }
+    }
+}
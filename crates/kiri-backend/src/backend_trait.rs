@@ -0,0 +1,56 @@
+use ash::vk;
+
+use crate::BackendResult;
+
+/// The surface area the renderer actually calls into, pulled out of the
+/// concrete `vulkan::Device` so a second backend (wgpu, to start) can be
+/// dropped in behind it.
+///
+/// This is deliberately small and Vulkan-flavored in its types for now —
+/// widening it to something backend-neutral (swapping `vk::Format` for a
+/// kiri-owned format enum, etc.) happens incrementally as a wgpu
+/// implementation actually needs it, rather than up front on a guess.
+/// `vulkan::Device` implements this directly; the only other
+/// implementation is the GPU-less [`crate::null_backend::NullBackend`].
+pub trait GraphicsBackend {
+    type Image;
+    type Buffer;
+
+    fn create_image(&self, desc: crate::vulkan::ImageDesc) -> BackendResult<Self::Image>;
+    fn create_buffer(&self, desc: crate::vulkan::BufferDesc) -> BackendResult<Self::Buffer>;
+
+    /// Name of the underlying graphics API, for logging and for feature
+    /// gates that only make sense on one backend (e.g. buffer device
+    /// address is Vulkan-only today).
+    fn backend_name(&self) -> &'static str;
+}
+
+impl GraphicsBackend for crate::vulkan::Device {
+    type Image = crate::vulkan::Image;
+    type Buffer = crate::vulkan::Buffer;
+
+    fn create_image(&self, desc: crate::vulkan::ImageDesc) -> BackendResult<Self::Image> {
+        crate::vulkan::Device::create_image(self, desc)
+    }
+
+    fn create_buffer(&self, desc: crate::vulkan::BufferDesc) -> BackendResult<Self::Buffer> {
+        crate::vulkan::Device::create_buffer(self, desc)
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "vulkan"
+    }
+}
+
+/// Placeholder for format conversion once a second backend exists; kept
+/// here rather than inline so the eventual wgpu backend has one obvious
+/// place to plug in `wgpu::TextureFormat` translation.
+pub fn vk_format_name(format: vk::Format) -> &'static str {
+    match format {
+        vk::Format::R8G8B8A8_UNORM => "R8G8B8A8_UNORM",
+        vk::Format::B8G8R8A8_UNORM => "B8G8R8A8_UNORM",
+        vk::Format::R8G8B8A8_SRGB => "R8G8B8A8_SRGB",
+        vk::Format::D32_SFLOAT => "D32_SFLOAT",
+        _ => "UNKNOWN",
+    }
+}
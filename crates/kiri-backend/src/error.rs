@@ -1,3 +1,43 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:c76b8656bb34e388ca5eded5e0a044afc2998652b71aaa0744410e0d43e11a5f
-size 3026
+use thiserror::Error;
+
+/// Errors surfaced by the Vulkan backend.
+///
+/// Most variants wrap the underlying `ash::vk::Result` so callers can still
+/// match on the raw Vulkan error code when they need to (e.g. to detect
+/// `ERROR_DEVICE_LOST` and trigger recovery), while still getting a message
+/// that makes sense without a debugger attached.
+#[derive(Debug, Error)]
+pub enum BackendError {
+    #[error("Vulkan error: {0:?}")]
+    Vulkan(#[from] ash::vk::Result),
+
+    #[error("No suitable physical device found")]
+    NoSuitablePhysicalDevice,
+
+    #[error("Required Vulkan extension not supported: {0}")]
+    ExtensionNotSupported(String),
+
+    #[error("Required Vulkan feature not supported: {0}")]
+    FeatureNotSupported(String),
+
+    #[error("{0}")]
+    Other(String),
+}
+
+impl BackendError {
+    /// True for the handful of Vulkan errors that mean "the device or its
+    /// surface is gone and everything GPU-resident needs to be rebuilt" —
+    /// a laptop switching GPUs, a driver reset, or the OS tearing down the
+    /// window's surface out from under us. Callers (`kiri-app`'s frame
+    /// loop, typically) use this to trigger device/swapchain recovery
+    /// instead of propagating the error and killing the process.
+    pub fn is_device_lost(&self) -> bool {
+        matches!(
+            self,
+            BackendError::Vulkan(ash::vk::Result::ERROR_DEVICE_LOST)
+                | BackendError::Vulkan(ash::vk::Result::ERROR_SURFACE_LOST_KHR)
+        )
+    }
+}
+
+pub type BackendResult<T> = Result<T, BackendError>;
@@ -1,3 +1,9 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:9b18bd7438d387187f08c7aa98195b1bae4aa87dae1d4860191a6724ba39b185
-size 780
+pub mod backend_trait;
+pub mod error;
+pub mod null_backend;
+pub mod vulkan;
+
+pub use backend_trait::GraphicsBackend;
+pub use error::{BackendError, BackendResult};
+pub use null_backend::{NullBackend, NullBuffer, NullCall, NullImage};
+pub use vulkan::*;
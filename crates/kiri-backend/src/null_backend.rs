@@ -0,0 +1,178 @@
+//! A [`GraphicsBackend`] implementation with no real GPU behind it, for
+//! unit-testing asset, render-graph, and culling logic in CI containers
+//! that have neither a GPU nor swiftshader available. Every
+//! `create_image`/`create_buffer` call is recorded (readable back via
+//! [`NullBackend::calls`]) and validated the way a real driver would
+//! reject obviously-wrong usage, so a test written against `NullBackend`
+//! catches the same "empty usage flags" or "zero-sized buffer" mistakes a
+//! real Vulkan validation layer would, without needing one running.
+//!
+//! Handles are tracked by id in `live_images`/`live_buffers` rather than
+//! actually freeing anything, so [`NullBackend::destroy_image`] /
+//! [`NullBackend::destroy_buffer`] can catch a double-destroy or a
+//! destroy of a handle that was never created — the "handle lifetime"
+//! half of what this module is for. `GraphicsBackend` itself has no
+//! destroy methods yet (nothing needs them today — see its doc comment),
+//! so these live as inherent methods on `NullBackend` until that trait
+//! grows one.
+
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::backend_trait::GraphicsBackend;
+use crate::vulkan::{BufferDesc, ImageDesc};
+use crate::{BackendError, BackendResult};
+
+/// Opaque handle standing in for a real `vulkan::Image` — carries no GPU
+/// resource, just the id `NullBackend` tracks it under and the desc it
+/// was created with, so a test can assert on what was requested.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NullImage {
+    pub id: u64,
+    pub extent: [u32; 3],
+    pub format: ash::vk::Format,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NullBuffer {
+    pub id: u64,
+    pub size: usize,
+}
+
+/// One call `NullBackend` recorded, in call order.
+#[derive(Clone, Debug)]
+pub enum NullCall {
+    CreateImage(ImageDesc),
+    CreateBuffer(BufferDesc),
+    DestroyImage(u64),
+    DestroyBuffer(u64),
+}
+
+#[derive(Default)]
+struct NullBackendState {
+    calls: Vec<NullCall>,
+    live_images: BTreeSet<u64>,
+    live_buffers: BTreeSet<u64>,
+}
+
+/// GPU-less [`GraphicsBackend`]. Cheap to construct (`NullBackend::new`),
+/// `Send + Sync` so it can be shared across test threads the way a real
+/// `Device` is.
+pub struct NullBackend {
+    next_id: AtomicU64,
+    state: Mutex<NullBackendState>,
+}
+
+impl Default for NullBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NullBackend {
+    pub fn new() -> Self {
+        Self {
+            next_id: AtomicU64::new(1),
+            state: Mutex::new(NullBackendState::default()),
+        }
+    }
+
+    fn alloc_id(&self) -> u64 {
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Calls recorded so far, in order.
+    pub fn calls(&self) -> Vec<NullCall> {
+        self.state.lock().unwrap().calls.clone()
+    }
+
+    pub fn live_image_count(&self) -> usize {
+        self.state.lock().unwrap().live_images.len()
+    }
+
+    pub fn live_buffer_count(&self) -> usize {
+        self.state.lock().unwrap().live_buffers.len()
+    }
+
+    /// Marks `image` as destroyed. Errors if it was never created by this
+    /// backend, or was already destroyed — the same "double free" /
+    /// "unknown handle" mistakes a real Vulkan validation layer would
+    /// flag.
+    pub fn destroy_image(&self, image: &NullImage) -> BackendResult<()> {
+        let mut state = self.state.lock().unwrap();
+        if !state.live_images.remove(&image.id) {
+            return Err(BackendError::Other(format!(
+                "NullBackend: destroy_image called on unknown or already-destroyed handle {}",
+                image.id
+            )));
+        }
+        state.calls.push(NullCall::DestroyImage(image.id));
+        Ok(())
+    }
+
+    pub fn destroy_buffer(&self, buffer: &NullBuffer) -> BackendResult<()> {
+        let mut state = self.state.lock().unwrap();
+        if !state.live_buffers.remove(&buffer.id) {
+            return Err(BackendError::Other(format!(
+                "NullBackend: destroy_buffer called on unknown or already-destroyed handle {}",
+                buffer.id
+            )));
+        }
+        state.calls.push(NullCall::DestroyBuffer(buffer.id));
+        Ok(())
+    }
+}
+
+impl GraphicsBackend for NullBackend {
+    type Image = NullImage;
+    type Buffer = NullBuffer;
+
+    fn create_image(&self, desc: ImageDesc) -> BackendResult<Self::Image> {
+        if desc.usage.is_empty() {
+            return Err(BackendError::Other(
+                "NullBackend: create_image with empty usage flags".to_string(),
+            ));
+        }
+        if desc.extent.iter().any(|&dim| dim == 0) {
+            return Err(BackendError::Other(
+                "NullBackend: create_image with a zero extent dimension".to_string(),
+            ));
+        }
+
+        let id = self.alloc_id();
+        let mut state = self.state.lock().unwrap();
+        state.live_images.insert(id);
+        state.calls.push(NullCall::CreateImage(desc.clone()));
+
+        Ok(NullImage {
+            id,
+            extent: desc.extent,
+            format: desc.format,
+        })
+    }
+
+    fn create_buffer(&self, desc: BufferDesc) -> BackendResult<Self::Buffer> {
+        if desc.usage.is_empty() {
+            return Err(BackendError::Other(
+                "NullBackend: create_buffer with empty usage flags".to_string(),
+            ));
+        }
+        if desc.size == 0 {
+            return Err(BackendError::Other(
+                "NullBackend: create_buffer with a zero size".to_string(),
+            ));
+        }
+
+        let id = self.alloc_id();
+        let mut state = self.state.lock().unwrap();
+        state.live_buffers.insert(id);
+        state.calls.push(NullCall::CreateBuffer(desc.clone()));
+
+        Ok(NullBuffer { id, size: desc.size })
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "null"
+    }
+}
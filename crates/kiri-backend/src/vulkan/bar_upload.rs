@@ -0,0 +1,84 @@
+use ash::vk;
+
+use super::device::Device;
+use super::physical_device::PhysicalDevice;
+
+/// Whether the device exposes a memory type that is both `DEVICE_LOCAL`
+/// and `HOST_VISIBLE` of a size worth using directly (resizable BAR, or a
+/// small BAR window on older GPUs).
+///
+/// When this is available, small/medium uploads (per-frame uniforms,
+/// streamed vertex data, ...) can write straight into device-local memory
+/// with a mapped pointer, skipping the usual host-visible staging buffer +
+/// `vkCmdCopyBuffer` round trip entirely. Large one-shot uploads (e.g. a
+/// multi-hundred-MB texture) still go through staging even with ReBAR,
+/// since PCIe writes into that window are uncached and slow in bulk.
+#[derive(Clone, Copy, Debug)]
+pub struct BarUploadHeap {
+    pub memory_type_index: u32,
+    pub heap_size: u64,
+}
+
+/// The size below which an upload is worth sending through the BAR path
+/// rather than staging, when available. Larger transfers are cheaper
+/// through a GPU-side copy even though that means an extra staging
+/// allocation and a queue submission. [`Device::try_map_bar_upload`]
+/// itself does not check it; it maps any size that fits the BAR heap.
+pub const BAR_UPLOAD_SIZE_THRESHOLD: u64 = 4 * 1024 * 1024;
+
+impl PhysicalDevice {
+    /// Looks for the largest `DEVICE_LOCAL | HOST_VISIBLE` heap and returns
+    /// a matching memory type to allocate from, if one exists.
+    pub fn bar_upload_heap(&self) -> Option<BarUploadHeap> {
+        let memory_properties = &self.memory_properties;
+        let required = vk::MemoryPropertyFlags::DEVICE_LOCAL | vk::MemoryPropertyFlags::HOST_VISIBLE;
+
+        (0..memory_properties.memory_type_count)
+            .filter(|&index| {
+                memory_properties.memory_types[index as usize]
+                    .property_flags
+                    .contains(required)
+            })
+            .map(|index| {
+                let heap_index = memory_properties.memory_types[index as usize].heap_index;
+                let heap_size = memory_properties.memory_heaps[heap_index as usize].size;
+                BarUploadHeap {
+                    memory_type_index: index,
+                    heap_size,
+                }
+            })
+            .max_by_key(|heap| heap.heap_size)
+    }
+}
+
+impl Device {
+    /// Allocates `size` bytes directly out of the BAR-visible device-local
+    /// heap and maps it for the caller, with no staging buffer involved.
+    /// Returns `None` if the device has no such heap (most GPUs without
+    /// resizable BAR enabled, or without a BAR window large enough).
+    pub fn try_map_bar_upload(&self, size: u64) -> Option<(vk::DeviceMemory, *mut u8)> {
+        let heap = self.physical_device.bar_upload_heap()?;
+        if size > heap.heap_size {
+            return None;
+        }
+
+        let memory = unsafe {
+            self.raw()
+                .allocate_memory(
+                    &vk::MemoryAllocateInfo::builder()
+                        .allocation_size(size)
+                        .memory_type_index(heap.memory_type_index),
+                    None,
+                )
+                .ok()?
+        };
+
+        let ptr = unsafe {
+            self.raw()
+                .map_memory(memory, 0, size, vk::MemoryMapFlags::empty())
+                .ok()?
+        };
+
+        Some((memory, ptr as *mut u8))
+    }
+}
@@ -1,3 +1,233 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:07eefe704dcbf42ecb20d2678dc8673dc9b3739b5e52e692536aa96f37424151
-size 5505
+use ash::vk;
+use ash::vk::Handle;
+
+use crate::BackendResult;
+
+use super::device::Device;
+use super::resource_registry::ResourceKind;
+
+#[derive(Clone, Copy, Debug)]
+pub struct BufferDesc {
+    pub size: usize,
+    pub usage: vk::BufferUsageFlags,
+    pub memory_location: MemoryLocation,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemoryLocation {
+    DeviceLocal,
+    HostVisible,
+}
+
+impl BufferDesc {
+    pub fn new_gpu_only(size: usize, usage: vk::BufferUsageFlags) -> Self {
+        Self {
+            size,
+            usage,
+            memory_location: MemoryLocation::DeviceLocal,
+        }
+    }
+
+    pub fn new_cpu_to_gpu(size: usize, usage: vk::BufferUsageFlags) -> Self {
+        Self {
+            size,
+            usage,
+            memory_location: MemoryLocation::HostVisible,
+        }
+    }
+
+    /// Opts this buffer into `VK_KHR_buffer_device_address`, so its GPU
+    /// address can be read back with [`Device::buffer_device_address`] and
+    /// passed to shaders as a raw pointer (e.g. in a bindless scene
+    /// buffer of mesh pointers). Requires the device to have been created
+    /// with the `bufferDeviceAddress` feature enabled.
+    pub fn with_device_address(mut self) -> Self {
+        self.usage |= vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS;
+        self
+    }
+}
+
+pub struct Buffer {
+    pub raw: vk::Buffer,
+    pub memory: vk::DeviceMemory,
+    pub desc: BufferDesc,
+}
+
+impl Device {
+    pub fn create_buffer(&self, desc: BufferDesc) -> BackendResult<Buffer> {
+        self.create_buffer_impl(desc, None)
+    }
+
+    /// Like [`Device::create_buffer`], but also names the buffer for
+    /// `VK_EXT_debug_utils` and records it in [`Device::dump_resources`]
+    /// under `name` — see [`Device::create_image_named`].
+    pub fn create_buffer_named(&self, desc: BufferDesc, name: &str) -> BackendResult<Buffer> {
+        self.create_buffer_impl(desc, Some(name))
+    }
+
+    fn create_buffer_impl(&self, desc: BufferDesc, name: Option<&str>) -> BackendResult<Buffer> {
+        let create_info = vk::BufferCreateInfo::builder()
+            .size(desc.size as u64)
+            .usage(desc.usage)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+
+        let raw = unsafe { self.raw().create_buffer(&create_info, None)? };
+        let requirements = unsafe { self.raw().get_buffer_memory_requirements(raw) };
+
+        let wants_device_address = desc.usage.contains(vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS);
+
+        let memory = if wants_device_address {
+            self.allocate_with_device_address(requirements, desc.memory_location)?
+        } else {
+            match desc.memory_location {
+                MemoryLocation::DeviceLocal => self.allocate_device_local(requirements)?,
+                MemoryLocation::HostVisible => self.allocate_host_visible(requirements)?,
+            }
+        };
+
+        unsafe {
+            self.raw().bind_buffer_memory(raw, memory, 0)?;
+        }
+
+        if let Some(name) = name {
+            self.set_debug_name(raw, name);
+            self.resource_registry
+                .register(raw.as_raw(), ResourceKind::Buffer, name.to_string(), desc.size as u64);
+        }
+
+        Ok(Buffer { raw, memory, desc })
+    }
+
+    /// Like [`Device::allocate_device_local`]/[`Device::allocate_host_visible`],
+    /// but chains a `VkMemoryAllocateFlagsInfo` requesting `deviceAddress`,
+    /// which the spec requires for memory backing a buffer created with
+    /// `SHADER_DEVICE_ADDRESS` usage.
+    fn allocate_with_device_address(
+        &self,
+        requirements: vk::MemoryRequirements,
+        memory_location: MemoryLocation,
+    ) -> BackendResult<vk::DeviceMemory> {
+        let memory_properties = &self.physical_device.memory_properties;
+        let required_flags = match memory_location {
+            MemoryLocation::DeviceLocal => vk::MemoryPropertyFlags::DEVICE_LOCAL,
+            MemoryLocation::HostVisible => {
+                vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT
+            }
+        };
+
+        let memory_type_index = (0..memory_properties.memory_type_count)
+            .find(|&index| {
+                let suitable = (requirements.memory_type_bits & (1 << index)) != 0;
+                let matches = memory_properties.memory_types[index as usize]
+                    .property_flags
+                    .contains(required_flags);
+                suitable && matches
+            })
+            .unwrap_or(0);
+
+        let mut allocate_flags_info = vk::MemoryAllocateFlagsInfo::builder()
+            .flags(vk::MemoryAllocateFlags::DEVICE_ADDRESS)
+            .build();
+
+        let allocate_info = vk::MemoryAllocateInfo::builder()
+            .allocation_size(requirements.size)
+            .memory_type_index(memory_type_index)
+            .push_next(&mut allocate_flags_info);
+
+        Ok(unsafe { self.raw().allocate_memory(&allocate_info, None)? })
+    }
+
+    /// Reads back the GPU-visible address of a buffer created with
+    /// [`BufferDesc::with_device_address`]. The resulting `u64` is only
+    /// meaningful on this device and only while `buffer` is alive.
+    pub fn buffer_device_address(&self, buffer: &Buffer) -> u64 {
+        debug_assert!(
+            buffer
+                .desc
+                .usage
+                .contains(vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS),
+            "buffer was not created with BufferDesc::with_device_address"
+        );
+
+        unsafe {
+            self.raw().get_buffer_device_address(
+                &vk::BufferDeviceAddressInfo::builder().buffer(buffer.raw),
+            )
+        }
+    }
+
+    pub(crate) fn allocate_host_visible(
+        &self,
+        requirements: vk::MemoryRequirements,
+    ) -> BackendResult<vk::DeviceMemory> {
+        let memory_properties = &self.physical_device.memory_properties;
+        let required_flags = vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT;
+
+        let memory_type_index = (0..memory_properties.memory_type_count)
+            .find(|&index| {
+                let suitable = (requirements.memory_type_bits & (1 << index)) != 0;
+                let matches = memory_properties.memory_types[index as usize]
+                    .property_flags
+                    .contains(required_flags);
+                suitable && matches
+            })
+            .unwrap_or(0);
+
+        let allocate_info = vk::MemoryAllocateInfo::builder()
+            .allocation_size(requirements.size)
+            .memory_type_index(memory_type_index);
+
+        Ok(unsafe { self.raw().allocate_memory(&allocate_info, None)? })
+    }
+}
+
+impl Buffer {
+    /// Maps the whole buffer and copies `data` into it. Only valid for
+    /// buffers created with [`MemoryLocation::HostVisible`].
+    pub fn write(&self, device: &Device, data: &[u8]) -> BackendResult<()> {
+        self.write_at(device, 0, data)
+    }
+
+    /// Like [`Buffer::write`], but at `offset` bytes into the buffer
+    /// instead of the start — used by [`super::geometry_arena::GeometryArena`]
+    /// to bump-allocate several suballocations into one buffer.
+    pub fn write_at(&self, device: &Device, offset: u64, data: &[u8]) -> BackendResult<()> {
+        debug_assert!(
+            offset + data.len() as u64 <= self.desc.size as u64,
+            "write_at out of bounds"
+        );
+        unsafe {
+            let ptr = device.raw().map_memory(
+                self.memory,
+                offset,
+                data.len() as u64,
+                vk::MemoryMapFlags::empty(),
+            )?;
+            std::ptr::copy_nonoverlapping(data.as_ptr(), ptr as *mut u8, data.len());
+            device.raw().unmap_memory(self.memory);
+        }
+        Ok(())
+    }
+
+    /// Maps `byte_len` bytes starting at `offset` and copies them out —
+    /// the read-back counterpart to [`Buffer::write_at`], used by GPU
+    /// readback paths like [`super::pick_buffer::Device::read_pick_id`]
+    /// once a staging copy has already landed in this buffer. Only valid
+    /// for buffers created with [`MemoryLocation::HostVisible`].
+    pub fn read_at(&self, device: &Device, offset: u64, byte_len: u64) -> BackendResult<Vec<u8>> {
+        debug_assert!(
+            offset + byte_len <= self.desc.size as u64,
+            "read_at out of bounds"
+        );
+        let mut out = vec![0u8; byte_len as usize];
+        unsafe {
+            let ptr =
+                device
+                    .raw()
+                    .map_memory(self.memory, offset, byte_len, vk::MemoryMapFlags::empty())?;
+            std::ptr::copy_nonoverlapping(ptr as *const u8, out.as_mut_ptr(), byte_len as usize);
+            device.raw().unmap_memory(self.memory);
+        }
+        Ok(out)
+    }
+}
@@ -0,0 +1,202 @@
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::buffer::{Buffer, BufferDesc};
+use super::device::Device;
+
+/// `vkCmdUpdateBuffer`'s hard limit — the spec requires `dataSize <= 65536`
+/// and a multiple of 4, since the data is embedded directly in the command
+/// buffer rather than read from a separate resource.
+const UPDATE_BUFFER_MAX_SIZE: u64 = 65536;
+
+impl Device {
+    /// Records `vkCmdUpdateBuffer` — the cheap path for small (`<= 64 KiB`),
+    /// infrequent writes that don't justify a staging buffer, e.g. patching
+    /// a handful of bytes of a uniform buffer already resident on the
+    /// device. For anything larger, or anything written every frame, use
+    /// [`Device::upload_to_buffer`] instead.
+    ///
+    /// Ends with a barrier making the write visible to `dst_stage`/
+    /// `dst_access`, so the caller doesn't have to hand-roll it — pass
+    /// whatever stage/access the next command reading `buffer` needs.
+    pub fn record_update_buffer(
+        &self,
+        command_buffer: vk::CommandBuffer,
+        buffer: &Buffer,
+        offset: u64,
+        data: &[u8],
+        dst_stage: vk::PipelineStageFlags,
+        dst_access: vk::AccessFlags,
+    ) {
+        debug_assert!(
+            buffer.desc.usage.contains(vk::BufferUsageFlags::TRANSFER_DST),
+            "buffer was not created with TRANSFER_DST usage"
+        );
+        debug_assert!(offset % 4 == 0, "vkCmdUpdateBuffer requires a 4-byte-aligned offset");
+        debug_assert!(
+            data.len() as u64 <= UPDATE_BUFFER_MAX_SIZE,
+            "vkCmdUpdateBuffer only supports up to {UPDATE_BUFFER_MAX_SIZE} bytes at a time — use Device::upload_to_buffer"
+        );
+        debug_assert!(
+            data.len() % 4 == 0,
+            "vkCmdUpdateBuffer requires a size that's a multiple of 4"
+        );
+        debug_assert!(
+            offset + data.len() as u64 <= buffer.desc.size as u64,
+            "record_update_buffer out of bounds"
+        );
+
+        unsafe {
+            self.raw().cmd_update_buffer(command_buffer, buffer.raw, offset, data);
+        }
+
+        self.record_transfer_barrier(command_buffer, buffer.raw, dst_stage, dst_access);
+    }
+
+    /// Records `vkCmdFillBuffer`, filling `[offset, offset + size)` with
+    /// repetitions of the 4-byte word `data` — the usual case being
+    /// zeroing a buffer (e.g. resetting an indirect-draw count before the
+    /// next frame's culling pass writes it) without a staging upload.
+    ///
+    /// Ends with a barrier the same way as [`Device::record_update_buffer`].
+    pub fn record_fill_buffer(
+        &self,
+        command_buffer: vk::CommandBuffer,
+        buffer: &Buffer,
+        offset: u64,
+        size: u64,
+        data: u32,
+        dst_stage: vk::PipelineStageFlags,
+        dst_access: vk::AccessFlags,
+    ) {
+        debug_assert!(
+            buffer.desc.usage.contains(vk::BufferUsageFlags::TRANSFER_DST),
+            "buffer was not created with TRANSFER_DST usage"
+        );
+        debug_assert!(offset % 4 == 0, "vkCmdFillBuffer requires a 4-byte-aligned offset");
+        debug_assert!(
+            size == vk::WHOLE_SIZE || size % 4 == 0,
+            "vkCmdFillBuffer requires a size that's a multiple of 4"
+        );
+        debug_assert!(
+            size == vk::WHOLE_SIZE || offset + size <= buffer.desc.size as u64,
+            "record_fill_buffer out of bounds"
+        );
+
+        unsafe {
+            self.raw().cmd_fill_buffer(command_buffer, buffer.raw, offset, size, data);
+        }
+
+        self.record_transfer_barrier(command_buffer, buffer.raw, dst_stage, dst_access);
+    }
+
+    /// Records `vkCmdCopyBuffer` from `src` to `dst` over `regions`.
+    ///
+    /// Ends with a barrier the same way as [`Device::record_update_buffer`].
+    pub fn record_copy_buffer(
+        &self,
+        command_buffer: vk::CommandBuffer,
+        src: &Buffer,
+        dst: &Buffer,
+        regions: &[vk::BufferCopy],
+        dst_stage: vk::PipelineStageFlags,
+        dst_access: vk::AccessFlags,
+    ) {
+        debug_assert!(
+            src.desc.usage.contains(vk::BufferUsageFlags::TRANSFER_SRC),
+            "src buffer was not created with TRANSFER_SRC usage"
+        );
+        debug_assert!(
+            dst.desc.usage.contains(vk::BufferUsageFlags::TRANSFER_DST),
+            "dst buffer was not created with TRANSFER_DST usage"
+        );
+        debug_assert!(
+            regions.iter().all(|region| {
+                region.src_offset + region.size <= src.desc.size as u64
+                    && region.dst_offset + region.size <= dst.desc.size as u64
+            }),
+            "record_copy_buffer region out of bounds"
+        );
+
+        unsafe {
+            self.raw().cmd_copy_buffer(command_buffer, src.raw, dst.raw, regions);
+        }
+
+        self.record_transfer_barrier(command_buffer, dst.raw, dst_stage, dst_access);
+    }
+
+    /// Stages `data` through a throwaway host-visible buffer and copies it
+    /// into `buffer` at `offset`, blocking until the copy completes — the
+    /// large-write counterpart to [`Device::record_update_buffer`], for
+    /// data too big to embed in the command stream (mesh/texture data,
+    /// bulk scene-buffer initialization).
+    ///
+    /// Uses [`Device::immediate_submit`], so unlike the `record_*` helpers
+    /// above it needs no caller-supplied destination barrier: by the time
+    /// this returns, the write has already completed and is visible to
+    /// whatever's submitted next.
+    pub fn upload_to_buffer(&self, buffer: &Buffer, offset: u64, data: &[u8]) -> BackendResult<()> {
+        debug_assert!(
+            buffer.desc.usage.contains(vk::BufferUsageFlags::TRANSFER_DST),
+            "buffer was not created with TRANSFER_DST usage"
+        );
+        debug_assert!(
+            offset + data.len() as u64 <= buffer.desc.size as u64,
+            "upload_to_buffer out of bounds"
+        );
+
+        let staging = self.create_buffer(BufferDesc::new_cpu_to_gpu(
+            data.len(),
+            vk::BufferUsageFlags::TRANSFER_SRC,
+        ))?;
+        staging.write(self, data)?;
+
+        let region = vk::BufferCopy {
+            src_offset: 0,
+            dst_offset: offset,
+            size: data.len() as u64,
+        };
+
+        self.immediate_submit(|device, command_buffer| unsafe {
+            device
+                .raw()
+                .cmd_copy_buffer(command_buffer, staging.raw, buffer.raw, &[region]);
+        })?;
+
+        self.queue_drop(staging.raw);
+        self.queue_drop(staging.memory);
+
+        Ok(())
+    }
+
+    /// Shared tail of every `record_*` helper above: a `BUFFER_MEMORY_BARRIER`
+    /// from `TRANSFER_WRITE` at `TRANSFER` stage to whatever stage/access
+    /// the caller says will read `buffer` next.
+    fn record_transfer_barrier(
+        &self,
+        command_buffer: vk::CommandBuffer,
+        buffer: vk::Buffer,
+        dst_stage: vk::PipelineStageFlags,
+        dst_access: vk::AccessFlags,
+    ) {
+        let barrier = vk::BufferMemoryBarrier::builder()
+            .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+            .dst_access_mask(dst_access)
+            .buffer(buffer)
+            .offset(0)
+            .size(vk::WHOLE_SIZE);
+
+        unsafe {
+            self.raw().cmd_pipeline_barrier(
+                command_buffer,
+                vk::PipelineStageFlags::TRANSFER,
+                dst_stage,
+                vk::DependencyFlags::empty(),
+                &[],
+                std::slice::from_ref(&barrier),
+                &[],
+            );
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use ash::vk;
+
+use kiri_assets::image::VolumeAsset;
+
+use crate::BackendResult;
+
+use super::device::Device;
+use super::frame_graph::{FrameGraph, PassHandle, ResourceHandle};
+use super::image::{Image, ImageDesc};
+use super::texture_format::vk_format_for_asset;
+
+/// A baked 3D LUT uploaded as a `TYPE_3D` image, plus the sampler grading
+/// wants to read it through — clamped (a LUT has no meaningful content
+/// past its edges) and linearly filtered (grading blends smoothly between
+/// adjacent LUT cells, unlike a data texture that wants nearest).
+pub struct ColorGradingLut {
+    pub image: Image,
+    pub sampler: vk::Sampler,
+}
+
+impl Device {
+    /// Creates the `TYPE_3D` image for `asset` and a clamped trilinear
+    /// sampler to read it through. Doesn't upload `asset.mips[0]` itself —
+    /// kiri has no upload-queue abstraction yet (see
+    /// [`super::image::Image::record_copy_from_buffer`]); the caller stages
+    /// and records the copy the same way any other baked texture is
+    /// uploaded.
+    pub fn create_color_grading_lut(&self, asset: &VolumeAsset) -> BackendResult<ColorGradingLut> {
+        let image = self.create_image(
+            ImageDesc::new_3d(vk_format_for_asset(asset.format), asset.extent)
+                .usage(vk::ImageUsageFlags::SAMPLED | vk::ImageUsageFlags::TRANSFER_DST),
+        )?;
+
+        let sampler = unsafe {
+            self.raw().create_sampler(
+                &vk::SamplerCreateInfo::builder()
+                    .mag_filter(vk::Filter::LINEAR)
+                    .min_filter(vk::Filter::LINEAR)
+                    .mipmap_mode(vk::SamplerMipmapMode::LINEAR)
+                    .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+                    .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+                    .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+                    .unnormalized_coordinates(false),
+                None,
+            )?
+        };
+
+        Ok(ColorGradingLut { image, sampler })
+    }
+}
+
+impl ColorGradingLut {
+    /// Queues the LUT's image, its default view, and its sampler for
+    /// deferred destruction. Any views from [`Image::view_for`] must be
+    /// queued separately via [`Image::queue_drop_views`] first.
+    pub fn queue_drop(&self, device: &Device) {
+        device.queue_drop(self.image.raw);
+        device.queue_drop(self.image.memory);
+        device.queue_drop(self.image.view);
+        device.queue_drop(self.sampler);
+    }
+}
+
+/// Push-constant layout the color grading shader blends between `lut_a`
+/// and `lut_b` with — `blend == 0.0` is pure `lut_a`, `1.0` is pure
+/// `lut_b`, matching a time-of-day or zone-transition crossfade driven by
+/// game code.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ColorGradingParams {
+    pub blend: f32,
+}
+
+/// A post-process node blending between two 3D LUTs and applying the
+/// result to the scene's color buffer. Owns the two LUTs it was built
+/// with; swap [`ColorGradingPass::lut_b`] (and reset `blend` to 0) at the
+/// start of a new transition rather than rebuilding the pass.
+pub struct ColorGradingPass {
+    pub lut_a: ColorGradingLut,
+    pub lut_b: ColorGradingLut,
+    pub blend: f32,
+}
+
+impl ColorGradingPass {
+    pub fn new(lut_a: ColorGradingLut, lut_b: ColorGradingLut) -> Self {
+        Self {
+            lut_a,
+            lut_b,
+            blend: 0.0,
+        }
+    }
+
+    pub fn params(&self) -> ColorGradingParams {
+        ColorGradingParams { blend: self.blend }
+    }
+
+    /// Registers this pass in `graph`: reads the scene color target plus
+    /// both LUTs, writes the graded result back to `scene_color`.
+    pub fn register(&self, graph: &mut FrameGraph, scene_color: ResourceHandle) -> PassHandle {
+        let lut_a = graph.resource("color_grading_lut_a");
+        let lut_b = graph.resource("color_grading_lut_b");
+        graph.pass("color_grading", &[scene_color, lut_a, lut_b], &[scene_color])
+    }
+
+    pub fn queue_drop(&self, device: &Device) {
+        self.lut_a.queue_drop(device);
+        self.lut_b.queue_drop(device);
+    }
+}
@@ -0,0 +1,158 @@
+//! Built-in GPU utility kernels — prefix sum, reduce, and one radix-sort
+//! digit pass — that particles, order-independent transparency, and GPU
+//! culling all need and would otherwise each reimplement slightly
+//! differently, with slightly different bugs. Mirrors
+//! [`super::skinning::SkinningPass`]'s shape: one compute pipeline built
+//! from already-baked SPIR-V, with a `cmd_dispatch_kernel` that hides the
+//! workgroup-count arithmetic behind a stable, element-count-based API
+//! instead of every caller computing it (and getting the rounding wrong)
+//! themselves.
+//!
+//! This crate doesn't ship the kernels' shader source — see `data/shaders/`
+//! and `kiri-asset-pipe::import_effect` for how a shader gets from HLSL to
+//! the SPIR-V [`Device::create_compute_kernel`] takes. [`KernelKind`] just
+//! pins down the binding layout and workgroup size each kernel's shader is
+//! assumed to use, so that assumption lives in one place instead of being
+//! duplicated at every call site.
+
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::device::Device;
+
+/// A radix-sort pass is 8 bits (256 buckets) per digit; sorting a full
+/// 32-bit key takes this many passes, each reading the previous pass's
+/// output — callers ping-pong a pair of key/value buffers across them the
+/// same way [`super::skinning::SkinnedMeshBuffers`] ping-pongs skinning
+/// output.
+pub const RADIX_SORT_PASS_COUNT: u32 = 4;
+
+/// Which built-in kernel a [`ComputeKernel`] was built for — pins the
+/// descriptor layout (`binding_count` flat storage buffers, all compute
+/// stage, no samplers or uniform buffers) and workgroup size its shader is
+/// assumed to declare via `local_size_x`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KernelKind {
+    /// Inclusive prefix sum (scan) over a `u32` buffer: `in`, `out`.
+    PrefixSum,
+    /// Single-pass sum reduction over a `u32` buffer, one partial sum per
+    /// workgroup: `in`, `out`.
+    Reduce,
+    /// One 8-bit digit counting+scatter pass of a radix sort over `u32`
+    /// key/value pairs: `keys_in`, `values_in`, `keys_out`, `values_out`.
+    /// Run [`RADIX_SORT_PASS_COUNT`] times, ping-ponging the buffer pairs,
+    /// to fully sort 32-bit keys.
+    RadixSortPass,
+}
+
+impl KernelKind {
+    fn workgroup_size(self) -> u32 {
+        match self {
+            KernelKind::PrefixSum => 256,
+            KernelKind::Reduce => 256,
+            KernelKind::RadixSortPass => 256,
+        }
+    }
+
+    fn binding_count(self) -> u32 {
+        match self {
+            KernelKind::PrefixSum => 2,
+            KernelKind::Reduce => 2,
+            KernelKind::RadixSortPass => 4,
+        }
+    }
+}
+
+/// A built-in compute kernel, ready to dispatch. See [`KernelKind`] for
+/// what each one expects to be bound to it.
+pub struct ComputeKernel {
+    pub kind: KernelKind,
+    pub descriptor_set_layout: vk::DescriptorSetLayout,
+    pub pipeline_layout: vk::PipelineLayout,
+    pub pipeline: vk::Pipeline,
+}
+
+impl Device {
+    /// Builds `kind`'s compute pipeline from already-compiled SPIR-V, with
+    /// a flat `kind.binding_count()`-wide storage-buffer descriptor set
+    /// layout — see [`Device::create_skinning_pass`] for the
+    /// pipeline-creation steps this mirrors.
+    pub fn create_compute_kernel(&self, kind: KernelKind, spirv: &[u8]) -> BackendResult<ComputeKernel> {
+        let shader_module = self.create_shader_module(spirv)?;
+
+        let bindings: Vec<_> = (0..kind.binding_count())
+            .map(storage_buffer_binding)
+            .collect();
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(&bindings);
+        let descriptor_set_layout =
+            unsafe { self.raw().create_descriptor_set_layout(&layout_info, None)? };
+
+        let set_layouts = [descriptor_set_layout];
+        let pipeline_layout_info = vk::PipelineLayoutCreateInfo::builder().set_layouts(&set_layouts);
+        let pipeline_layout =
+            unsafe { self.raw().create_pipeline_layout(&pipeline_layout_info, None)? };
+
+        let entry_point = std::ffi::CString::new("main").unwrap();
+        let stage = vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::COMPUTE)
+            .module(shader_module)
+            .name(&entry_point);
+
+        let create_info = vk::ComputePipelineCreateInfo::builder()
+            .stage(*stage)
+            .layout(pipeline_layout);
+
+        let pipeline = unsafe {
+            self.raw()
+                .create_compute_pipelines(vk::PipelineCache::null(), &[*create_info], None)
+                .map_err(|(_, result)| result)?[0]
+        };
+
+        unsafe {
+            self.raw().destroy_shader_module(shader_module, None);
+        }
+
+        Ok(ComputeKernel {
+            kind,
+            descriptor_set_layout,
+            pipeline_layout,
+            pipeline,
+        })
+    }
+
+    /// Dispatches `kernel` over `element_count` elements, rounding up to
+    /// `kernel.kind`'s workgroup size so callers never have to. The
+    /// caller binds the descriptor set pointing at `kernel.kind`'s buffers
+    /// before calling this.
+    pub fn cmd_dispatch_kernel(
+        &self,
+        command_buffer: vk::CommandBuffer,
+        kernel: &ComputeKernel,
+        element_count: u32,
+    ) {
+        let workgroup_count = element_count.div_ceil(kernel.kind.workgroup_size());
+        unsafe {
+            self.raw()
+                .cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::COMPUTE, kernel.pipeline);
+            self.raw().cmd_dispatch(command_buffer, workgroup_count, 1, 1);
+        }
+    }
+}
+
+impl ComputeKernel {
+    pub fn queue_drop(&self, device: &Device) {
+        device.queue_drop(self.pipeline);
+        device.queue_drop(self.pipeline_layout);
+        device.queue_drop(self.descriptor_set_layout);
+    }
+}
+
+fn storage_buffer_binding(binding: u32) -> vk::DescriptorSetLayoutBinding {
+    vk::DescriptorSetLayoutBinding::builder()
+        .binding(binding)
+        .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+        .descriptor_count(1)
+        .stage_flags(vk::ShaderStageFlags::COMPUTE)
+        .build()
+}
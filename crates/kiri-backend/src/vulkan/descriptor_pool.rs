@@ -0,0 +1,136 @@
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::device::Device;
+
+/// A `vk::DescriptorPool` that tracks how close it is to exhaustion and
+/// grows itself by allocating a fresh, larger pool once it gets there,
+/// rather than failing an allocation mid-frame.
+///
+/// Old pools are kept in `retired` instead of destroyed immediately, since
+/// descriptor sets allocated from them may still be in flight; they get
+/// reclaimed once the device is idle (today) or through the drop list
+/// (once deferred destroy covers descriptor pools).
+pub struct GrowableDescriptorPool {
+    current: vk::DescriptorPool,
+    retired: Vec<vk::DescriptorPool>,
+    pool_sizes: Vec<vk::DescriptorPoolSize>,
+    max_sets: u32,
+    allocated_sets: u32,
+    growth_factor: u32,
+}
+
+/// A snapshot of how full the pool is, for logging/HUD display.
+#[derive(Clone, Copy, Debug)]
+pub struct DescriptorPoolPressure {
+    pub allocated_sets: u32,
+    pub max_sets: u32,
+    pub retired_pool_count: usize,
+}
+
+impl DescriptorPoolPressure {
+    pub fn utilization(&self) -> f32 {
+        self.allocated_sets as f32 / self.max_sets.max(1) as f32
+    }
+}
+
+impl GrowableDescriptorPool {
+    pub fn new(
+        device: &Device,
+        pool_sizes: Vec<vk::DescriptorPoolSize>,
+        max_sets: u32,
+    ) -> BackendResult<Self> {
+        let current = Self::create_pool(device, &pool_sizes, max_sets)?;
+
+        Ok(Self {
+            current,
+            retired: Vec::new(),
+            pool_sizes,
+            max_sets,
+            allocated_sets: 0,
+            growth_factor: 2,
+        })
+    }
+
+    fn create_pool(
+        device: &Device,
+        pool_sizes: &[vk::DescriptorPoolSize],
+        max_sets: u32,
+    ) -> BackendResult<vk::DescriptorPool> {
+        let create_info = vk::DescriptorPoolCreateInfo::builder()
+            .pool_sizes(pool_sizes)
+            .max_sets(max_sets);
+
+        Ok(unsafe { device.raw().create_descriptor_pool(&create_info, None)? })
+    }
+
+    /// Grows the pool ahead of an allocation if `layouts.len()` more sets
+    /// would take the current one past its `max_sets` budget, then
+    /// allocates `layouts.len()` sets
+    /// from whichever pool is current.
+    pub fn allocate(
+        &mut self,
+        device: &Device,
+        layouts: &[vk::DescriptorSetLayout],
+    ) -> BackendResult<Vec<vk::DescriptorSet>> {
+        if self.allocated_sets + layouts.len() as u32 > self.max_sets {
+            self.grow(device)?;
+        }
+
+        let sets = unsafe {
+            device.raw().allocate_descriptor_sets(
+                &vk::DescriptorSetAllocateInfo::builder()
+                    .descriptor_pool(self.current)
+                    .set_layouts(layouts),
+            )?
+        };
+
+        self.allocated_sets += layouts.len() as u32;
+        Ok(sets)
+    }
+
+    fn grow(&mut self, device: &Device) -> BackendResult<()> {
+        self.retired.push(self.current);
+
+        self.max_sets *= self.growth_factor;
+        let scaled_sizes: Vec<_> = self
+            .pool_sizes
+            .iter()
+            .map(|size| vk::DescriptorPoolSize {
+                ty: size.ty,
+                descriptor_count: size.descriptor_count * self.growth_factor,
+            })
+            .collect();
+
+        self.current = Self::create_pool(device, &scaled_sizes, self.max_sets)?;
+        self.pool_sizes = scaled_sizes;
+        self.allocated_sets = 0;
+
+        log::info!(
+            "Descriptor pool exhausted; grew to max_sets={}",
+            self.max_sets
+        );
+
+        Ok(())
+    }
+
+    pub fn pressure(&self) -> DescriptorPoolPressure {
+        DescriptorPoolPressure {
+            allocated_sets: self.allocated_sets,
+            max_sets: self.max_sets,
+            retired_pool_count: self.retired.len(),
+        }
+    }
+
+    /// Destroys every retired pool. Only safe to call once the device is
+    /// idle, since sets allocated from those pools may still be in use by
+    /// in-flight command buffers.
+    pub fn reclaim_retired(&mut self, device: &Device) {
+        for pool in self.retired.drain(..) {
+            unsafe {
+                device.raw().destroy_descriptor_pool(pool, None);
+            }
+        }
+    }
+}
@@ -1,3 +1,483 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:ffcb328ba7a0731b9efbeb599488330c21670ca6a23b58a32ab66ebfd36fc79f
-size 11516
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+
+use ash::vk;
+
+use crate::{BackendError, BackendResult};
+
+use super::drop_list::{DropList, ToDrop};
+use super::format_policy::FormatPolicy;
+use super::frame_hooks::FrameHooks;
+use super::history_image::HistoryImagePool;
+use super::physical_device::PhysicalDevice;
+use super::resource_destroyer::ResourceDestroyer;
+use super::resource_registry::ResourceRegistry;
+
+/// A logical Vulkan device and the single graphics/compute queue kiri
+/// drives it through.
+///
+/// Every resource pool a `Device` owns (the deferred destroy list, the
+/// resource registry, the history-image and sync-object pools, ...) lives
+/// on this struct, so
+/// two `Device`s created from the same `Instance` against different
+/// `PhysicalDevice`s never share state: destroying one has no effect on
+/// the other.
+pub struct Device {
+    pub physical_device: PhysicalDevice,
+    pub raw: ash::Device,
+    pub queue: vk::Queue,
+    pub queue_family_index: u32,
+    pub compute_queue: Option<vk::Queue>,
+    pub compute_queue_family_index: Option<u32>,
+    pub transfer_queue: Option<vk::Queue>,
+    pub transfer_queue_family_index: Option<u32>,
+    pub queue_report: QueueSetupReport,
+    pub features: DeviceFeaturesDesc,
+    /// This device's resolved format choices — see [`FormatPolicy`].
+    /// Queried by the render graph instead of hardcoding a depth/HDR/
+    /// compressed-texture format in user code.
+    pub format_policy: FormatPolicy,
+    pub(crate) drop_list: Mutex<DropList>,
+    pub(crate) resource_registry: ResourceRegistry,
+    destroy_sender: mpsc::Sender<ToDrop>,
+    destroy_receiver: Mutex<mpsc::Receiver<ToDrop>>,
+    pub(crate) frame_hooks: Mutex<FrameHooks>,
+    pub(crate) history_images: Mutex<HistoryImagePool>,
+    pub(crate) sync_pool: Mutex<SyncPool>,
+}
+
+/// Per-role queue priority and family-selection overrides for
+/// [`Device::create_with_queue_config`]. Leaving `compute`/`transfer` at
+/// `None` (the default) creates a single graphics/compute/transfer-capable
+/// queue, matching [`Device::create`]'s behavior.
+#[derive(Clone, Debug)]
+pub struct QueueConfig {
+    /// Priority of the graphics queue, in `[0.0, 1.0]`.
+    pub graphics_priority: f32,
+    /// Forces the graphics queue onto a specific family instead of letting
+    /// [`PhysicalDevice::graphics_queue_family_index`] pick one. Must
+    /// support `GRAPHICS | COMPUTE`, or device creation fails.
+    pub graphics_family_override: Option<u32>,
+    /// Requests a compute queue. When `family_override` is unset, prefers
+    /// [`PhysicalDevice::dedicated_compute_queue_family_index`], falling
+    /// back to sharing the graphics family if the device has no dedicated
+    /// compute family.
+    pub compute: Option<QueueRoleConfig>,
+    /// Requests a transfer queue. Resolved the same way as `compute`, via
+    /// [`PhysicalDevice::dedicated_transfer_queue_family_index`].
+    pub transfer: Option<QueueRoleConfig>,
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            graphics_priority: 1.0,
+            graphics_family_override: None,
+            compute: None,
+            transfer: None,
+        }
+    }
+}
+
+/// Priority and optional family override for one non-graphics queue role
+/// in a [`QueueConfig`].
+#[derive(Clone, Copy, Debug)]
+pub struct QueueRoleConfig {
+    /// Priority of this queue, in `[0.0, 1.0]`.
+    pub priority: f32,
+    /// Forces this role onto a specific queue family instead of letting
+    /// `Device::create_with_queue_config` pick one.
+    pub family_override: Option<u32>,
+}
+
+impl Default for QueueRoleConfig {
+    fn default() -> Self {
+        Self {
+            priority: 1.0,
+            family_override: None,
+        }
+    }
+}
+
+/// Records what [`Device::create_with_queue_config`] actually did, since a
+/// requested compute/transfer queue may end up sharing the graphics family
+/// on hardware with no dedicated queue family for it.
+#[derive(Clone, Copy, Debug)]
+pub struct QueueSetupReport {
+    pub graphics_family: u32,
+    pub compute_family: Option<u32>,
+    /// `true` if the compute queue landed on its own family rather than
+    /// sharing `graphics_family`.
+    pub compute_is_dedicated: bool,
+    pub transfer_family: Option<u32>,
+    /// `true` if the transfer queue landed on its own family rather than
+    /// sharing `graphics_family`.
+    pub transfer_is_dedicated: bool,
+}
+
+/// Which optional robustness features to enable on a `Device`. All of
+/// these trade a small but measurable amount of performance for catching
+/// out-of-bounds buffer/descriptor access instead of hitting undefined
+/// behavior on the GPU, so shipping builds generally want them off and
+/// development/debug builds generally want them on.
+#[derive(Clone, Copy, Debug)]
+pub struct DeviceFeaturesDesc {
+    /// `robustBufferAccess`: out-of-bounds buffer reads/writes in shaders
+    /// return zero / are discarded instead of corrupting memory.
+    pub robust_buffer_access: bool,
+    /// `robustness2`'s `nullDescriptor`: binding a null descriptor and
+    /// accessing it behaves like an out-of-bounds access instead of
+    /// being invalid usage, so an unbound texture slot fails safely.
+    pub null_descriptor: bool,
+    /// `imagelessFramebuffer` (core since Vulkan 1.2): framebuffers are
+    /// created without concrete image views bound, so a swapchain
+    /// recreate doesn't require recreating every framebuffer that
+    /// references it.
+    pub imageless_framebuffer: bool,
+}
+
+impl Default for DeviceFeaturesDesc {
+    /// Shipping-safe defaults: robustness off (it costs real performance
+    /// on most drivers), imageless framebuffers on (free, and simplifies
+    /// swapchain-resize handling).
+    fn default() -> Self {
+        Self {
+            robust_buffer_access: false,
+            null_descriptor: false,
+            imageless_framebuffer: true,
+        }
+    }
+}
+
+impl DeviceFeaturesDesc {
+    /// Both robustness toggles on, for development/debug builds that
+    /// would rather fail safely than corrupt memory on an out-of-bounds
+    /// access.
+    pub fn debug() -> Self {
+        Self {
+            robust_buffer_access: true,
+            null_descriptor: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// Whether a `Device` needs to present anything. A compute-only device
+/// (asset baking, a headless simulation server) has no surface to present
+/// to and should not require `VK_KHR_swapchain` or a presentation-capable
+/// queue family at all.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeviceMode {
+    Presentable,
+    ComputeOnly,
+}
+
+impl Device {
+    pub fn create(physical_device: &PhysicalDevice) -> BackendResult<Arc<Device>> {
+        Self::create_with_mode(physical_device, DeviceMode::Presentable)
+    }
+
+    /// Like [`Device::create`], but for [`DeviceMode::ComputeOnly`] skips
+    /// `VK_KHR_swapchain`, so the device can be created on a system with no
+    /// display server and no surface at all (a render farm node baking
+    /// textures, for instance). Uses [`DeviceFeaturesDesc::default`]; call
+    /// [`Device::create_with_features`] directly to opt into robustness
+    /// checks for a debug build.
+    pub fn create_with_mode(
+        physical_device: &PhysicalDevice,
+        mode: DeviceMode,
+    ) -> BackendResult<Arc<Device>> {
+        Self::create_with_features(physical_device, mode, DeviceFeaturesDesc::default())
+    }
+
+    /// Full form of [`Device::create`]: lets the caller choose which
+    /// optional robustness/imageless-framebuffer features to enable via
+    /// `features`, instead of a single hardcoded feature chain. Creates a
+    /// single graphics/compute/transfer-capable queue; use
+    /// [`Device::create_with_queue_config`] for a dedicated async compute
+    /// or transfer queue.
+    pub fn create_with_features(
+        physical_device: &PhysicalDevice,
+        mode: DeviceMode,
+        features: DeviceFeaturesDesc,
+    ) -> BackendResult<Arc<Device>> {
+        Self::create_with_queue_config(physical_device, mode, features, QueueConfig::default())
+    }
+
+    /// Full form of [`Device::create_with_features`]: also lets the caller
+    /// request dedicated compute/transfer queues and override which queue
+    /// family each role lands on. [`Device::queue_report`] on the returned
+    /// `Device` reports what was actually created, since a requested
+    /// dedicated queue may fall back to sharing the graphics family on
+    /// hardware that doesn't expose one.
+    pub fn create_with_queue_config(
+        physical_device: &PhysicalDevice,
+        mode: DeviceMode,
+        features: DeviceFeaturesDesc,
+        queue_config: QueueConfig,
+    ) -> BackendResult<Arc<Device>> {
+        let graphics_family_index = match queue_config.graphics_family_override {
+            Some(family) => {
+                if !physical_device
+                    .queue_family_supports(family, vk::QueueFlags::GRAPHICS | vk::QueueFlags::COMPUTE)
+                {
+                    return Err(BackendError::Other(format!(
+                        "queue family {family} does not support graphics + compute"
+                    )));
+                }
+                family
+            }
+            None => physical_device
+                .graphics_queue_family_index()
+                .ok_or(BackendError::NoSuitablePhysicalDevice)?,
+        };
+
+        let resolve_role = |role: &QueueRoleConfig,
+                             required: vk::QueueFlags,
+                             dedicated: Option<u32>|
+         -> BackendResult<(u32, bool)> {
+            match role.family_override {
+                Some(family) => {
+                    if !physical_device.queue_family_supports(family, required) {
+                        return Err(BackendError::Other(format!(
+                            "queue family {family} does not support {required:?}"
+                        )));
+                    }
+                    Ok((family, family != graphics_family_index))
+                }
+                None => match dedicated {
+                    Some(family) => Ok((family, true)),
+                    None => Ok((graphics_family_index, false)),
+                },
+            }
+        };
+
+        let compute_resolved = queue_config
+            .compute
+            .as_ref()
+            .map(|role| {
+                resolve_role(
+                    role,
+                    vk::QueueFlags::COMPUTE,
+                    physical_device.dedicated_compute_queue_family_index(),
+                )
+            })
+            .transpose()?;
+
+        let transfer_resolved = queue_config
+            .transfer
+            .as_ref()
+            .map(|role| {
+                resolve_role(
+                    role,
+                    vk::QueueFlags::TRANSFER,
+                    physical_device.dedicated_transfer_queue_family_index(),
+                )
+            })
+            .transpose()?;
+
+        // One `DeviceQueueCreateInfo` per distinct family actually used —
+        // Vulkan rejects a `DeviceCreateInfo` that names the same family
+        // twice.
+        let mut family_priorities: Vec<(u32, f32)> = vec![(graphics_family_index, queue_config.graphics_priority)];
+        for (role, resolved) in [(&queue_config.compute, compute_resolved), (&queue_config.transfer, transfer_resolved)] {
+            if let (Some(role), Some((family, _))) = (role, resolved) {
+                if !family_priorities.iter().any(|&(existing, _)| existing == family) {
+                    family_priorities.push((family, role.priority));
+                }
+            }
+        }
+
+        let priorities: Vec<[f32; 1]> = family_priorities.iter().map(|&(_, priority)| [priority]).collect();
+        let queue_create_infos: Vec<vk::DeviceQueueCreateInfo> = family_priorities
+            .iter()
+            .zip(&priorities)
+            .map(|(&(family, _), priority)| {
+                vk::DeviceQueueCreateInfo::builder()
+                    .queue_family_index(family)
+                    .queue_priorities(priority)
+                    .build()
+            })
+            .collect();
+
+        let mut device_extension_names: Vec<*const i8> = match mode {
+            DeviceMode::Presentable => vec![
+                ash::extensions::khr::Swapchain::name().as_ptr(),
+                // Lets `Device::create_swapchain` create a mutable-format
+                // swapchain with a declared UNORM/SRGB view-format pair,
+                // so sRGB-correct output doesn't depend on drivers that
+                // only expose one or the other as a presentable format.
+                c"VK_KHR_image_format_list".as_ptr(),
+                c"VK_KHR_swapchain_mutable_format".as_ptr(),
+            ],
+            DeviceMode::ComputeOnly => vec![],
+        };
+        if features.null_descriptor {
+            device_extension_names.push(c"VK_EXT_robustness2".as_ptr());
+        }
+
+        let mut imageless_framebuffer_features =
+            vk::PhysicalDeviceImagelessFramebufferFeatures::builder()
+                .imageless_framebuffer(features.imageless_framebuffer);
+
+        let mut robustness2_features = vk::PhysicalDeviceRobustness2FeaturesEXT::builder()
+            .null_descriptor(features.null_descriptor);
+
+        let mut features2 = vk::PhysicalDeviceFeatures2::builder()
+            .features(vk::PhysicalDeviceFeatures {
+                robust_buffer_access: features.robust_buffer_access as vk::Bool32,
+                ..Default::default()
+            })
+            .push_next(&mut imageless_framebuffer_features);
+
+        if features.null_descriptor {
+            features2 = features2.push_next(&mut robustness2_features);
+        }
+
+        let create_info = vk::DeviceCreateInfo::builder()
+            .queue_create_infos(&queue_create_infos)
+            .enabled_extension_names(&device_extension_names)
+            .push_next(&mut features2);
+
+        let raw = unsafe {
+            physical_device
+                .instance
+                .raw()
+                .create_device(physical_device.raw, &create_info, None)?
+        };
+
+        let queue = unsafe { raw.get_device_queue(graphics_family_index, 0) };
+        let compute_queue_family_index = compute_resolved.map(|(family, _)| family);
+        let compute_queue =
+            compute_queue_family_index.map(|family| unsafe { raw.get_device_queue(family, 0) });
+        let transfer_queue_family_index = transfer_resolved.map(|(family, _)| family);
+        let transfer_queue =
+            transfer_queue_family_index.map(|family| unsafe { raw.get_device_queue(family, 0) });
+
+        let queue_report = QueueSetupReport {
+            graphics_family: graphics_family_index,
+            compute_family: compute_resolved.map(|(family, _)| family),
+            compute_is_dedicated: compute_resolved.is_some_and(|(_, dedicated)| dedicated),
+            transfer_family: transfer_resolved.map(|(family, _)| family),
+            transfer_is_dedicated: transfer_resolved.is_some_and(|(_, dedicated)| dedicated),
+        };
+
+        let (destroy_sender, destroy_receiver) = mpsc::channel();
+
+        Ok(Arc::new(Device {
+            physical_device: physical_device.clone(),
+            raw,
+            queue,
+            queue_family_index: graphics_family_index,
+            compute_queue,
+            compute_queue_family_index,
+            transfer_queue,
+            transfer_queue_family_index,
+            queue_report,
+            features,
+            format_policy: FormatPolicy::resolve(physical_device),
+            drop_list: Mutex::new(DropList::new()),
+            resource_registry: ResourceRegistry::new(),
+            destroy_sender,
+            destroy_receiver: Mutex::new(destroy_receiver),
+            frame_hooks: Mutex::new(FrameHooks::default()),
+            history_images: Mutex::new(HistoryImagePool::default()),
+            sync_pool: Mutex::new(SyncPool::default()),
+        }))
+    }
+
+    pub fn raw(&self) -> &ash::Device {
+        &self.raw
+    }
+
+    /// A cloneable [`ResourceDestroyer`] any thread can hold onto and call
+    /// [`ResourceDestroyer::destroy`] on, without needing a `&Device` — for
+    /// gameplay threads and asset-unload callbacks that don't otherwise
+    /// have one threaded through. Queued destroys are merged into this
+    /// device's own drop list the next time [`Device::collect_garbage`]
+    /// runs.
+    pub fn destroyer(&self) -> ResourceDestroyer {
+        ResourceDestroyer::new(self.destroy_sender.clone())
+    }
+
+    /// Creates a `vk::ShaderModule` from already-compiled SPIR-V bytes (a
+    /// [`kiri_assets::effect::ShaderStage`] holds only an `AssetRef` to
+    /// them, not the bytes themselves). Callers
+    /// that only need the module transiently to build a pipeline (the
+    /// common case) are responsible for destroying it afterwards.
+    pub fn create_shader_module(&self, spirv: &[u8]) -> BackendResult<vk::ShaderModule> {
+        debug_assert_eq!(spirv.len() % 4, 0, "SPIR-V must be a whole number of u32 words");
+        let words: Vec<u32> = spirv
+            .chunks_exact(4)
+            .map(|chunk| u32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+
+        let create_info = vk::ShaderModuleCreateInfo::builder().code(&words);
+        Ok(unsafe { self.raw.create_shader_module(&create_info, None)? })
+    }
+}
+
+impl Device {
+    /// Logs exactly which named resources (and how much drop-list backlog)
+    /// are still outstanding when a `Device` is about to be destroyed,
+    /// using [`Device::dump_resources`] rather than waiting for the
+    /// validation layer to notice the same thing at
+    /// `vkDestroyDevice`-time — the validation layer only runs when
+    /// enabled, and even then only reports a bare handle, not the name or
+    /// creation site [`Device::create_image_named`]/
+    /// [`Device::create_buffer_named`] attached to it.
+    ///
+    /// In a debug build (`debug_assertions`), a leaked named resource
+    /// fails the assertion after logging, so a leak shows up as a test
+    /// failure instead of a line a developer has to notice in the log;
+    /// release builds only log, since panicking during drop in a shipped
+    /// build would be worse than the leak it's reporting.
+    fn report_leaks(&self) {
+        self.absorb_destroyer_queue();
+        let drop_list_len = self.drop_list.lock().unwrap().len();
+        let live_resources = self.resource_registry.dump();
+
+        if drop_list_len == 0 && live_resources.is_empty() {
+            return;
+        }
+
+        if drop_list_len > 0 {
+            log::warn!(
+                "Device dropped with {drop_list_len} object(s) still queued in its drop list \
+                 (never reached by Device::collect_garbage)"
+            );
+        }
+
+        for resource in &live_resources {
+            log::error!(
+                "leaked {:?} {:?} ({} bytes), created at:\n{}",
+                resource.kind,
+                resource.name,
+                resource.size_bytes,
+                resource
+                    .creation_stack
+                    .as_deref()
+                    .unwrap_or("  <creation stack only captured in debug builds>"),
+            );
+        }
+
+        debug_assert!(
+            live_resources.is_empty(),
+            "{} named resource(s) leaked past Device::drop — see the error log above for names and creation stacks",
+            live_resources.len()
+        );
+    }
+}
+
+impl Drop for Device {
+    fn drop(&mut self) {
+        self.report_leaks();
+        unsafe {
+            self.raw.device_wait_idle().ok();
+            self.absorb_destroyer_queue();
+            self.drop_list.lock().unwrap().drain_destroy(&self.raw);
+            self.clear_sync_pool();
+            self.raw.destroy_device(None);
+        }
+    }
+}
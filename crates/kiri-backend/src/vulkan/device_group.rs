@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use crate::BackendResult;
+
+use super::device::Device;
+use super::instance::Instance;
+use super::physical_device::{enumerate_physical_devices, PhysicalDevice};
+
+/// A set of independently-created `Device`s, each targeting a different
+/// `PhysicalDevice` from the same `Instance`.
+///
+/// Each `Device` keeps its own queue and drop list, so a
+/// resource created on one device is never visible to another. Sharing an
+/// image between two devices in the group (e.g. handing a baked texture
+/// from the iGPU over to the dGPU) requires external memory support, which
+/// is added on top of this in a follow-up.
+///
+/// This is primarily meant for tools: baking assets on an integrated GPU
+/// while a discrete GPU is busy rendering, without the two contending for
+/// the same descriptor pools or command buffers.
+pub struct DeviceGroup {
+    devices: Vec<Arc<Device>>,
+}
+
+impl DeviceGroup {
+    /// Creates one `Device` per physical device index in `indices`, in the
+    /// order given by [`enumerate_physical_devices`].
+    pub fn create(instance: &Arc<Instance>, indices: &[usize]) -> BackendResult<Self> {
+        let physical_devices = enumerate_physical_devices(instance)?;
+
+        let devices = indices
+            .iter()
+            .map(|&index| {
+                let physical_device = &physical_devices[index];
+                Device::create(physical_device)
+            })
+            .collect::<BackendResult<Vec<_>>>()?;
+
+        Ok(Self { devices })
+    }
+
+    /// All devices currently in the group, in creation order.
+    pub fn devices(&self) -> &[Arc<Device>] {
+        &self.devices
+    }
+
+    pub fn device(&self, index: usize) -> &Arc<Device> {
+        &self.devices[index]
+    }
+
+    /// Physical device info for every device in the group, useful for
+    /// logging which GPU got picked for which role.
+    pub fn physical_device_names(&self) -> Vec<String> {
+        self.devices
+            .iter()
+            .map(|device| device.physical_device.name())
+            .collect()
+    }
+}
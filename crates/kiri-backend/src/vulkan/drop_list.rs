@@ -1,3 +1,247 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:6cb190ca4dcf791a70b9a3ca73ec278d0279d4f4ad1c94eb63da6318967da94c
-size 3664
+use std::collections::HashMap;
+
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::device::Device;
+
+/// A single GPU handle queued for destruction. Kept as one flat enum
+/// rather than a `Box<dyn FnOnce>` per entry so queuing a drop never
+/// allocates beyond the `Vec` growing.
+pub enum ToDrop {
+    Image(vk::Image),
+    ImageView(vk::ImageView),
+    Buffer(vk::Buffer),
+    Memory(vk::DeviceMemory),
+    Sampler(vk::Sampler),
+    DescriptorPool(vk::DescriptorPool),
+    DescriptorSetLayout(vk::DescriptorSetLayout),
+    PipelineLayout(vk::PipelineLayout),
+    Pipeline(vk::Pipeline),
+    ShaderModule(vk::ShaderModule),
+    RenderPass(vk::RenderPass),
+    Framebuffer(vk::Framebuffer),
+}
+
+impl ToDrop {
+    /// Category name used to key the per-category pending counts in
+    /// [`DropListStats`]. Matches the variant name, in snake_case.
+    fn category(&self) -> &'static str {
+        match self {
+            ToDrop::Image(_) => "image",
+            ToDrop::ImageView(_) => "image_view",
+            ToDrop::Buffer(_) => "buffer",
+            ToDrop::Memory(_) => "memory",
+            ToDrop::Sampler(_) => "sampler",
+            ToDrop::DescriptorPool(_) => "descriptor_pool",
+            ToDrop::DescriptorSetLayout(_) => "descriptor_set_layout",
+            ToDrop::PipelineLayout(_) => "pipeline_layout",
+            ToDrop::Pipeline(_) => "pipeline",
+            ToDrop::ShaderModule(_) => "shader_module",
+            ToDrop::RenderPass(_) => "render_pass",
+            ToDrop::Framebuffer(_) => "framebuffer",
+        }
+    }
+}
+
+macro_rules! impl_from_for_to_drop {
+    ($($variant:ident($ty:ty)),* $(,)?) => {
+        $(
+            impl From<$ty> for ToDrop {
+                fn from(handle: $ty) -> Self {
+                    ToDrop::$variant(handle)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_to_drop! {
+    Image(vk::Image),
+    ImageView(vk::ImageView),
+    Buffer(vk::Buffer),
+    Memory(vk::DeviceMemory),
+    Sampler(vk::Sampler),
+    DescriptorPool(vk::DescriptorPool),
+    DescriptorSetLayout(vk::DescriptorSetLayout),
+    PipelineLayout(vk::PipelineLayout),
+    Pipeline(vk::Pipeline),
+    ShaderModule(vk::ShaderModule),
+    RenderPass(vk::RenderPass),
+    Framebuffer(vk::Framebuffer),
+}
+
+/// How many objects can sit in a [`DropList`] before it logs a one-time
+/// warning that something is churning resources faster than the frame
+/// cycle reclaims them (a hot-reload loop gone wrong, a leak, ...).
+pub const DROP_LIST_WARN_THRESHOLD: usize = 4096;
+
+/// A snapshot of what's currently queued in a [`DropList`], for tools and
+/// debug overlays that want to watch destruction pressure over time.
+#[derive(Clone, Debug, Default)]
+pub struct DropListStats {
+    pub objects_by_category: HashMap<&'static str, usize>,
+    pub bytes_pending: u64,
+}
+
+impl DropListStats {
+    pub fn total_objects(&self) -> usize {
+        self.objects_by_category.values().sum()
+    }
+}
+
+/// A queue of GPU handles waiting to be destroyed. Letting go of a
+/// resource that a previous, still-in-flight frame might reference is
+/// only safe once that frame has retired; pushing it here instead of
+/// destroying it immediately lets callers avoid a `device_wait_idle` on
+/// every hot-reload of a shader, pipeline, or sampler.
+///
+/// This only tracks *what* to destroy, not *when* it's safe to — callers
+/// are expected to drain it at a point they know to be safe (e.g. once a
+/// frame's fence has signaled), not every frame unconditionally. When
+/// something churns resources faster than that cycle reclaims them, the
+/// list keeps growing; [`DropList::stats`] and the warn-threshold log line
+/// in [`DropList::push_sized`] are there to catch that before it becomes
+/// an out-of-memory crash, and [`Device::flush_deferred_destruction`] is
+/// the escape hatch for reclaiming it immediately.
+#[derive(Default)]
+pub struct DropList {
+    items: Vec<(ToDrop, u64)>,
+    objects_by_category: HashMap<&'static str, usize>,
+    bytes_pending: u64,
+    warned: bool,
+}
+
+impl DropList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, item: impl Into<ToDrop>) {
+        self.push_sized(item, 0);
+    }
+
+    /// Like [`DropList::push`], but also counts `size_bytes` towards
+    /// [`DropListStats::bytes_pending`] — use this for buffers, images, and
+    /// memory allocations, where the caller knows the size and it's worth
+    /// tracking; other categories can just use `push`.
+    pub fn push_sized(&mut self, item: impl Into<ToDrop>, size_bytes: u64) {
+        let item = item.into();
+        *self.objects_by_category.entry(item.category()).or_insert(0) += 1;
+        self.bytes_pending += size_bytes;
+        self.items.push((item, size_bytes));
+
+        if self.items.len() >= DROP_LIST_WARN_THRESHOLD && !self.warned {
+            log::warn!(
+                "DropList has {} objects pending destruction ({} bytes) — \
+                 resources are being churned faster than they're being reclaimed",
+                self.items.len(),
+                self.bytes_pending,
+            );
+            self.warned = true;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn stats(&self) -> DropListStats {
+        DropListStats {
+            objects_by_category: self.objects_by_category.clone(),
+            bytes_pending: self.bytes_pending,
+        }
+    }
+
+    /// Destroys every queued handle and empties the list.
+    ///
+    /// # Safety
+    /// Every entry must no longer be referenced by any pending GPU work.
+    pub unsafe fn drain_destroy(&mut self, device: &ash::Device) {
+        for (item, size_bytes) in self.items.drain(..) {
+            if let Some(count) = self.objects_by_category.get_mut(item.category()) {
+                *count -= 1;
+            }
+            self.bytes_pending -= size_bytes;
+
+            match item {
+                ToDrop::Image(image) => device.destroy_image(image, None),
+                ToDrop::ImageView(view) => device.destroy_image_view(view, None),
+                ToDrop::Buffer(buffer) => device.destroy_buffer(buffer, None),
+                ToDrop::Memory(memory) => device.free_memory(memory, None),
+                ToDrop::Sampler(sampler) => device.destroy_sampler(sampler, None),
+                ToDrop::DescriptorPool(pool) => device.destroy_descriptor_pool(pool, None),
+                ToDrop::DescriptorSetLayout(layout) => {
+                    device.destroy_descriptor_set_layout(layout, None)
+                }
+                ToDrop::PipelineLayout(layout) => device.destroy_pipeline_layout(layout, None),
+                ToDrop::Pipeline(pipeline) => device.destroy_pipeline(pipeline, None),
+                ToDrop::ShaderModule(module) => device.destroy_shader_module(module, None),
+                ToDrop::RenderPass(pass) => device.destroy_render_pass(pass, None),
+                ToDrop::Framebuffer(framebuffer) => device.destroy_framebuffer(framebuffer, None),
+            }
+        }
+        self.warned = false;
+    }
+}
+
+impl Device {
+    /// Queues `item` for destruction the next time [`Device::collect_garbage`]
+    /// is called, instead of destroying it right away.
+    pub fn queue_drop(&self, item: impl Into<ToDrop>) {
+        self.drop_list.lock().unwrap().push(item);
+    }
+
+    /// Like [`Device::queue_drop`], additionally counting `size_bytes`
+    /// towards the drop list's pending-bytes metric.
+    pub fn queue_drop_sized(&self, item: impl Into<ToDrop>, size_bytes: u64) {
+        self.drop_list.lock().unwrap().push_sized(item, size_bytes);
+    }
+
+    /// Snapshot of what's currently queued for destruction on this device.
+    pub fn drop_list_stats(&self) -> DropListStats {
+        self.drop_list.lock().unwrap().stats()
+    }
+
+    /// Moves every handle queued via a cloned [`super::resource_destroyer::ResourceDestroyer`]
+    /// (from any thread) into this device's own drop list, so
+    /// [`Device::collect_garbage`] and `Device`'s `Drop` impl only ever
+    /// have to drain one place.
+    pub(crate) fn absorb_destroyer_queue(&self) {
+        let mut drop_list = self.drop_list.lock().unwrap();
+        for item in self.destroy_receiver.lock().unwrap().try_iter() {
+            drop_list.push(item);
+        }
+    }
+
+    /// Destroys every handle queued via [`Device::queue_drop`] or a
+    /// [`super::resource_destroyer::ResourceDestroyer`] so far.
+    ///
+    /// # Safety
+    /// The caller must know none of the queued handles are still
+    /// referenced by in-flight GPU work — typically called once a frame,
+    /// after waiting on that frame's fence.
+    pub unsafe fn collect_garbage(&self) {
+        self.absorb_destroyer_queue();
+        self.drop_list.lock().unwrap().drain_destroy(&self.raw);
+    }
+
+    /// Waits for all in-flight GPU work to finish and immediately purges
+    /// everything queued in the drop list, regardless of the normal
+    /// two-frame reclaim cycle. Meant for tools that churn resources much
+    /// faster than a game would (asset-pipeline previews, hot-reload
+    /// loops) rather than the per-frame render path, since it stalls the
+    /// GPU.
+    pub fn flush_deferred_destruction(&self) -> BackendResult<()> {
+        unsafe {
+            self.raw.device_wait_idle()?;
+            self.collect_garbage();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use ash::vk;
+
+use super::device::Device;
+
+/// State kiri always marks dynamic on its graphics pipelines, so switching
+/// viewport/scissor/blend constants/line width never requires a pipeline
+/// variant or a `vkCmdBindPipeline` call.
+pub const DYNAMIC_STATES: &[vk::DynamicState] = &[
+    vk::DynamicState::VIEWPORT,
+    vk::DynamicState::SCISSOR,
+    vk::DynamicState::LINE_WIDTH,
+    vk::DynamicState::BLEND_CONSTANTS,
+];
+
+pub fn dynamic_state_create_info() -> vk::PipelineDynamicStateCreateInfo {
+    vk::PipelineDynamicStateCreateInfo::builder()
+        .dynamic_states(DYNAMIC_STATES)
+        .build()
+}
+
+/// The actual per-draw values behind [`DYNAMIC_STATES`], recorded into a
+/// command buffer right before the draw that needs them.
+#[derive(Clone, Copy, Debug)]
+pub struct DynamicStateValues {
+    pub viewport: vk::Viewport,
+    pub scissor: vk::Rect2D,
+    pub line_width: f32,
+    pub blend_constants: [f32; 4],
+}
+
+impl DynamicStateValues {
+    pub fn for_extent(extent: [u32; 2]) -> Self {
+        Self {
+            viewport: vk::Viewport {
+                x: 0.0,
+                y: 0.0,
+                width: extent[0] as f32,
+                height: extent[1] as f32,
+                min_depth: 0.0,
+                max_depth: 1.0,
+            },
+            scissor: vk::Rect2D {
+                offset: vk::Offset2D { x: 0, y: 0 },
+                extent: vk::Extent2D {
+                    width: extent[0],
+                    height: extent[1],
+                },
+            },
+            line_width: 1.0,
+            blend_constants: [0.0, 0.0, 0.0, 0.0],
+        }
+    }
+}
+
+impl Device {
+    pub fn cmd_apply_dynamic_state(&self, cb: vk::CommandBuffer, state: &DynamicStateValues) {
+        unsafe {
+            self.raw().cmd_set_viewport(cb, 0, std::slice::from_ref(&state.viewport));
+            self.raw().cmd_set_scissor(cb, 0, std::slice::from_ref(&state.scissor));
+            self.raw().cmd_set_line_width(cb, state.line_width);
+            self.raw().cmd_set_blend_constants(cb, &state.blend_constants);
+        }
+    }
+}
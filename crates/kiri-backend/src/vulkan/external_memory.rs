@@ -0,0 +1,118 @@
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::device::Device;
+
+/// Which external handle type to export/import, mapped to the matching
+/// Vulkan extension on each platform.
+///
+/// [`Device::create_with_queue_config`] does not enable these platform
+/// extensions, so exporting memory fails on a `Device` it creates;
+/// [`ExternalHandleKind::required_device_extension`] names the one each
+/// kind needs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExternalHandleKind {
+    /// `VK_KHR_external_memory_fd` — Linux fd, consumable by OpenGL, CUDA
+    /// interop, and most Linux video encoders.
+    OpaqueFd,
+    /// `VK_KHR_external_memory_win32` — Windows `HANDLE`, consumable by
+    /// D3D11/D3D12 and OBS Game/Window Capture.
+    OpaqueWin32,
+}
+
+impl ExternalHandleKind {
+    fn as_vk_handle_type(self) -> vk::ExternalMemoryHandleTypeFlags {
+        match self {
+            ExternalHandleKind::OpaqueFd => vk::ExternalMemoryHandleTypeFlags::OPAQUE_FD,
+            ExternalHandleKind::OpaqueWin32 => vk::ExternalMemoryHandleTypeFlags::OPAQUE_WIN32,
+        }
+    }
+
+    /// The extension name to request at device creation time for this
+    /// handle kind, on top of `VK_KHR_external_memory`.
+    pub fn required_device_extension(self) -> &'static std::ffi::CStr {
+        match self {
+            ExternalHandleKind::OpaqueFd => {
+                ash::extensions::khr::ExternalMemoryFd::name()
+            }
+            #[cfg(windows)]
+            ExternalHandleKind::OpaqueWin32 => {
+                ash::extensions::khr::ExternalMemoryWin32::name()
+            }
+            #[cfg(not(windows))]
+            ExternalHandleKind::OpaqueWin32 => {
+                ash::extensions::khr::ExternalMemoryFd::name()
+            }
+        }
+    }
+}
+
+/// An opaque OS handle to GPU memory backing a kiri image or buffer,
+/// exported for another API (OpenGL, D3D, CUDA) to import.
+pub enum ExternalMemoryHandle {
+    Fd(std::os::raw::c_int),
+    #[cfg(windows)]
+    Win32(vk::HANDLE),
+}
+
+/// `vk::ExternalMemoryImageCreateInfo` / `vk::ExternalMemoryBufferCreateInfo`
+/// pNext chains both need the same handle type flags; this is the shared
+/// piece resource creation plumbs into their `p_next` chain when a caller
+/// asks for an exportable allocation.
+pub fn external_memory_image_create_info(
+    kind: ExternalHandleKind,
+) -> vk::ExternalMemoryImageCreateInfo {
+    vk::ExternalMemoryImageCreateInfo::builder()
+        .handle_types(kind.as_vk_handle_type())
+        .build()
+}
+
+pub fn external_memory_buffer_create_info(
+    kind: ExternalHandleKind,
+) -> vk::ExternalMemoryBufferCreateInfo {
+    vk::ExternalMemoryBufferCreateInfo::builder()
+        .handle_types(kind.as_vk_handle_type())
+        .build()
+}
+
+/// Exports an OS handle to `memory`, allocated with a matching
+/// `ExternalMemoryImageCreateInfo`/`ExternalMemoryBufferCreateInfo` in its
+/// `p_next` chain. Exporting the same `vk::DeviceMemory` twice returns a
+/// new handle each time per the spec; callers are expected to close/release
+/// it once consumed.
+pub fn export_memory_fd(
+    device: &Device,
+    memory: vk::DeviceMemory,
+) -> BackendResult<ExternalMemoryHandle> {
+    let external_memory_fd = ash::extensions::khr::ExternalMemoryFd::new(
+        device.physical_device.instance.raw(),
+        device.raw(),
+    );
+
+    let fd = unsafe {
+        external_memory_fd.get_fd(
+            &vk::MemoryGetFdInfoKHR::builder()
+                .memory(memory)
+                .handle_type(vk::ExternalMemoryHandleTypeFlags::OPAQUE_FD),
+        )?
+    };
+
+    Ok(ExternalMemoryHandle::Fd(fd))
+}
+
+/// An exportable binary semaphore, used to hand off GPU-GPU or GPU-external
+/// ordering (e.g. "this frame's render is done, the encoder can read it
+/// now") without a CPU round-trip.
+pub fn create_exportable_semaphore(
+    device: &Device,
+    kind: ExternalHandleKind,
+) -> BackendResult<vk::Semaphore> {
+    let mut export_info = vk::ExportSemaphoreCreateInfo::builder()
+        .handle_types(kind.as_vk_handle_type())
+        .build();
+
+    let create_info = vk::SemaphoreCreateInfo::builder().push_next(&mut export_info);
+
+    Ok(unsafe { device.raw().create_semaphore(&create_info, None)? })
+}
@@ -0,0 +1,102 @@
+use ash::vk;
+
+use super::physical_device::PhysicalDevice;
+
+impl PhysicalDevice {
+    /// Returns the first of `candidates` (in preference order) supporting
+    /// every flag in `features` for `tiling`, or `None` if this device
+    /// supports none of them. Centralizes the
+    /// `vkGetPhysicalDeviceFormatProperties` round trip every "pick a
+    /// format for X" decision needs, instead of each call site querying
+    /// (or, worse, hardcoding) it separately.
+    pub fn find_optimal_format(
+        &self,
+        candidates: &[vk::Format],
+        tiling: vk::ImageTiling,
+        features: vk::FormatFeatureFlags,
+    ) -> Option<vk::Format> {
+        candidates.iter().copied().find(|&format| {
+            let properties = unsafe {
+                self.instance
+                    .raw()
+                    .get_physical_device_format_properties(self.raw, format)
+            };
+            let supported = match tiling {
+                vk::ImageTiling::LINEAR => properties.linear_tiling_features,
+                _ => properties.optimal_tiling_features,
+            };
+            supported.contains(features)
+        })
+    }
+}
+
+/// The set of formats this device should use for each role the renderer
+/// needs one for, resolved once against the physical device's actual
+/// format support instead of hardcoded per call site. Cached on
+/// [`super::device::Device`] at creation — see
+/// [`super::device::Device::format_policy`].
+#[derive(Clone, Copy, Debug)]
+pub struct FormatPolicy {
+    /// Best available depth (or depth+stencil) format, preferring a pure
+    /// depth format (`D32_SFLOAT`, no wasted stencil bits and no shared
+    /// aspect to worry about in a depth-only pass) over `D24_UNORM_S8_UINT`
+    /// on hardware that doesn't expose one.
+    pub depth: vk::Format,
+    /// Best HDR intermediate/scene-color format, preferring the full
+    /// 16-bit-per-channel float format most content is authored assuming,
+    /// falling back to `B10G11R11_UFLOAT_PACK32` (no alpha, less
+    /// precision, but half the memory) only if a device doesn't support
+    /// filtering/blending the wider format as a color attachment.
+    pub hdr_color: vk::Format,
+    /// Whether this device can sample `BC7`-compressed textures — the
+    /// baker's preferred desktop compressed format.
+    pub supports_bc7: bool,
+    /// Whether this device can sample `ASTC_4x4`-compressed textures — the
+    /// baker's preferred format on mobile/tiled-GPU targets.
+    pub supports_astc_4x4: bool,
+}
+
+impl FormatPolicy {
+    /// Resolves every field against `physical_device`'s actual format
+    /// support. Called once from [`super::device::Device::create_with_queue_config`]
+    /// so the render graph queries [`super::device::Device::format_policy`]
+    /// instead of re-deriving these choices (or hardcoding a format) itself.
+    pub fn resolve(physical_device: &PhysicalDevice) -> FormatPolicy {
+        let depth = physical_device
+            .find_optimal_format(
+                &[vk::Format::D32_SFLOAT, vk::Format::D24_UNORM_S8_UINT, vk::Format::D16_UNORM],
+                vk::ImageTiling::OPTIMAL,
+                vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT,
+            )
+            // Every Vulkan-conformant device supports at least one of the
+            // three candidates above, so this only trips on a broken
+            // driver — falling back to the most universally supported
+            // format keeps device creation from failing outright.
+            .unwrap_or(vk::Format::D16_UNORM);
+
+        let hdr_features = vk::FormatFeatureFlags::COLOR_ATTACHMENT_BLEND
+            | vk::FormatFeatureFlags::SAMPLED_IMAGE_FILTER_LINEAR;
+        let hdr_color = physical_device
+            .find_optimal_format(
+                &[vk::Format::R16G16B16A16_SFLOAT, vk::Format::B10G11R11_UFLOAT_PACK32],
+                vk::ImageTiling::OPTIMAL,
+                hdr_features,
+            )
+            .unwrap_or(vk::Format::R16G16B16A16_SFLOAT);
+
+        let sampled = vk::FormatFeatureFlags::SAMPLED_IMAGE;
+        let supports_bc7 = physical_device
+            .find_optimal_format(&[vk::Format::BC7_UNORM_BLOCK], vk::ImageTiling::OPTIMAL, sampled)
+            .is_some();
+        let supports_astc_4x4 = physical_device
+            .find_optimal_format(&[vk::Format::ASTC_4X4_UNORM_BLOCK], vk::ImageTiling::OPTIMAL, sampled)
+            .is_some();
+
+        FormatPolicy {
+            depth,
+            hdr_color,
+            supports_bc7,
+            supports_astc_4x4,
+        }
+    }
+}
@@ -1,3 +1,53 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:09053c8afa7bb963f2dc7e0d19bebf90bd73bfdba508169e7b0acb7bf5926af6
-size 5849
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::device::Device;
+
+/// Per-frame state: the command pool/buffer and the fence gating reuse of
+/// this frame-in-flight slot.
+///
+/// Deliberately doesn't own an acquire or a render-finished semaphore —
+/// see [`super::swapchain::SwapchainSync`] for why those need to be keyed
+/// on frame-in-flight index and swapchain image index respectively, not
+/// bundled onto the same per-frame slot as this command buffer.
+pub struct Frame {
+    pub command_pool: vk::CommandPool,
+    pub command_buffer: vk::CommandBuffer,
+    pub fence: vk::Fence,
+}
+
+impl Device {
+    pub fn create_frame(&self) -> BackendResult<Frame> {
+        let command_pool = unsafe {
+            self.raw().create_command_pool(
+                &vk::CommandPoolCreateInfo::builder()
+                    .queue_family_index(self.queue_family_index)
+                    .flags(vk::CommandPoolCreateFlags::TRANSIENT),
+                None,
+            )?
+        };
+
+        let command_buffer = unsafe {
+            self.raw().allocate_command_buffers(
+                &vk::CommandBufferAllocateInfo::builder()
+                    .command_pool(command_pool)
+                    .level(vk::CommandBufferLevel::PRIMARY)
+                    .command_buffer_count(1),
+            )?[0]
+        };
+
+        let fence = unsafe {
+            self.raw().create_fence(
+                &vk::FenceCreateInfo::builder().flags(vk::FenceCreateFlags::SIGNALED),
+                None,
+            )?
+        };
+
+        Ok(Frame {
+            command_pool,
+            command_buffer,
+            fence,
+        })
+    }
+}
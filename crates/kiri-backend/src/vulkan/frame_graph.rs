@@ -0,0 +1,132 @@
+use std::fmt::Write as _;
+
+/// A minimal, backend-agnostic description of one frame's render passes
+/// and the resources they read/write, built by the renderer before
+/// recording any Vulkan commands.
+///
+/// Today this only exists to answer "what ran, and in what order, and why
+/// does pass B depend on pass A" for debugging — it does not yet drive
+/// barrier insertion or pass reordering itself.
+#[derive(Default)]
+pub struct FrameGraph {
+    passes: Vec<PassNode>,
+    resources: Vec<ResourceInfo>,
+}
+
+struct PassNode {
+    name: String,
+    reads: Vec<usize>,
+    writes: Vec<usize>,
+}
+
+struct ResourceInfo {
+    name: String,
+    /// Set by [`FrameGraph::resource_history`] — a resource fed by last
+    /// frame's write rather than an earlier pass in this same frame (TAA,
+    /// SSR, and auto-exposure all read one of these). Purely descriptive
+    /// here; the actual previous-frame image is a [`super::history_image::HistoryImage`]
+    /// looked up by the same name via [`Device::ensure_history_image`].
+    is_history: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ResourceHandle(usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PassHandle(usize);
+
+impl FrameGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn resource(&mut self, name: impl Into<String>) -> ResourceHandle {
+        self.resources.push(ResourceInfo {
+            name: name.into(),
+            is_history: false,
+        });
+        ResourceHandle(self.resources.len() - 1)
+    }
+
+    /// Like [`FrameGraph::resource`], but marks the resource as a history
+    /// read — last frame's version of something, kept alive and fed in as
+    /// this frame's input, rather than a resource written earlier in the
+    /// same frame. Doesn't allocate anything itself; pair it with a call
+    /// to [`super::device::Device::ensure_history_image`] using the same
+    /// name to get the actual image.
+    pub fn resource_history(&mut self, name: impl Into<String>) -> ResourceHandle {
+        self.resources.push(ResourceInfo {
+            name: name.into(),
+            is_history: true,
+        });
+        ResourceHandle(self.resources.len() - 1)
+    }
+
+    pub fn pass(
+        &mut self,
+        name: impl Into<String>,
+        reads: &[ResourceHandle],
+        writes: &[ResourceHandle],
+    ) -> PassHandle {
+        self.passes.push(PassNode {
+            name: name.into(),
+            reads: reads.iter().map(|h| h.0).collect(),
+            writes: writes.iter().map(|h| h.0).collect(),
+        });
+        PassHandle(self.passes.len() - 1)
+    }
+
+    /// Renders the graph as GraphViz `dot` source: one node per pass, one
+    /// node per resource, edges from a resource to every pass that reads
+    /// it and from every pass to the resources it writes.
+    pub fn to_graphviz(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "digraph frame_graph {{").unwrap();
+        writeln!(out, "  rankdir=LR;").unwrap();
+
+        for (index, pass) in self.passes.iter().enumerate() {
+            writeln!(out, "  pass{index} [shape=box, label=\"{}\"];", pass.name).unwrap();
+        }
+        for (index, resource) in self.resources.iter().enumerate() {
+            let style = if resource.is_history { ", style=dashed" } else { "" };
+            writeln!(
+                out,
+                "  res{index} [shape=ellipse, label=\"{}\"{style}];",
+                resource.name
+            )
+            .unwrap();
+        }
+
+        for (pass_index, pass) in self.passes.iter().enumerate() {
+            for &resource in &pass.reads {
+                writeln!(out, "  res{resource} -> pass{pass_index};").unwrap();
+            }
+            for &resource in &pass.writes {
+                writeln!(out, "  pass{pass_index} -> res{resource};").unwrap();
+            }
+        }
+
+        writeln!(out, "}}").unwrap();
+        out
+    }
+
+    /// Renders the graph as JSON, for tooling that would rather parse a
+    /// structured dump than `dot` source.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "resources": self.resources.iter().map(|resource| {
+                serde_json::json!({
+                    "name": resource.name,
+                    "history": resource.is_history,
+                })
+            }).collect::<Vec<_>>(),
+            "passes": self.passes.iter().map(|pass| {
+                serde_json::json!({
+                    "name": pass.name,
+                    "reads": pass.reads,
+                    "writes": pass.writes,
+                })
+            }).collect::<Vec<_>>(),
+        })
+    }
+}
@@ -0,0 +1,122 @@
+//! Registration point for callbacks that want to observe frame
+//! boundaries without owning the frame loop themselves — an egui
+//! overlay, a profiler, or a capture tool (RenderDoc, PIX) all want to
+//! know when a frame starts and when it's been handed to the
+//! presentation engine, but none of them should have to fork
+//! `kiri-app`'s `run` loop, or the game's own loop, to get that.
+//!
+//! Registered closures run in registration order and are never removed
+//! automatically — an integration that only wants to fire once should
+//! track its own "already ran" flag. Hooks run on whatever thread drives
+//! the frame (the event loop thread for [`Device::run_begin_frame_hooks`]
+//! and [`Device::run_on_swapchain_recreate_hooks`], the thread that calls
+//! [`super::swapchain::Swapchain::present`] for the other two) — they are
+//! not a place to do expensive work without expecting it to show up in
+//! frame time.
+
+use super::device::Device;
+use super::swapchain::Swapchain;
+
+type Hook = Box<dyn FnMut() + Send>;
+type SwapchainRecreateHook = Box<dyn FnMut(&Swapchain) + Send>;
+
+#[derive(Default)]
+pub(crate) struct FrameHooks {
+    begin_frame: Vec<Hook>,
+    before_submit: Vec<Hook>,
+    after_present: Vec<Hook>,
+    on_swapchain_recreate: Vec<SwapchainRecreateHook>,
+}
+
+impl FrameHooks {
+    fn run_begin_frame(&mut self) {
+        for hook in &mut self.begin_frame {
+            hook();
+        }
+    }
+
+    fn run_before_submit(&mut self) {
+        for hook in &mut self.before_submit {
+            hook();
+        }
+    }
+
+    fn run_after_present(&mut self) {
+        for hook in &mut self.after_present {
+            hook();
+        }
+    }
+
+    fn run_on_swapchain_recreate(&mut self, swapchain: &Swapchain) {
+        for hook in &mut self.on_swapchain_recreate {
+            hook(swapchain);
+        }
+    }
+}
+
+impl Device {
+    /// Registers `hook` to run once at the start of every frame — for
+    /// `kiri-app`'s `run`, right before `kiri_app::App::render` is called;
+    /// for a game driving its own loop, wherever it considers a frame to
+    /// begin.
+    pub fn register_begin_frame_hook(&self, hook: impl FnMut() + Send + 'static) {
+        self.frame_hooks.lock().unwrap().begin_frame.push(Box::new(hook));
+    }
+
+    /// Registers `hook` to run in [`Swapchain::present`], immediately
+    /// before the present call queues the frame's image to the
+    /// presentation engine. The caller has already submitted the frame's
+    /// command buffers by then.
+    pub fn register_before_submit_hook(&self, hook: impl FnMut() + Send + 'static) {
+        self.frame_hooks.lock().unwrap().before_submit.push(Box::new(hook));
+    }
+
+    /// Registers `hook` to run in [`Swapchain::present`], right after the
+    /// present call returns successfully.
+    pub fn register_after_present_hook(&self, hook: impl FnMut() + Send + 'static) {
+        self.frame_hooks.lock().unwrap().after_present.push(Box::new(hook));
+    }
+
+    /// Registers `hook` to run whenever the swapchain is (re)created —
+    /// on the first frame, on resize, and after a device-lost recovery.
+    /// Integrations that cache per-swapchain-image state (an egui render
+    /// target per image, a capture tool's readback buffers) should
+    /// rebuild it here rather than polling `Swapchain::images.len()`
+    /// every frame.
+    pub fn register_on_swapchain_recreate_hook(
+        &self,
+        hook: impl FnMut(&Swapchain) + Send + 'static,
+    ) {
+        self.frame_hooks
+            .lock()
+            .unwrap()
+            .on_swapchain_recreate
+            .push(Box::new(hook));
+    }
+
+    /// Runs the registered begin-frame hooks. `kiri-app`'s `run` calls
+    /// this once per frame; a game driving its own loop instead of
+    /// `kiri-app` should call it at the equivalent point.
+    pub fn run_begin_frame_hooks(&self) {
+        self.frame_hooks.lock().unwrap().run_begin_frame();
+    }
+
+    pub(crate) fn run_before_submit_hooks(&self) {
+        self.frame_hooks.lock().unwrap().run_before_submit();
+    }
+
+    pub(crate) fn run_after_present_hooks(&self) {
+        self.frame_hooks.lock().unwrap().run_after_present();
+    }
+
+    /// Runs the registered swapchain-recreate hooks. `kiri-app`'s `run`
+    /// calls this whenever it (re)creates the swapchain; a game driving
+    /// its own loop instead of `kiri-app` should call it at the
+    /// equivalent point.
+    pub fn run_on_swapchain_recreate_hooks(&self, swapchain: &Swapchain) {
+        self.frame_hooks
+            .lock()
+            .unwrap()
+            .run_on_swapchain_recreate(swapchain);
+    }
+}
@@ -0,0 +1,156 @@
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::device::Device;
+use super::frame_graph::{FrameGraph, PassHandle, ResourceHandle};
+use super::image::{Image, ImageDesc};
+
+/// Dimensions of the froxel grid a [`FroxelFogPass`] scatters/integrates
+/// light into. `depth_slices` splits the camera's near/far range
+/// logarithmically (packing more resolution near the camera, where fog
+/// detail matters most) rather than linearly.
+#[derive(Clone, Copy, Debug)]
+pub struct FroxelGridDesc {
+    pub width: u32,
+    pub height: u32,
+    pub depth_slices: u32,
+    pub near: f32,
+    pub far: f32,
+}
+
+impl FroxelGridDesc {
+    /// A grid coarse enough to run at 1/8th of a typical 1080p-ish target
+    /// resolution per axis — froxel fog is inherently low-frequency, so
+    /// this is oversized for most content rather than undersized.
+    pub fn default_for_resolution(render_extent: [u32; 2]) -> Self {
+        Self {
+            width: (render_extent[0] / 8).max(1),
+            height: (render_extent[1] / 8).max(1),
+            depth_slices: 64,
+            near: 0.1,
+            far: 64.0,
+        }
+    }
+}
+
+/// A local fog volume: a sphere of extra density/scattering color, for
+/// scripted effects (a smoke grenade, a magic mist) layered on top of
+/// height fog. Baked lighting/asset data doesn't describe these — they're
+/// spawned and moved by gameplay code frame to frame.
+#[derive(Clone, Copy, Debug)]
+pub struct LocalFogVolume {
+    pub center: [f32; 3],
+    pub radius: f32,
+    pub density: f32,
+    pub scattering_color: [f32; 3],
+}
+
+/// Global (height-based) fog plus every active [`LocalFogVolume`] this
+/// frame — what the froxel media-injection step reads to build the
+/// scattering/extinction volume before lighting is applied.
+#[derive(Clone, Debug, Default)]
+pub struct FogMediaDesc {
+    pub height_fog_density: f32,
+    pub height_fog_falloff: f32,
+    pub height_fog_base_height: f32,
+    pub ambient_scattering_color: [f32; 3],
+    pub local_volumes: Vec<LocalFogVolume>,
+}
+
+/// The froxel grid's GPU-side storage: a `TYPE_3D` image per grid, double
+/// buffered so the current frame's integration pass can read the previous
+/// frame's result for temporal reprojection (froxel fog is expensive
+/// enough per-sample that most of a frame's detail comes from
+/// accumulating history rather than resolving it in one pass).
+pub struct FroxelVolume {
+    pub scattering_extinction: Image,
+    pub history: Image,
+}
+
+impl Device {
+    /// Allocates the pair of `TYPE_3D` images a [`FroxelFogPass`] needs for
+    /// `grid`. `RGBA16_SFLOAT`: rgb is scattered light color, alpha is
+    /// extinction — the same packing most froxel fog implementations use
+    /// so a single texture read gives both terms the raymarch/composite
+    /// step needs.
+    pub fn create_froxel_volume(&self, grid: &FroxelGridDesc) -> BackendResult<FroxelVolume> {
+        let extent = [grid.width, grid.height, grid.depth_slices];
+        let usage = vk::ImageUsageFlags::STORAGE | vk::ImageUsageFlags::SAMPLED;
+
+        Ok(FroxelVolume {
+            scattering_extinction: self
+                .create_image(ImageDesc::new_3d(vk::Format::R16G16B16A16_SFLOAT, extent).usage(usage))?,
+            history: self
+                .create_image(ImageDesc::new_3d(vk::Format::R16G16B16A16_SFLOAT, extent).usage(usage))?,
+        })
+    }
+}
+
+impl FroxelVolume {
+    pub fn queue_drop(&self, device: &Device) {
+        for image in [&self.scattering_extinction, &self.history] {
+            device.queue_drop(image.raw);
+            device.queue_drop(image.memory);
+            device.queue_drop(image.view);
+        }
+    }
+}
+
+/// A froxel-based volumetric fog system: media injection, light
+/// scattering, temporal reprojection, and composite into the lighting
+/// output, as four frame-graph passes sharing one [`FroxelVolume`].
+///
+/// The scattering pass reads a clustered light list resource by handle
+/// (`clustered_lights`, threaded in by the caller) rather than owning any
+/// clustered-lighting logic itself — clustered light culling is a
+/// separate system this pass consumes, not one it implements.
+pub struct FroxelFogPass {
+    pub grid: FroxelGridDesc,
+    pub volume: FroxelVolume,
+    pub media: FogMediaDesc,
+}
+
+impl FroxelFogPass {
+    pub fn new(grid: FroxelGridDesc, volume: FroxelVolume) -> Self {
+        Self {
+            grid,
+            volume,
+            media: FogMediaDesc::default(),
+        }
+    }
+
+    /// Registers this frame's four passes — injection, scattering,
+    /// temporal reprojection, composite — against `clustered_lights` (the
+    /// light-cluster resource populated upstream this frame) and
+    /// `scene_color` (the lighting output this pass composites fog into).
+    /// Returns the composite pass's handle.
+    pub fn register(
+        &self,
+        graph: &mut FrameGraph,
+        clustered_lights: ResourceHandle,
+        scene_color: ResourceHandle,
+    ) -> PassHandle {
+        let media = graph.resource("froxel_fog_media");
+        let scattering = graph.resource("froxel_fog_scattering");
+        let history = graph.resource("froxel_fog_history");
+        let reprojected = graph.resource("froxel_fog_reprojected");
+
+        graph.pass("froxel_fog_injection", &[], &[media]);
+        graph.pass(
+            "froxel_fog_scattering",
+            &[media, clustered_lights],
+            &[scattering],
+        );
+        graph.pass(
+            "froxel_fog_reprojection",
+            &[scattering, history],
+            &[reprojected],
+        );
+        graph.pass("froxel_fog_composite", &[reprojected, scene_color], &[scene_color])
+    }
+
+    pub fn queue_drop(&self, device: &Device) {
+        self.volume.queue_drop(device);
+    }
+}
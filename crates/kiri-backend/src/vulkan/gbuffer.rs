@@ -0,0 +1,116 @@
+use ash::vk;
+
+use kiri_assets::effect::RenderPath;
+
+use crate::BackendResult;
+
+use super::device::Device;
+use super::frame_graph::{FrameGraph, PassHandle, ResourceHandle};
+use super::image::{Image, ImageDesc};
+
+/// The attachment set a [`RenderPath::Deferred`] geometry pass writes,
+/// read back by the lighting-resolve pass that follows it. Layout mirrors
+/// what most deferred renderers settle on: `albedo` also carries an alpha
+/// channel used as a material ID mask rather than transparency (the
+/// deferred path doesn't support blended geometry — that still goes
+/// through a forward pass over the resolved result).
+pub struct GBuffer {
+    pub albedo: Image,
+    pub normal: Image,
+    pub motion: Image,
+    pub depth: Image,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GBufferDesc {
+    pub extent: [u32; 2],
+}
+
+impl Device {
+    pub fn create_gbuffer(&self, desc: &GBufferDesc) -> BackendResult<GBuffer> {
+        let color_usage = vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::SAMPLED;
+        let depth_usage = vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT | vk::ImageUsageFlags::SAMPLED;
+
+        Ok(GBuffer {
+            albedo: self.create_image(
+                ImageDesc::new_2d(vk::Format::R8G8B8A8_UNORM, desc.extent).usage(color_usage),
+            )?,
+            // Octahedral-encoded normal plus roughness: two channels would
+            // do for the normal alone, but packing roughness alongside it
+            // avoids a fifth attachment just for one scalar.
+            normal: self.create_image(
+                ImageDesc::new_2d(vk::Format::R16G16B16A16_SFLOAT, desc.extent).usage(color_usage),
+            )?,
+            motion: self.create_image(
+                ImageDesc::new_2d(vk::Format::R16G16_SFLOAT, desc.extent).usage(color_usage),
+            )?,
+            depth: self.create_image(
+                ImageDesc::new_2d(self.format_policy.depth, desc.extent).usage(depth_usage),
+            )?,
+        })
+    }
+}
+
+impl GBuffer {
+    pub fn queue_drop(&self, device: &Device) {
+        for image in [&self.albedo, &self.normal, &self.motion, &self.depth] {
+            device.queue_drop(image.raw);
+            device.queue_drop(image.memory);
+            device.queue_drop(image.view);
+        }
+    }
+}
+
+/// Which pass topology the renderer draws this frame's opaque geometry
+/// with. [`RenderPath::Forward`] needs no extra resources beyond the
+/// scene color/depth targets every path already has; [`RenderPath::Deferred`]
+/// additionally needs a [`GBuffer`] and a lighting-resolve pass, built by
+/// [`DeferredPath::register`].
+pub struct DeferredPath {
+    pub gbuffer: GBuffer,
+}
+
+impl DeferredPath {
+    pub fn new(gbuffer: GBuffer) -> Self {
+        Self { gbuffer }
+    }
+
+    /// Registers the geometry and lighting-resolve passes: geometry writes
+    /// `albedo`/`normal`/`motion`/`depth`, resolve reads them (plus
+    /// `clustered_lights`, this frame's light-cluster resource) and writes
+    /// `scene_color`. Materials drawn into the geometry pass must be
+    /// permutations compiled with [`RenderPath::Deferred`] — see
+    /// `kiri-asset-pipe::material_compile::UberShaderCompiler`.
+    pub fn register(
+        &self,
+        graph: &mut FrameGraph,
+        clustered_lights: ResourceHandle,
+        scene_color: ResourceHandle,
+    ) -> PassHandle {
+        let albedo = graph.resource("gbuffer_albedo");
+        let normal = graph.resource("gbuffer_normal");
+        let motion = graph.resource("gbuffer_motion");
+        let depth = graph.resource("gbuffer_depth");
+
+        graph.pass("gbuffer_geometry", &[], &[albedo, normal, motion, depth]);
+        graph.pass(
+            "lighting_resolve",
+            &[albedo, normal, motion, depth, clustered_lights],
+            &[scene_color],
+        )
+    }
+
+    pub fn queue_drop(&self, device: &Device) {
+        self.gbuffer.queue_drop(device);
+    }
+}
+
+/// Selects, per view, which pass topology to draw opaque geometry with.
+/// `kiri-app` games read this to decide whether to stand up a
+/// [`DeferredPath`] alongside their forward resources; nothing here
+/// forces a single choice for the whole application, since a forward UI
+/// or debug overlay pass often coexists with a deferred main view.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RenderPathConfig {
+    pub main_view: RenderPath,
+}
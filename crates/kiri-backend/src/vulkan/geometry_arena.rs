@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use ash::vk;
+
+use crate::{BackendError, BackendResult};
+
+use super::buffer::{Buffer, BufferDesc};
+use super::device::Device;
+
+/// Identifies a single suballocation carved out of a [`GeometryArena`].
+/// Stable for the allocation's lifetime; meaningless once the arena that
+/// handed it out is dropped or the allocation has been freed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BufferHandle(pub u32);
+
+struct Allocation {
+    offset: u32,
+    size: u32,
+}
+
+/// One large device buffer that vertex, index, and uniform data for many
+/// meshes gets bump-allocated out of, instead of every mesh owning its own
+/// `vk::Buffer`. Cuts down both the number of live Vulkan buffer objects
+/// and the bind churn of switching buffers between draws.
+///
+/// Never shrinks or compacts — freeing isn't supported yet, since nothing
+/// in kiri unloads geometry mid-session today. Bake-time content is
+/// expected to fit in one arena sized up front.
+pub struct GeometryArena {
+    buffer: Buffer,
+    cursor: u32,
+    capacity: u32,
+    allocations: HashMap<u32, Allocation>,
+    next_handle: u32,
+}
+
+impl Device {
+    /// Creates a [`GeometryArena`] backed by a single host-visible buffer
+    /// of `capacity` bytes, usable for any of `usage`'s buffer types
+    /// (typically `VERTEX_BUFFER | INDEX_BUFFER | UNIFORM_BUFFER`).
+    pub fn create_geometry_arena(
+        &self,
+        capacity: u32,
+        usage: vk::BufferUsageFlags,
+    ) -> BackendResult<GeometryArena> {
+        let buffer = self.create_buffer(BufferDesc::new_cpu_to_gpu(capacity as usize, usage))?;
+
+        Ok(GeometryArena {
+            buffer,
+            cursor: 0,
+            capacity,
+            allocations: HashMap::new(),
+            next_handle: 0,
+        })
+    }
+}
+
+impl GeometryArena {
+    /// Bump-allocates `data.len()` bytes aligned to `align`, uploads
+    /// `data` into it, and returns a [`BufferSlice`] addressing the new
+    /// region.
+    pub fn alloc(&mut self, device: &Device, data: &[u8], align: u32) -> BackendResult<BufferSlice> {
+        let offset = align_up(self.cursor, align.max(1));
+        let size = data.len() as u32;
+
+        if offset.checked_add(size).map_or(true, |end| end > self.capacity) {
+            return Err(BackendError::Other(
+                "GeometryArena is out of space for this allocation".to_string(),
+            ));
+        }
+
+        self.buffer.write_at(device, offset as u64, data)?;
+        self.cursor = offset + size;
+
+        let handle = BufferHandle(self.next_handle);
+        self.next_handle += 1;
+        self.allocations.insert(handle.0, Allocation { offset, size });
+
+        Ok(BufferSlice(handle, offset))
+    }
+
+    pub fn raw_buffer(&self) -> vk::Buffer {
+        self.buffer.raw
+    }
+
+    pub fn usage(&self) -> vk::BufferUsageFlags {
+        self.buffer.desc.usage
+    }
+
+    fn allocation(&self, handle: BufferHandle) -> &Allocation {
+        self.allocations.get(&handle.0).unwrap_or_else(|| {
+            panic!(
+                "BufferSlice({:?}) does not reference a live allocation in this GeometryArena \
+                 (freed, or from a different arena)",
+                handle
+            )
+        })
+    }
+}
+
+/// A view into one [`GeometryArena`] allocation: which allocation
+/// (`BufferHandle`) and at what byte offset within the arena's underlying
+/// buffer. On its own this is just two numbers — every accessor and
+/// binding helper needs the originating `GeometryArena` passed back in to
+/// resolve it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferSlice(pub BufferHandle, pub u32);
+
+impl BufferSlice {
+    pub fn handle(&self) -> BufferHandle {
+        self.0
+    }
+
+    pub fn offset(&self) -> u32 {
+        self.1
+    }
+
+    pub fn size(&self, arena: &GeometryArena) -> u32 {
+        arena.allocation(self.0).size
+    }
+
+    /// Checks that `arena`'s underlying buffer supports `usage` and that
+    /// this slice still fits within it. Call before binding a slice for a
+    /// use the arena wasn't created for (e.g. treating a vertex-only
+    /// arena's slice as a uniform buffer) instead of letting the driver
+    /// reject it.
+    pub fn validate(&self, arena: &GeometryArena, usage: vk::BufferUsageFlags) -> BackendResult<()> {
+        if !arena.usage().contains(usage) {
+            return Err(BackendError::Other(format!(
+                "BufferSlice usage {:?} is not supported by its arena's buffer usage {:?}",
+                usage,
+                arena.usage()
+            )));
+        }
+
+        let allocation = arena.allocation(self.0);
+        let end = allocation.offset as u64 + allocation.size as u64;
+        if end > arena.buffer.desc.size as u64 {
+            return Err(BackendError::Other(
+                "BufferSlice extends past the end of its arena's buffer".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn bind_as_vertex_buffer(
+        &self,
+        device: &Device,
+        arena: &GeometryArena,
+        command_buffer: vk::CommandBuffer,
+        binding: u32,
+    ) -> BackendResult<()> {
+        self.validate(arena, vk::BufferUsageFlags::VERTEX_BUFFER)?;
+        unsafe {
+            device.raw().cmd_bind_vertex_buffers(
+                command_buffer,
+                binding,
+                &[arena.raw_buffer()],
+                &[self.offset() as u64],
+            );
+        }
+        Ok(())
+    }
+
+    pub fn bind_as_index_buffer(
+        &self,
+        device: &Device,
+        arena: &GeometryArena,
+        command_buffer: vk::CommandBuffer,
+        index_type: vk::IndexType,
+    ) -> BackendResult<()> {
+        self.validate(arena, vk::BufferUsageFlags::INDEX_BUFFER)?;
+        unsafe {
+            device.raw().cmd_bind_index_buffer(
+                command_buffer,
+                arena.raw_buffer(),
+                self.offset() as u64,
+                index_type,
+            );
+        }
+        Ok(())
+    }
+
+    /// Builds the `vk::DescriptorBufferInfo` for binding this slice as a
+    /// uniform buffer in a descriptor set write.
+    pub fn descriptor_buffer_info(&self, arena: &GeometryArena) -> BackendResult<vk::DescriptorBufferInfo> {
+        self.validate(arena, vk::BufferUsageFlags::UNIFORM_BUFFER)?;
+        Ok(vk::DescriptorBufferInfo::builder()
+            .buffer(arena.raw_buffer())
+            .offset(self.offset() as u64)
+            .range(self.size(arena) as u64)
+            .build())
+    }
+}
+
+fn align_up(value: u32, align: u32) -> u32 {
+    (value + align - 1) / align * align
+}
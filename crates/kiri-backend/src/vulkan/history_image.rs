@@ -0,0 +1,142 @@
+//! Generic previous-frame ("history") image management, so a pass like
+//! TAA, SSR, or auto-exposure that needs last frame's output as an input
+//! this frame doesn't have to hand-roll a double-buffered pair and
+//! recreate it on every resize itself — see
+//! [`super::froxel_fog::FroxelVolume`] for what that looks like done by
+//! hand today.
+//!
+//! A [`HistoryImage`] is a pair of images and an `active` index that
+//! [`HistoryImage::swap`] flips once per frame. [`Device::ensure_history_image`]
+//! looks one up (or creates/resizes it) by name and hands back a
+//! [`HistoryFrame`] with this frame's write target and, once there's been
+//! a prior frame, last frame's.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::device::Device;
+use super::image::{Image, ImageDesc};
+
+struct HistoryImage {
+    desc: ImageDesc,
+    images: [Image; 2],
+    active: usize,
+    /// `false` until the first [`HistoryImage::swap`] — there's nothing
+    /// to reproject from on the resource's very first frame, and a
+    /// freshly (re)created pair's second slot holds garbage, not "last
+    /// frame".
+    primed: bool,
+}
+
+impl HistoryImage {
+    fn current(&self) -> &Image {
+        &self.images[self.active]
+    }
+
+    fn previous(&self) -> Option<&Image> {
+        self.primed.then(|| &self.images[1 - self.active])
+    }
+
+    fn swap(&mut self) {
+        self.active = 1 - self.active;
+        self.primed = true;
+    }
+
+    fn queue_drop(&self, device: &Device) {
+        for image in &self.images {
+            device.queue_drop(image.raw);
+            device.queue_drop(image.memory);
+            device.queue_drop(image.view);
+        }
+    }
+}
+
+/// Handles to a [`HistoryImage`]'s two sides for one frame, as returned by
+/// [`Device::ensure_history_image`]. Plain Vulkan handles rather than
+/// borrowed [`Image`]s, since the pool they come from is behind a mutex
+/// that shouldn't stay locked for the rest of the pass.
+#[derive(Clone, Copy, Debug)]
+pub struct HistoryFrame {
+    pub current: vk::Image,
+    pub current_view: vk::ImageView,
+    /// `None` on this resource's first frame — there's nothing to read
+    /// yet, so callers should fall back to a cleared or non-temporal path
+    /// for one frame rather than sampling garbage.
+    pub previous: Option<vk::Image>,
+    pub previous_view: Option<vk::ImageView>,
+}
+
+#[derive(Default)]
+pub(crate) struct HistoryImagePool {
+    entries: HashMap<String, HistoryImage>,
+}
+
+impl Device {
+    /// Ensures a history resource named `key` exists sized/formatted per
+    /// `desc`, creating its pair of images on first use and transparently
+    /// recreating them whenever `desc`'s extent or format no longer
+    /// matches what's there — the swapchain-resize case a pass would
+    /// otherwise have to detect and handle itself. The stale pair, if
+    /// any, is queued for deferred destruction rather than freed
+    /// immediately, in case a still-in-flight frame is reading from it.
+    ///
+    /// Two different calls with the same `key` but different `desc`s
+    /// within the same frame will thrash the pair on every call — `key`
+    /// is meant to be stable per logical resource (e.g. `"taa_history"`),
+    /// not reused across unrelated passes.
+    pub fn ensure_history_image(&self, key: &str, desc: ImageDesc) -> BackendResult<HistoryFrame> {
+        let mut pool = self.history_images.lock().unwrap();
+
+        let needs_recreate = match pool.entries.get(key) {
+            Some(entry) => entry.desc.extent != desc.extent || entry.desc.format != desc.format,
+            None => true,
+        };
+
+        if needs_recreate {
+            let images = [self.create_image(desc.clone())?, self.create_image(desc.clone())?];
+            if let Some(stale) = pool.entries.insert(
+                key.to_string(),
+                HistoryImage {
+                    desc,
+                    images,
+                    active: 0,
+                    primed: false,
+                },
+            ) {
+                stale.queue_drop(self);
+            }
+        }
+
+        let entry = pool.entries.get(key).expect("just inserted or already present");
+        Ok(HistoryFrame {
+            current: entry.current().raw,
+            current_view: entry.current().view,
+            previous: entry.previous().map(|image| image.raw),
+            previous_view: entry.previous().map(|image| image.view),
+        })
+    }
+
+    /// Flips `key`'s [`HistoryImage`] so what was `current` this frame
+    /// becomes `previous` next frame. Call once per frame, per history
+    /// resource, after every pass that reads or writes it this frame has
+    /// been recorded — calling it more than once in the same frame drops
+    /// this frame's write from ever being read back.
+    pub fn advance_history_image(&self, key: &str) {
+        if let Some(entry) = self.history_images.lock().unwrap().entries.get_mut(key) {
+            entry.swap();
+        }
+    }
+
+    /// Queues every history image's pair for deferred destruction and
+    /// forgets them — for shutdown, or a full renderer reset that's about
+    /// to re-register everything from scratch.
+    pub fn clear_history_images(&self) {
+        for (_, entry) in self.history_images.lock().unwrap().entries.drain() {
+            entry.queue_drop(self);
+        }
+    }
+}
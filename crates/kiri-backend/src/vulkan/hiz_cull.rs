@@ -0,0 +1,189 @@
+use std::mem::size_of;
+
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::buffer::{Buffer, BufferDesc};
+use super::device::Device;
+use super::frame_graph::{FrameGraph, PassHandle, ResourceHandle};
+use super::image::{Image, ImageDesc};
+
+/// A depth mip chain built from the previous frame's depth buffer, each
+/// mip holding the max depth of its 2x2 footprint in the mip below (so a
+/// conservative "is anything in this box possibly visible" test can pick
+/// whichever mip roughly matches an object's screen-space footprint
+/// instead of testing against full-resolution depth).
+pub struct HiZPyramid {
+    pub image: Image,
+}
+
+impl Device {
+    /// Allocates a full mip chain down to 1x1 over `extent`. Nothing in
+    /// this crate builds the chain itself (the mip-by-mip max-reduction
+    /// passes): [`TwoPhaseCuller::register`] only records the pyramid as a
+    /// frame-graph dependency, it doesn't populate it.
+    pub fn create_hiz_pyramid(&self, extent: [u32; 2]) -> BackendResult<HiZPyramid> {
+        let mip_levels = mip_count_for_extent(extent);
+
+        let image = self.create_image(
+            ImageDesc::new_2d(vk::Format::R32_SFLOAT, extent)
+                .mip_levels(mip_levels)
+                .usage(
+                    vk::ImageUsageFlags::STORAGE
+                        | vk::ImageUsageFlags::SAMPLED
+                        | vk::ImageUsageFlags::TRANSFER_DST,
+                ),
+        )?;
+
+        Ok(HiZPyramid { image })
+    }
+}
+
+impl HiZPyramid {
+    pub fn queue_drop(&self, device: &Device) {
+        device.queue_drop(self.image.raw);
+        device.queue_drop(self.image.memory);
+        device.queue_drop(self.image.view);
+        self.image.queue_drop_views(device);
+    }
+}
+
+fn mip_count_for_extent(extent: [u32; 2]) -> u32 {
+    32 - extent[0].max(extent[1]).max(1).leading_zeros()
+}
+
+/// GPU-written, CPU-readable counts from one frame's two culling phases,
+/// for a HUD overlay. Layout matches what a culling compute shader would
+/// atomically increment directly in the indirect draw buffer's counter
+/// region — see [`IndirectDrawBuffer`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CullingStats {
+    pub phase1_visible: u32,
+    pub phase1_culled: u32,
+    pub phase2_visible: u32,
+    pub phase2_culled: u32,
+}
+
+/// The indirect draw arguments and per-phase visible counts a culling
+/// compute pass would populate, consumed by `vkCmdDrawIndexedIndirectCount`
+/// so the draw call count itself is GPU-determined rather than read back
+/// to the CPU first.
+///
+/// Backed by host-visible memory rather than device-local, so the HUD
+/// reads [`CullingStats`] straight out of the same mapped allocation the
+/// GPU wrote into, with no staging copy. The caller must have waited
+/// on the frame's completion fence before calling [`IndirectDrawBuffer::read_stats`]
+/// — nothing here tracks that for you.
+pub struct IndirectDrawBuffer {
+    commands: Buffer,
+    stats: Buffer,
+    max_draws: u32,
+}
+
+impl Device {
+    pub fn create_indirect_draw_buffer(&self, max_draws: u32) -> BackendResult<IndirectDrawBuffer> {
+        let commands = self.create_buffer(BufferDesc::new_cpu_to_gpu(
+            max_draws as usize * size_of::<vk::DrawIndexedIndirectCommand>(),
+            vk::BufferUsageFlags::INDIRECT_BUFFER | vk::BufferUsageFlags::STORAGE_BUFFER,
+        ))?;
+
+        let stats = self.create_buffer(BufferDesc::new_cpu_to_gpu(
+            size_of::<CullingStats>(),
+            vk::BufferUsageFlags::STORAGE_BUFFER | vk::BufferUsageFlags::INDIRECT_BUFFER,
+        ))?;
+
+        Ok(IndirectDrawBuffer {
+            commands,
+            stats,
+            max_draws,
+        })
+    }
+}
+
+impl IndirectDrawBuffer {
+    pub fn raw_commands(&self) -> vk::Buffer {
+        self.commands.raw
+    }
+
+    pub fn raw_stats(&self) -> vk::Buffer {
+        self.stats.raw
+    }
+
+    pub fn max_draws(&self) -> u32 {
+        self.max_draws
+    }
+
+    /// Reads back this frame's [`CullingStats`] for the HUD. Only valid
+    /// once the GPU work that wrote them has completed.
+    pub fn read_stats(&self, device: &Device) -> BackendResult<CullingStats> {
+        let mut stats = CullingStats::default();
+        unsafe {
+            let ptr = device.raw().map_memory(
+                self.stats.memory,
+                0,
+                size_of::<CullingStats>() as u64,
+                vk::MemoryMapFlags::empty(),
+            )?;
+            std::ptr::copy_nonoverlapping(ptr as *const CullingStats, &mut stats, 1);
+            device.raw().unmap_memory(self.stats.memory);
+        }
+        Ok(stats)
+    }
+
+    pub fn queue_drop(&self, device: &Device) {
+        device.queue_drop(self.commands.raw);
+        device.queue_drop(self.commands.memory);
+        device.queue_drop(self.stats.raw);
+        device.queue_drop(self.stats.memory);
+    }
+}
+
+/// The two-phase Hi-Z occlusion culling scheme: phase 1 re-draws whatever
+/// was visible last frame (a good conservative guess for this frame too)
+/// and builds this frame's Hi-Z pyramid from the result; phase 2 culls
+/// everything else against that pyramid and draws whatever turns out to
+/// still be visible. Splitting it this way means the expensive "cull the
+/// whole scene" pass runs against up-to-date depth instead of last
+/// frame's, at the cost of one extra small draw phase.
+pub struct TwoPhaseCuller {
+    pub hiz: HiZPyramid,
+    pub draw_buffer: IndirectDrawBuffer,
+}
+
+impl TwoPhaseCuller {
+    pub fn new(hiz: HiZPyramid, draw_buffer: IndirectDrawBuffer) -> Self {
+        Self { hiz, draw_buffer }
+    }
+
+    /// Registers the five-pass sequence against `scene_objects` (the
+    /// [`super::scene_buffer::SceneBuffer`] this frame's culling reads)
+    /// and `depth` (the depth target both render phases write into).
+    /// Returns the final phase's pass handle.
+    pub fn register(
+        &self,
+        graph: &mut FrameGraph,
+        scene_objects: ResourceHandle,
+        depth: ResourceHandle,
+    ) -> PassHandle {
+        let phase1_draws = graph.resource("cull_phase1_draws");
+        let hiz_pyramid = graph.resource("hiz_pyramid");
+        let phase2_draws = graph.resource("cull_phase2_draws");
+
+        graph.pass("cull_phase1_previous_visible", &[scene_objects], &[phase1_draws]);
+        graph.pass("render_phase1_previous_visible", &[phase1_draws], &[depth]);
+        graph.pass("hiz_build", &[depth], &[hiz_pyramid]);
+        graph.pass(
+            "cull_phase2_remaining",
+            &[scene_objects, hiz_pyramid],
+            &[phase2_draws],
+        );
+        graph.pass("render_phase2_newly_visible", &[phase2_draws], &[depth])
+    }
+
+    pub fn queue_drop(&self, device: &Device) {
+        self.hiz.queue_drop(device);
+        self.draw_buffer.queue_drop(device);
+    }
+}
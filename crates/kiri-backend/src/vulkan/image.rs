@@ -1,3 +1,478 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:c6b05c9430c808837ada01d6f6f21eb3425de96e4a86453941ea91cad42de4b1
-size 11656
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use ash::vk;
+use ash::vk::Handle;
+
+use crate::BackendResult;
+
+use super::device::Device;
+use super::resource_registry::ResourceKind;
+
+/// Description of an image to create, built up with the usual chained
+/// setters and handed to [`Device::create_image`].
+///
+/// Mirrors the subset of `vk::ImageCreateInfo` kiri actually varies in
+/// practice; anything else (sample count, sharing mode, ...) is fixed to
+/// the common case and can grow here as a new need shows up.
+#[derive(Clone, Debug)]
+pub struct ImageDesc {
+    pub image_type: vk::ImageType,
+    pub extent: [u32; 3],
+    pub format: vk::Format,
+    pub usage: vk::ImageUsageFlags,
+    pub mip_levels: u32,
+    pub array_elements: u32,
+    /// Set by [`ImageDesc::cube_compatible`] for a baked
+    /// [`kiri_assets::EnvironmentAsset`] cubemap — `array_elements` must be
+    /// a multiple of 6 (the six cube faces, or that many cube-array
+    /// entries), and the default view created by [`Device::create_image`]
+    /// is `CUBE`/`CUBE_ARRAY` instead of `TYPE_2D_ARRAY`.
+    pub cube: bool,
+    /// Other formats a view of this image may be created in besides
+    /// `format` itself (typically `format`'s UNORM/SRGB sibling — see
+    /// [`super::texture_format::mutable_format_sibling`]). Non-empty
+    /// enables `MUTABLE_FORMAT` + `EXTENDED_USAGE` and attaches a
+    /// `vk::ImageFormatListCreateInfo` naming every format the image may
+    /// be viewed as, which drivers exposing the image-format-list
+    /// extension require up front rather than at view-creation time.
+    pub mutable_view_formats: Vec<vk::Format>,
+}
+
+impl ImageDesc {
+    pub fn new_2d(format: vk::Format, extent: [u32; 2]) -> Self {
+        Self {
+            image_type: vk::ImageType::TYPE_2D,
+            extent: [extent[0], extent[1], 1],
+            format,
+            usage: vk::ImageUsageFlags::empty(),
+            mip_levels: 1,
+            array_elements: 1,
+            cube: false,
+            mutable_view_formats: Vec::new(),
+        }
+    }
+
+    /// A `TYPE_3D` (volume) image, e.g. a color-grading LUT or a froxel fog
+    /// medium. `extent`'s third component is the volume's depth; unlike
+    /// `TYPE_2D`, a 3D image can't have array layers, so
+    /// [`ImageDesc::array_elements`] must not be called on the result — the
+    /// Vulkan spec requires `arrayLayers == 1` for `TYPE_3D`.
+    pub fn new_3d(format: vk::Format, extent: [u32; 3]) -> Self {
+        Self {
+            image_type: vk::ImageType::TYPE_3D,
+            extent,
+            format,
+            usage: vk::ImageUsageFlags::empty(),
+            mip_levels: 1,
+            array_elements: 1,
+            cube: false,
+            mutable_view_formats: Vec::new(),
+        }
+    }
+
+    pub fn usage(mut self, usage: vk::ImageUsageFlags) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    pub fn mip_levels(mut self, mip_levels: u32) -> Self {
+        self.mip_levels = mip_levels;
+        self
+    }
+
+    pub fn array_elements(mut self, array_elements: u32) -> Self {
+        self.array_elements = array_elements;
+        self
+    }
+
+    /// Marks this image mutable, additionally viewable as any format in
+    /// `formats` (besides `self.format`).
+    pub fn mutable_view_formats(mut self, formats: Vec<vk::Format>) -> Self {
+        self.mutable_view_formats = formats;
+        self
+    }
+
+    /// Marks this `TYPE_2D` image as a cubemap (or cubemap array) — set
+    /// `array_elements` to 6 times the number of cube entries before
+    /// calling this.
+    pub fn cube_compatible(mut self) -> Self {
+        self.cube = true;
+        self
+    }
+}
+
+/// Description of a view onto an [`Image`], keying [`Image`]'s view cache.
+///
+/// Defaults to a full-resource view in `format` with an identity swizzle;
+/// use [`ImageViewDesc::array_layers`] to view a single layer (or a
+/// sub-range) of a cubemap or texture array, and [`ImageViewDesc::swizzle`]
+/// to remap components (e.g. an `R8` mask sampled through `.rrrr` so every
+/// channel reads the same value).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ImageViewDesc {
+    pub format: vk::Format,
+    pub base_array_layer: u32,
+    pub layer_count: u32,
+    pub components: [vk::ComponentSwizzle; 4],
+}
+
+impl ImageViewDesc {
+    pub fn new(format: vk::Format, array_elements: u32) -> Self {
+        Self {
+            format,
+            base_array_layer: 0,
+            layer_count: array_elements,
+            components: [vk::ComponentSwizzle::IDENTITY; 4],
+        }
+    }
+
+    /// Views layers `[base_array_layer, base_array_layer + layer_count)` of
+    /// the image instead of every layer — e.g. a single face of a cubemap,
+    /// or one entry of a texture array baked by `kiri-asset-pipe`.
+    pub fn array_layers(mut self, base_array_layer: u32, layer_count: u32) -> Self {
+        self.base_array_layer = base_array_layer;
+        self.layer_count = layer_count;
+        self
+    }
+
+    pub fn swizzle(mut self, components: [vk::ComponentSwizzle; 4]) -> Self {
+        self.components = components;
+        self
+    }
+
+    fn component_mapping(&self) -> vk::ComponentMapping {
+        vk::ComponentMapping {
+            r: self.components[0],
+            g: self.components[1],
+            b: self.components[2],
+            a: self.components[3],
+        }
+    }
+}
+
+/// A Vulkan image plus the memory backing it and a default full-resource
+/// view, kept alive together.
+///
+/// Additional views (a single cubemap face, a component-swizzled read,
+/// ...) are created on demand through [`Image::view_for`] and cached by
+/// [`ImageViewDesc`] for the lifetime of the image — call
+/// [`Image::queue_drop_views`] alongside queuing `raw`/`memory`/`view` for
+/// destruction so the cached views aren't leaked.
+pub struct Image {
+    pub raw: vk::Image,
+    pub memory: vk::DeviceMemory,
+    pub view: vk::ImageView,
+    pub desc: ImageDesc,
+    view_cache: Mutex<HashMap<ImageViewDesc, vk::ImageView>>,
+}
+
+impl Device {
+    pub fn create_image(&self, desc: ImageDesc) -> BackendResult<Image> {
+        self.create_image_impl(desc, None)
+    }
+
+    /// Like [`Device::create_image`], but also names the image for
+    /// `VK_EXT_debug_utils` (see [`Device::set_debug_name`]) and records
+    /// it in [`Device::dump_resources`] under `name`, so a leaked image
+    /// shows up there instead of only as a validation message at shutdown.
+    pub fn create_image_named(&self, desc: ImageDesc, name: &str) -> BackendResult<Image> {
+        self.create_image_impl(desc, Some(name))
+    }
+
+    fn create_image_impl(&self, desc: ImageDesc, name: Option<&str>) -> BackendResult<Image> {
+        let mut view_formats: Vec<vk::Format> = std::iter::once(desc.format)
+            .chain(desc.mutable_view_formats.iter().copied())
+            .collect();
+        view_formats.dedup();
+
+        let mut format_list = vk::ImageFormatListCreateInfo::builder().view_formats(&view_formats);
+
+        let mut create_info = vk::ImageCreateInfo::builder()
+            .image_type(desc.image_type)
+            .format(desc.format)
+            .extent(vk::Extent3D {
+                width: desc.extent[0],
+                height: desc.extent[1],
+                depth: desc.extent[2],
+            })
+            .mip_levels(desc.mip_levels)
+            // TYPE_3D images can't have array layers per the Vulkan spec —
+            // their third extent dimension is depth, not layer count.
+            .array_layers(if desc.image_type == vk::ImageType::TYPE_3D {
+                1
+            } else {
+                desc.array_elements
+            })
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .usage(desc.usage)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .initial_layout(vk::ImageLayout::UNDEFINED);
+
+        let mut flags = vk::ImageCreateFlags::empty();
+        if !desc.mutable_view_formats.is_empty() {
+            flags |= vk::ImageCreateFlags::MUTABLE_FORMAT | vk::ImageCreateFlags::EXTENDED_USAGE;
+        }
+        if desc.cube {
+            flags |= vk::ImageCreateFlags::CUBE_COMPATIBLE;
+        }
+        create_info = create_info.flags(flags);
+        if !desc.mutable_view_formats.is_empty() {
+            create_info = create_info.push_next(&mut format_list);
+        }
+
+        let raw = unsafe { self.raw().create_image(&create_info, None)? };
+        let requirements = unsafe { self.raw().get_image_memory_requirements(raw) };
+        let memory = self.allocate_device_local(requirements)?;
+
+        unsafe {
+            self.raw().bind_image_memory(raw, memory, 0)?;
+        }
+
+        let view_type = image_view_type(desc.image_type, desc.array_elements, desc.cube);
+
+        let aspect_mask = aspect_mask_for_format(desc.format);
+
+        let view_create_info = vk::ImageViewCreateInfo::builder()
+            .image(raw)
+            .view_type(view_type)
+            .format(desc.format)
+            .subresource_range(vk::ImageSubresourceRange {
+                aspect_mask,
+                base_mip_level: 0,
+                level_count: desc.mip_levels,
+                base_array_layer: 0,
+                layer_count: desc.array_elements,
+            });
+
+        let view = unsafe { self.raw().create_image_view(&view_create_info, None)? };
+
+        if let Some(name) = name {
+            self.set_debug_name(raw, name);
+            self.resource_registry
+                .register(raw.as_raw(), ResourceKind::Image, name.to_string(), requirements.size);
+        }
+
+        Ok(Image {
+            raw,
+            memory,
+            view,
+            desc,
+            view_cache: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Allocates device-local memory satisfying `requirements`. Shared by
+    /// image and buffer creation. Falls back to memory type 0 when no
+    /// device-local type fits `requirements`.
+    pub(crate) fn allocate_device_local(
+        &self,
+        requirements: vk::MemoryRequirements,
+    ) -> BackendResult<vk::DeviceMemory> {
+        let memory_properties = &self.physical_device.memory_properties;
+
+        let memory_type_index = (0..memory_properties.memory_type_count)
+            .find(|&index| {
+                let suitable = (requirements.memory_type_bits & (1 << index)) != 0;
+                let is_device_local = memory_properties.memory_types[index as usize]
+                    .property_flags
+                    .contains(vk::MemoryPropertyFlags::DEVICE_LOCAL);
+                suitable && is_device_local
+            })
+            .unwrap_or(0);
+
+        let allocate_info = vk::MemoryAllocateInfo::builder()
+            .allocation_size(requirements.size)
+            .memory_type_index(memory_type_index);
+
+        Ok(unsafe { self.raw().allocate_memory(&allocate_info, None)? })
+    }
+}
+
+impl Image {
+    /// Returns a view of this image matching `desc`, creating and caching
+    /// it on first use. `desc.format` must be `self.desc.format` or one of
+    /// `self.desc.mutable_view_formats` — e.g. an albedo texture baked as
+    /// UNORM that needs an SRGB view for correct sampling, without a
+    /// re-bake — and `desc.base_array_layer`/`layer_count` must fall within
+    /// `self.desc.array_elements`, letting a single cubemap face or texture
+    /// array entry be viewed on its own.
+    pub fn view_for(&self, device: &Device, desc: ImageViewDesc) -> BackendResult<vk::ImageView> {
+        if let Some(&view) = self.view_cache.lock().unwrap().get(&desc) {
+            return Ok(view);
+        }
+
+        // A view naming fewer layers than the full cube (e.g. one face via
+        // `ImageViewDesc::array_layers`) can't itself be a `CUBE` view, so
+        // cube-ness only applies once `layer_count` covers a whole cube (or
+        // cube array).
+        let view_type = image_view_type(
+            self.desc.image_type,
+            desc.layer_count,
+            self.desc.cube && desc.layer_count % 6 == 0,
+        );
+
+        let create_info = vk::ImageViewCreateInfo::builder()
+            .image(self.raw)
+            .view_type(view_type)
+            .format(desc.format)
+            .components(desc.component_mapping())
+            .subresource_range(vk::ImageSubresourceRange {
+                aspect_mask: aspect_mask_for_format(desc.format),
+                base_mip_level: 0,
+                level_count: self.desc.mip_levels,
+                base_array_layer: desc.base_array_layer,
+                layer_count: desc.layer_count,
+            });
+
+        let view = unsafe { device.raw().create_image_view(&create_info, None)? };
+        self.view_cache.lock().unwrap().insert(desc, view);
+        Ok(view)
+    }
+
+    /// Queues every cached view from [`Image::view_for`] (but not
+    /// `self.view`, the default full-resource view — queue that alongside
+    /// `raw`/`memory` yourself) for deferred destruction.
+    pub fn queue_drop_views(&self, device: &Device) {
+        for (_, view) in self.view_cache.lock().unwrap().drain() {
+            device.queue_drop(view);
+        }
+    }
+
+    /// Records a copy of tightly-packed texel data starting at
+    /// `buffer_offset` in `buffer` into this image's `mip_level`, covering
+    /// `[offset, offset + extent)` — works for both `TYPE_2D` (pass a depth
+    /// of 1) and `TYPE_3D` volumes, since `vk::BufferImageCopy` already
+    /// treats depth uniformly.
+    ///
+    /// The caller owns staging `buffer` and must have already recorded a
+    /// barrier transitioning this image to `TRANSFER_DST_OPTIMAL` on
+    /// `command_buffer` before this call, and one back to the layout the
+    /// image is sampled/read in afterwards — see [`Device::upload_image`]
+    /// for the common case of one staging buffer covering every mip.
+    pub fn record_copy_from_buffer(
+        &self,
+        device: &Device,
+        command_buffer: vk::CommandBuffer,
+        buffer: vk::Buffer,
+        buffer_offset: u64,
+        mip_level: u32,
+        offset: [u32; 3],
+        extent: [u32; 3],
+    ) {
+        let layer_count = if self.desc.image_type == vk::ImageType::TYPE_3D {
+            1
+        } else {
+            self.desc.array_elements
+        };
+
+        let region = vk::BufferImageCopy::builder()
+            .buffer_offset(buffer_offset)
+            .image_subresource(vk::ImageSubresourceLayers {
+                aspect_mask: aspect_mask_for_format(self.desc.format),
+                mip_level,
+                base_array_layer: 0,
+                layer_count,
+            })
+            .image_offset(vk::Offset3D {
+                x: offset[0] as i32,
+                y: offset[1] as i32,
+                z: offset[2] as i32,
+            })
+            .image_extent(vk::Extent3D {
+                width: extent[0],
+                height: extent[1],
+                depth: extent[2],
+            });
+
+        unsafe {
+            device.raw().cmd_copy_buffer_to_image(
+                command_buffer,
+                buffer,
+                self.raw,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                std::slice::from_ref(&region),
+            );
+        }
+    }
+
+    /// The read-back counterpart to [`Image::record_copy_from_buffer`]:
+    /// records a copy of `[offset, offset + extent)` in this image's
+    /// `mip_level` into `buffer` starting at `buffer_offset`.
+    ///
+    /// The caller owns destination `buffer` (typically host-visible, so it
+    /// can be mapped afterwards) and must have already recorded a barrier
+    /// transitioning this image to `TRANSFER_SRC_OPTIMAL` on
+    /// `command_buffer` before this call, and one back to its prior layout
+    /// afterwards — see [`super::pick_buffer::Device::read_pick_id`] for
+    /// the single-texel readback this exists for.
+    pub fn record_copy_to_buffer(
+        &self,
+        device: &Device,
+        command_buffer: vk::CommandBuffer,
+        buffer: vk::Buffer,
+        buffer_offset: u64,
+        mip_level: u32,
+        offset: [u32; 3],
+        extent: [u32; 3],
+    ) {
+        let layer_count = if self.desc.image_type == vk::ImageType::TYPE_3D {
+            1
+        } else {
+            self.desc.array_elements
+        };
+
+        let region = vk::BufferImageCopy::builder()
+            .buffer_offset(buffer_offset)
+            .image_subresource(vk::ImageSubresourceLayers {
+                aspect_mask: aspect_mask_for_format(self.desc.format),
+                mip_level,
+                base_array_layer: 0,
+                layer_count,
+            })
+            .image_offset(vk::Offset3D {
+                x: offset[0] as i32,
+                y: offset[1] as i32,
+                z: offset[2] as i32,
+            })
+            .image_extent(vk::Extent3D {
+                width: extent[0],
+                height: extent[1],
+                depth: extent[2],
+            });
+
+        unsafe {
+            device.raw().cmd_copy_image_to_buffer(
+                command_buffer,
+                self.raw,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                buffer,
+                std::slice::from_ref(&region),
+            );
+        }
+    }
+}
+
+fn image_view_type(image_type: vk::ImageType, layer_count: u32, cube: bool) -> vk::ImageViewType {
+    match (image_type, layer_count, cube) {
+        (vk::ImageType::TYPE_2D, 6, true) => vk::ImageViewType::CUBE,
+        (vk::ImageType::TYPE_2D, _, true) => vk::ImageViewType::CUBE_ARRAY,
+        (vk::ImageType::TYPE_2D, 1, false) => vk::ImageViewType::TYPE_2D,
+        (vk::ImageType::TYPE_2D, _, false) => vk::ImageViewType::TYPE_2D_ARRAY,
+        (vk::ImageType::TYPE_3D, _, _) => vk::ImageViewType::TYPE_3D,
+        _ => vk::ImageViewType::TYPE_2D,
+    }
+}
+
+pub fn aspect_mask_for_format(format: vk::Format) -> vk::ImageAspectFlags {
+    match format {
+        vk::Format::D16_UNORM | vk::Format::D32_SFLOAT => vk::ImageAspectFlags::DEPTH,
+        vk::Format::D16_UNORM_S8_UINT
+        | vk::Format::D24_UNORM_S8_UINT
+        | vk::Format::D32_SFLOAT_S8_UINT => {
+            vk::ImageAspectFlags::DEPTH | vk::ImageAspectFlags::STENCIL
+        }
+        _ => vk::ImageAspectFlags::COLOR,
+    }
+}
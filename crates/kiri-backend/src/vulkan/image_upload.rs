@@ -0,0 +1,201 @@
+use ash::vk;
+
+use kiri_assets::image::ImageAsset;
+
+use crate::BackendResult;
+
+use super::buffer::BufferDesc;
+use super::device::Device;
+use super::image::{aspect_mask_for_format, Image, ImageDesc};
+use super::texture_format::vk_format_for_asset;
+
+impl Device {
+    /// Records `record` into a fresh transient command buffer, submits it
+    /// to [`Device::queue`], and blocks until it's done — for the
+    /// infrequent, latency-insensitive uploads (asset loading, not
+    /// per-frame work) that don't warrant threading through the frame's
+    /// own command buffer and fence.
+    pub fn immediate_submit(
+        &self,
+        record: impl FnOnce(&Device, vk::CommandBuffer),
+    ) -> BackendResult<()> {
+        let command_pool = unsafe {
+            self.raw().create_command_pool(
+                &vk::CommandPoolCreateInfo::builder()
+                    .queue_family_index(self.queue_family_index)
+                    .flags(vk::CommandPoolCreateFlags::TRANSIENT),
+                None,
+            )?
+        };
+
+        let command_buffer = unsafe {
+            self.raw().allocate_command_buffers(
+                &vk::CommandBufferAllocateInfo::builder()
+                    .command_pool(command_pool)
+                    .level(vk::CommandBufferLevel::PRIMARY)
+                    .command_buffer_count(1),
+            )?[0]
+        };
+
+        let fence = unsafe { self.raw().create_fence(&vk::FenceCreateInfo::builder(), None)? };
+
+        unsafe {
+            self.raw().begin_command_buffer(
+                command_buffer,
+                &vk::CommandBufferBeginInfo::builder()
+                    .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT),
+            )?;
+        }
+
+        record(self, command_buffer);
+
+        let result = unsafe {
+            self.raw().end_command_buffer(command_buffer)?;
+
+            let submit_info =
+                vk::SubmitInfo::builder().command_buffers(std::slice::from_ref(&command_buffer));
+            self.raw()
+                .queue_submit(self.queue, std::slice::from_ref(&submit_info), fence)?;
+            self.raw().wait_for_fences(&[fence], true, u64::MAX)
+        };
+
+        unsafe {
+            self.raw().destroy_fence(fence, None);
+            // Destroying the pool frees the command buffer allocated from it.
+            self.raw().destroy_command_pool(command_pool, None);
+        }
+
+        result.map_err(Into::into)
+    }
+
+    /// Names a Vulkan object for validation-layer messages and GPU capture
+    /// tools (RenderDoc, Nsight). A no-op unless the device was created
+    /// with [`super::instance::InstanceBuilder::enable_validation`], since
+    /// `VK_EXT_debug_utils` is only loaded in that case.
+    pub fn set_debug_name<H: vk::Handle>(&self, handle: H, name: &str) {
+        let Some(debug_utils) = self.physical_device.instance.debug_utils.as_ref() else {
+            return;
+        };
+
+        let name = std::ffi::CString::new(name).unwrap_or_default();
+        let name_info = vk::DebugUtilsObjectNameInfoEXT::builder()
+            .object_type(H::TYPE)
+            .object_handle(handle.as_raw())
+            .object_name(name.as_c_str());
+
+        unsafe {
+            // Naming is best-effort diagnostics — a failure here shouldn't
+            // fail the upload it's annotating.
+            let _ = debug_utils.set_debug_utils_object_name(self.raw().handle(), &name_info);
+        }
+    }
+
+    /// Creates an image from `asset`, stages every mip through one staging
+    /// buffer, and leaves it in `SHADER_READ_ONLY_OPTIMAL` — the unsafe
+    /// create/stage/copy/transition dance every texture-loading call site
+    /// otherwise has to hand-roll itself.
+    ///
+    /// Falls back to a CPU-decompressed copy first via
+    /// [`Device::ensure_supported_format`] when `asset`'s baked format
+    /// isn't sampleable on this device. Named `name` for validation/capture
+    /// tooling — see [`Device::set_debug_name`].
+    pub fn upload_image(&self, asset: &ImageAsset, name: &str) -> BackendResult<Image> {
+        let asset = self.ensure_supported_format(asset);
+        let format = vk_format_for_asset(asset.format);
+        let mip_count = asset.mip_count() as u32;
+
+        let image = self.create_image(
+            ImageDesc::new_2d(format, asset.extent)
+                .mip_levels(mip_count)
+                .usage(vk::ImageUsageFlags::SAMPLED | vk::ImageUsageFlags::TRANSFER_DST),
+        )?;
+        self.set_debug_name(image.raw, name);
+
+        let total_size: usize = asset.mips.iter().map(|mip| mip.len()).sum();
+        let staging = self.create_buffer(BufferDesc::new_cpu_to_gpu(
+            total_size,
+            vk::BufferUsageFlags::TRANSFER_SRC,
+        ))?;
+
+        let mut packed = Vec::with_capacity(total_size);
+        let mut mip_offsets = Vec::with_capacity(asset.mips.len());
+        for mip in &asset.mips {
+            mip_offsets.push(packed.len() as u64);
+            packed.extend_from_slice(mip);
+        }
+        staging.write(self, &packed)?;
+
+        let aspect_mask = aspect_mask_for_format(format);
+        let subresource_range = vk::ImageSubresourceRange {
+            aspect_mask,
+            base_mip_level: 0,
+            level_count: mip_count,
+            base_array_layer: 0,
+            layer_count: 1,
+        };
+
+        self.immediate_submit(|device, command_buffer| {
+            let to_transfer_dst = vk::ImageMemoryBarrier::builder()
+                .old_layout(vk::ImageLayout::UNDEFINED)
+                .new_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+                .src_access_mask(vk::AccessFlags::empty())
+                .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+                .image(image.raw)
+                .subresource_range(subresource_range);
+
+            unsafe {
+                device.raw().cmd_pipeline_barrier(
+                    command_buffer,
+                    vk::PipelineStageFlags::TOP_OF_PIPE,
+                    vk::PipelineStageFlags::TRANSFER,
+                    vk::DependencyFlags::empty(),
+                    &[],
+                    &[],
+                    std::slice::from_ref(&to_transfer_dst),
+                );
+            }
+
+            for (mip_level, mip_offset) in mip_offsets.iter().enumerate() {
+                let mip_extent = [
+                    (asset.extent[0] >> mip_level).max(1),
+                    (asset.extent[1] >> mip_level).max(1),
+                    1,
+                ];
+                image.record_copy_from_buffer(
+                    device,
+                    command_buffer,
+                    staging.raw,
+                    *mip_offset,
+                    mip_level as u32,
+                    [0, 0, 0],
+                    mip_extent,
+                );
+            }
+
+            let to_shader_read = vk::ImageMemoryBarrier::builder()
+                .old_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+                .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+                .dst_access_mask(vk::AccessFlags::SHADER_READ)
+                .image(image.raw)
+                .subresource_range(subresource_range);
+
+            unsafe {
+                device.raw().cmd_pipeline_barrier(
+                    command_buffer,
+                    vk::PipelineStageFlags::TRANSFER,
+                    vk::PipelineStageFlags::FRAGMENT_SHADER,
+                    vk::DependencyFlags::empty(),
+                    &[],
+                    &[],
+                    std::slice::from_ref(&to_shader_read),
+                );
+            }
+        })?;
+
+        self.queue_drop(staging.raw);
+        self.queue_drop(staging.memory);
+
+        Ok(image)
+    }
+}
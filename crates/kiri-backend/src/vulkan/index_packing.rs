@@ -0,0 +1,46 @@
+use ash::vk;
+
+/// Chooses the smallest GPU index type that can represent every index in
+/// `indices` and packs them into tightly-packed little-endian bytes ready
+/// for [`super::geometry_arena::GeometryArena::alloc`] — small meshes
+/// upload as `u16` (half the index-buffer memory and bandwidth of `u32`),
+/// larger ones are promoted to `u32` automatically instead of failing or
+/// silently truncating.
+///
+/// When `primitive_restart` is set, the maximum representable value of the
+/// chosen type is reserved as the restart marker (see
+/// [`primitive_restart_index`]), so a mesh whose largest index would
+/// otherwise land exactly on that value is promoted to the next width
+/// rather than colliding with it.
+pub fn pack_indices(indices: &[u32], primitive_restart: bool) -> (vk::IndexType, Vec<u8>) {
+    let max_index = indices.iter().copied().max().unwrap_or(0);
+    let u16_limit = if primitive_restart {
+        u16::MAX as u32 - 1
+    } else {
+        u16::MAX as u32
+    };
+
+    if max_index <= u16_limit {
+        let bytes = indices
+            .iter()
+            .flat_map(|&index| (index as u16).to_le_bytes())
+            .collect();
+        (vk::IndexType::UINT16, bytes)
+    } else {
+        let bytes = indices.iter().flat_map(|&index| index.to_le_bytes()).collect();
+        (vk::IndexType::UINT32, bytes)
+    }
+}
+
+/// The reserved index value that ends a strip/fan primitive early instead
+/// of connecting to it, when a pipeline's
+/// [`super::pipeline::input_assembly_create_info`] has
+/// `primitive_restart_enable` set — `index_type`'s max representable
+/// value, since the spec reserves exactly that value for this purpose and
+/// no other.
+pub fn primitive_restart_index(index_type: vk::IndexType) -> u32 {
+    match index_type {
+        vk::IndexType::UINT16 => u16::MAX as u32,
+        _ => u32::MAX,
+    }
+}
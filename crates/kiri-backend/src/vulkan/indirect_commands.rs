@@ -0,0 +1,151 @@
+//! Safe wrappers for `vkCmdDispatchIndirect` and
+//! `vkCmdDrawIndexedIndirect`/`vkCmdDrawIndexedIndirectCount` — the
+//! command-buffer-side counterpart of a GPU-driven pipeline, where the
+//! dispatch/draw arguments are written by an earlier compute pass
+//! ([`super::hiz_cull::TwoPhaseCuller`], particle simulation, ...) instead
+//! of being known on the CPU when the command buffer is recorded.
+//!
+//! Every entry point here takes the argument buffer as a whole `&Buffer`
+//! plus a byte offset into it rather than a raw `vk::Buffer`, so the usage
+//! flags and bounds checks below have the size to check against.
+
+use ash::vk;
+
+use super::buffer::Buffer;
+use super::device::Device;
+
+/// Alignment the Vulkan spec requires of the argument-buffer offset passed
+/// to `vkCmdDispatchIndirect` (`VUID-vkCmdDispatchIndirect-offset-02710`)
+/// and every indirect draw call below (`VUID-vkCmdDrawIndexedIndirect-offset-02710`
+/// and friends) — 4 bytes, regardless of how many indirect commands are
+/// packed one after another in the buffer.
+pub const INDIRECT_ARGS_ALIGNMENT: u64 = 4;
+
+/// Rounds `offset` up to [`INDIRECT_ARGS_ALIGNMENT`] — for a caller
+/// packing several draws' or dispatches' worth of indirect args
+/// back-to-back into one buffer and needing each one's start offset to
+/// stay valid.
+pub fn align_indirect_offset(offset: u64) -> u64 {
+    (offset + INDIRECT_ARGS_ALIGNMENT - 1) & !(INDIRECT_ARGS_ALIGNMENT - 1)
+}
+
+impl Device {
+    /// Records `vkCmdDispatchIndirect`, reading a `vk::DispatchIndirectCommand`
+    /// from `args` at `offset`.
+    pub fn cmd_dispatch_indirect(&self, command_buffer: vk::CommandBuffer, args: &Buffer, offset: u64) {
+        debug_assert!(
+            args.desc.usage.contains(vk::BufferUsageFlags::INDIRECT_BUFFER),
+            "indirect args buffer was not created with INDIRECT_BUFFER usage"
+        );
+        debug_assert!(
+            offset % INDIRECT_ARGS_ALIGNMENT == 0,
+            "vkCmdDispatchIndirect requires a {INDIRECT_ARGS_ALIGNMENT}-byte-aligned offset"
+        );
+        debug_assert!(
+            offset + std::mem::size_of::<vk::DispatchIndirectCommand>() as u64 <= args.desc.size as u64,
+            "cmd_dispatch_indirect out of bounds"
+        );
+
+        unsafe {
+            self.raw().cmd_dispatch_indirect(command_buffer, args.raw, offset);
+        }
+    }
+
+    /// Records `vkCmdDrawIndexedIndirect`, reading `draw_count`
+    /// `vk::DrawIndexedIndirectCommand`s from `args`, `stride` bytes apart,
+    /// starting at `offset`.
+    pub fn cmd_draw_indexed_indirect(
+        &self,
+        command_buffer: vk::CommandBuffer,
+        args: &Buffer,
+        offset: u64,
+        draw_count: u32,
+        stride: u32,
+    ) {
+        debug_assert!(
+            args.desc.usage.contains(vk::BufferUsageFlags::INDIRECT_BUFFER),
+            "indirect args buffer was not created with INDIRECT_BUFFER usage"
+        );
+        debug_assert!(
+            offset % INDIRECT_ARGS_ALIGNMENT == 0,
+            "vkCmdDrawIndexedIndirect requires a {INDIRECT_ARGS_ALIGNMENT}-byte-aligned offset"
+        );
+        debug_assert!(
+            indirect_draws_fit(offset, draw_count, stride, args.desc.size as u64),
+            "cmd_draw_indexed_indirect out of bounds"
+        );
+
+        unsafe {
+            self.raw()
+                .cmd_draw_indexed_indirect(command_buffer, args.raw, offset, draw_count, stride);
+        }
+    }
+
+    /// Records `vkCmdDrawIndexedIndirectCount`: like
+    /// [`Device::cmd_draw_indexed_indirect`], but the actual draw count is
+    /// read from `count_buffer` at `count_offset` at execution time
+    /// (capped at `max_draw_count`) instead of fixed when the command
+    /// buffer is recorded — what [`super::hiz_cull::IndirectDrawBuffer`]'s
+    /// GPU-written [`super::hiz_cull::CullingStats`] feeds.
+    pub fn cmd_draw_indexed_indirect_count(
+        &self,
+        command_buffer: vk::CommandBuffer,
+        args: &Buffer,
+        offset: u64,
+        count_buffer: &Buffer,
+        count_offset: u64,
+        max_draw_count: u32,
+        stride: u32,
+    ) {
+        debug_assert!(
+            args.desc.usage.contains(vk::BufferUsageFlags::INDIRECT_BUFFER),
+            "indirect args buffer was not created with INDIRECT_BUFFER usage"
+        );
+        debug_assert!(
+            count_buffer.desc.usage.contains(vk::BufferUsageFlags::INDIRECT_BUFFER),
+            "indirect count buffer was not created with INDIRECT_BUFFER usage"
+        );
+        debug_assert!(
+            offset % INDIRECT_ARGS_ALIGNMENT == 0,
+            "vkCmdDrawIndexedIndirectCount requires a {INDIRECT_ARGS_ALIGNMENT}-byte-aligned args offset"
+        );
+        debug_assert!(
+            count_offset % INDIRECT_ARGS_ALIGNMENT == 0,
+            "vkCmdDrawIndexedIndirectCount requires a {INDIRECT_ARGS_ALIGNMENT}-byte-aligned count offset"
+        );
+        debug_assert!(
+            indirect_draws_fit(offset, max_draw_count, stride, args.desc.size as u64),
+            "cmd_draw_indexed_indirect_count args out of bounds"
+        );
+        debug_assert!(
+            count_offset + std::mem::size_of::<u32>() as u64 <= count_buffer.desc.size as u64,
+            "cmd_draw_indexed_indirect_count count offset out of bounds"
+        );
+
+        unsafe {
+            self.raw().cmd_draw_indexed_indirect_count(
+                command_buffer,
+                args.raw,
+                offset,
+                count_buffer.raw,
+                count_offset,
+                max_draw_count,
+                stride,
+            );
+        }
+    }
+}
+
+/// Whether `draw_count` `vk::DrawIndexedIndirectCommand`s, `stride` bytes
+/// apart starting at `offset`, fit within a buffer of `buffer_size` bytes.
+/// `stride == 0` means the commands are tightly packed, per the Vulkan
+/// spec's meaning of a zero stride for these calls.
+fn indirect_draws_fit(offset: u64, draw_count: u32, stride: u32, buffer_size: u64) -> bool {
+    if draw_count == 0 {
+        return true;
+    }
+    let command_size = std::mem::size_of::<vk::DrawIndexedIndirectCommand>() as u64;
+    let effective_stride = if stride == 0 { command_size } else { stride as u64 };
+    let span = effective_stride * (draw_count as u64 - 1) + command_size;
+    offset + span <= buffer_size
+}
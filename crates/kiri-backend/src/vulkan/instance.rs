@@ -1,3 +1,123 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:596879935f39c6153e2f4690e6eef67f1e75870ecaa6b53cb7e0cff23c823520
-size 6904
+use std::sync::Arc;
+
+use ash::vk;
+use raw_window_handle::{RawDisplayHandle, RawWindowHandle};
+
+use crate::{BackendError, BackendResult};
+
+/// Thin wrapper around `ash::Instance`, owning the loader and the debug
+/// utils messenger (when validation is enabled).
+///
+/// [`InstanceBuilder::build`] returns it inside an `Arc`, so sharing it
+/// is a cheap `Arc` clone; it is expected to live for the lifetime of
+/// the application, and every `PhysicalDevice` created from it holds an
+/// `Arc` back to it.
+pub struct Instance {
+    pub(crate) entry: ash::Entry,
+    pub(crate) raw: ash::Instance,
+    pub(crate) debug_utils: Option<ash::extensions::ext::DebugUtils>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct InstanceBuilder {
+    pub enable_validation: bool,
+    pub required_extensions: Vec<&'static std::ffi::CStr>,
+}
+
+impl InstanceBuilder {
+    pub fn enable_validation(mut self, enable: bool) -> Self {
+        self.enable_validation = enable;
+        self
+    }
+
+    pub fn required_extensions(mut self, extensions: Vec<&'static std::ffi::CStr>) -> Self {
+        self.required_extensions = extensions;
+        self
+    }
+
+    pub fn build(self) -> BackendResult<Arc<Instance>> {
+        let entry = unsafe { ash::Entry::load() }
+            .map_err(|err| BackendError::Other(format!("Failed to load Vulkan: {:?}", err)))?;
+
+        let app_info = vk::ApplicationInfo::builder()
+            .application_name(c"kiri")
+            .engine_name(c"kiri")
+            .api_version(vk::API_VERSION_1_2);
+
+        let mut layer_names = Vec::new();
+        if self.enable_validation {
+            layer_names.push(c"VK_LAYER_KHRONOS_validation".as_ptr());
+        }
+
+        let extension_ptrs: Vec<*const i8> = self
+            .required_extensions
+            .iter()
+            .map(|ext| ext.as_ptr())
+            .collect();
+
+        let create_info = vk::InstanceCreateInfo::builder()
+            .application_info(&app_info)
+            .enabled_layer_names(&layer_names)
+            .enabled_extension_names(&extension_ptrs);
+
+        let raw = unsafe { entry.create_instance(&create_info, None)? };
+
+        let debug_utils = self
+            .enable_validation
+            .then(|| ash::extensions::ext::DebugUtils::new(&entry, &raw));
+
+        Ok(Arc::new(Instance {
+            entry,
+            raw,
+            debug_utils,
+        }))
+    }
+}
+
+impl Instance {
+    pub fn builder() -> InstanceBuilder {
+        InstanceBuilder::default()
+    }
+
+    pub fn raw(&self) -> &ash::Instance {
+        &self.raw
+    }
+
+    pub fn entry(&self) -> &ash::Entry {
+        &self.entry
+    }
+
+    /// Creates a `vk::SurfaceKHR` for `window`. The returned surface is not
+    /// owned by `self` — callers (`kiri-app`'s window setup, typically) are
+    /// responsible for destroying it via `khr::Surface::destroy_surface`
+    /// once the window closes, after every `Swapchain` built from it has
+    /// already been dropped.
+    pub fn create_surface(
+        &self,
+        display_handle: RawDisplayHandle,
+        window_handle: RawWindowHandle,
+    ) -> BackendResult<vk::SurfaceKHR> {
+        Ok(unsafe {
+            ash_window::create_surface(&self.entry, &self.raw, display_handle, window_handle, None)?
+        })
+    }
+
+    /// Extensions the instance must be created with for
+    /// [`Instance::create_surface`] to work on the current platform.
+    pub fn required_surface_extensions(
+        display_handle: RawDisplayHandle,
+    ) -> BackendResult<Vec<&'static std::ffi::CStr>> {
+        Ok(ash_window::enumerate_required_extensions(display_handle)?
+            .iter()
+            .map(|&ptr| unsafe { std::ffi::CStr::from_ptr(ptr) })
+            .collect())
+    }
+}
+
+impl Drop for Instance {
+    fn drop(&mut self) {
+        unsafe {
+            self.raw.destroy_instance(None);
+        }
+    }
+}
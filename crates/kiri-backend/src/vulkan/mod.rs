@@ -1,3 +1,104 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:20560c56ccd73c4a5f4f8919edaa13870da47c19a3ee92dc8ce3707a5f4a3fec
-size 1391
+mod bar_upload;
+mod buffer;
+mod buffer_commands;
+mod color_grading;
+mod compute_kernels;
+mod descriptor_pool;
+mod device;
+mod device_group;
+mod draw_list;
+mod drop_list;
+mod dynamic_state;
+mod external_memory;
+mod format_policy;
+mod frame;
+mod frame_graph;
+mod frame_hooks;
+mod froxel_fog;
+mod gbuffer;
+mod geometry_arena;
+mod history_image;
+mod hiz_cull;
+mod image;
+mod image_upload;
+mod index_packing;
+mod indirect_commands;
+mod instance;
+mod offscreen;
+mod physical_device;
+mod pick_buffer;
+mod pipeline;
+mod postfx;
+mod readback_ring;
+mod reflection_probe;
+mod render_cvars;
+mod render_pass_cache;
+mod render_world;
+mod resource_destroyer;
+mod resource_registry;
+mod scene_buffer;
+mod skinning;
+mod sparse_image;
+mod split_screen;
+mod surface_transform;
+mod swapchain;
+mod sync_pool;
+mod texture_fallback;
+mod texture_format;
+mod texture_streaming;
+mod transient;
+mod uniforms;
+mod vertex_format;
+mod xr_hooks;
+
+pub use bar_upload::*;
+pub use buffer::*;
+pub use buffer_commands::*;
+pub use color_grading::*;
+pub use compute_kernels::*;
+pub use descriptor_pool::*;
+pub use device::*;
+pub use device_group::*;
+pub use draw_list::*;
+pub use drop_list::*;
+pub use dynamic_state::*;
+pub use external_memory::*;
+pub use format_policy::*;
+pub use frame::*;
+pub use frame_graph::*;
+pub use froxel_fog::*;
+pub use gbuffer::*;
+pub use geometry_arena::*;
+pub use history_image::*;
+pub use hiz_cull::*;
+pub use image::*;
+pub use image_upload::*;
+pub use index_packing::*;
+pub use indirect_commands::*;
+pub use instance::*;
+pub use offscreen::*;
+pub use physical_device::*;
+pub use pick_buffer::*;
+pub use pipeline::*;
+pub use postfx::*;
+pub use readback_ring::*;
+pub use reflection_probe::*;
+pub use render_cvars::*;
+pub use render_pass_cache::*;
+pub use render_world::*;
+pub use resource_destroyer::*;
+pub use resource_registry::*;
+pub use scene_buffer::*;
+pub use skinning::*;
+pub use sparse_image::*;
+pub use split_screen::*;
+pub use surface_transform::*;
+pub use swapchain::*;
+pub use sync_pool::*;
+pub use texture_fallback::*;
+pub use texture_format::*;
+pub use texture_streaming::*;
+pub use transient::*;
+pub use uniforms::*;
+pub use vertex_format::*;
+pub use xr_hooks::*;
@@ -0,0 +1,79 @@
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::buffer::{Buffer, BufferDesc, MemoryLocation};
+use super::device::Device;
+use super::image::{Image, ImageDesc};
+
+/// A render target and matching host-visible readback buffer that does not
+/// depend on a `Swapchain` or a presentable surface at all.
+///
+/// Useful for video export / offscreen recording: the render loop writes
+/// into [`OffscreenTarget::image`] exactly like it would a swapchain image,
+/// then [`OffscreenTarget::record_copy_to_readback`] pulls the frame into
+/// CPU-visible memory for an encoder to consume, with no `vkAcquireNextImage`
+/// / `vkQueuePresentKHR` anywhere in the loop.
+pub struct OffscreenTarget {
+    pub image: Image,
+    pub readback: Buffer,
+    pub extent: [u32; 2],
+}
+
+impl Device {
+    pub fn create_offscreen_target(
+        &self,
+        extent: [u32; 2],
+        format: vk::Format,
+    ) -> BackendResult<OffscreenTarget> {
+        let image = self.create_image(
+            ImageDesc::new_2d(format, extent)
+                .usage(vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSFER_SRC),
+        )?;
+
+        let bytes_per_pixel = 4; // kiri only records 8-bit-per-channel formats today
+        let readback_size = extent[0] as usize * extent[1] as usize * bytes_per_pixel;
+
+        let readback = self.create_buffer(BufferDesc {
+            size: readback_size,
+            usage: vk::BufferUsageFlags::TRANSFER_DST,
+            memory_location: MemoryLocation::HostVisible,
+        })?;
+
+        Ok(OffscreenTarget {
+            image,
+            readback,
+            extent,
+        })
+    }
+}
+
+impl OffscreenTarget {
+    /// Records a copy of the full target image into the readback buffer.
+    /// Callers are responsible for submitting `cb` and waiting for it
+    /// before reading `readback` on the host; this only builds the command.
+    pub fn record_copy_to_readback(&self, device: &Device, cb: vk::CommandBuffer) {
+        let region = vk::BufferImageCopy::builder()
+            .image_subresource(vk::ImageSubresourceLayers {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                mip_level: 0,
+                base_array_layer: 0,
+                layer_count: 1,
+            })
+            .image_extent(vk::Extent3D {
+                width: self.extent[0],
+                height: self.extent[1],
+                depth: 1,
+            });
+
+        unsafe {
+            device.raw().cmd_copy_image_to_buffer(
+                cb,
+                self.image.raw,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                self.readback.raw,
+                std::slice::from_ref(&region),
+            );
+        }
+    }
+}
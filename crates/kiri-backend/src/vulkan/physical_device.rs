@@ -1,3 +1,136 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:7ae380af51493c38b10c94b33777a5471d9598de7aff79bef998b69d356c674d
-size 7073
+use std::sync::Arc;
+
+use ash::vk;
+
+use crate::{BackendError, BackendResult};
+
+use super::instance::Instance;
+
+/// A Vulkan physical device, together with the queue family and memory
+/// property data queried up-front so the rest of the backend doesn't need
+/// to round-trip through the loader every time it wants to answer "which
+/// queue family should I use" or "which memory type is device-local".
+#[derive(Clone)]
+pub struct PhysicalDevice {
+    pub instance: Arc<Instance>,
+    pub raw: vk::PhysicalDevice,
+    pub properties: vk::PhysicalDeviceProperties,
+    pub memory_properties: vk::PhysicalDeviceMemoryProperties,
+    pub queue_family_properties: Vec<vk::QueueFamilyProperties>,
+}
+
+impl PhysicalDevice {
+    pub fn name(&self) -> String {
+        let raw_name = &self.properties.device_name;
+        let name = unsafe { std::ffi::CStr::from_ptr(raw_name.as_ptr()) };
+        name.to_string_lossy().into_owned()
+    }
+
+    pub fn device_type(&self) -> vk::PhysicalDeviceType {
+        self.properties.device_type
+    }
+
+    /// Index of the first queue family that supports graphics, compute and
+    /// transfer operations. Every device kiri targets is expected to expose
+    /// at least one such family.
+    pub fn graphics_queue_family_index(&self) -> Option<u32> {
+        self.queue_family_properties
+            .iter()
+            .position(|props| {
+                props
+                    .queue_flags
+                    .contains(vk::QueueFlags::GRAPHICS | vk::QueueFlags::COMPUTE)
+            })
+            .map(|index| index as u32)
+    }
+
+    /// Index of a queue family that supports compute but *not* graphics —
+    /// a "dedicated" async compute family, on hardware that exposes one.
+    /// Callers wanting compute work concurrent with graphics should prefer
+    /// this over sharing the graphics family's queue.
+    pub fn dedicated_compute_queue_family_index(&self) -> Option<u32> {
+        self.queue_family_properties
+            .iter()
+            .position(|props| {
+                props.queue_flags.contains(vk::QueueFlags::COMPUTE)
+                    && !props.queue_flags.contains(vk::QueueFlags::GRAPHICS)
+            })
+            .map(|index| index as u32)
+    }
+
+    /// Index of a queue family that only supports transfer — a DMA-only
+    /// copy engine, on hardware that exposes one. Useful for uploads that
+    /// shouldn't contend with the graphics/compute queues at all.
+    pub fn dedicated_transfer_queue_family_index(&self) -> Option<u32> {
+        self.queue_family_properties
+            .iter()
+            .position(|props| {
+                props.queue_flags.contains(vk::QueueFlags::TRANSFER)
+                    && !props
+                        .queue_flags
+                        .intersects(vk::QueueFlags::GRAPHICS | vk::QueueFlags::COMPUTE)
+            })
+            .map(|index| index as u32)
+    }
+
+    /// Whether `family_index` supports every flag in `required`.
+    pub fn queue_family_supports(&self, family_index: u32, required: vk::QueueFlags) -> bool {
+        self.queue_family_properties
+            .get(family_index as usize)
+            .is_some_and(|props| props.queue_flags.contains(required))
+    }
+}
+
+/// Enumerates every physical device visible to `instance`, in driver-reported
+/// order, with queue family and memory properties pre-fetched.
+///
+/// This is intentionally separate from "pick me the best device" selection
+/// logic: tools that need to target a specific GPU (e.g. baking on an
+/// integrated GPU while a discrete one renders) enumerate first, inspect
+/// `name()` / `device_type()`, and then construct a `Device` for whichever
+/// entries they want.
+pub fn enumerate_physical_devices(instance: &Arc<Instance>) -> BackendResult<Vec<PhysicalDevice>> {
+    let raw_devices = unsafe { instance.raw().enumerate_physical_devices()? };
+
+    if raw_devices.is_empty() {
+        return Err(BackendError::NoSuitablePhysicalDevice);
+    }
+
+    Ok(raw_devices
+        .into_iter()
+        .map(|raw| {
+            let properties = unsafe { instance.raw().get_physical_device_properties(raw) };
+            let memory_properties =
+                unsafe { instance.raw().get_physical_device_memory_properties(raw) };
+            let queue_family_properties =
+                unsafe { instance.raw().get_physical_device_queue_family_properties(raw) };
+
+            PhysicalDevice {
+                instance: instance.clone(),
+                raw,
+                properties,
+                memory_properties,
+                queue_family_properties,
+            }
+        })
+        .collect())
+}
+
+/// Picks the highest-priority discrete GPU, falling back to whatever is
+/// available. Kept around for callers that just want "the one good device"
+/// without enumerating themselves.
+pub fn select_default_physical_device(
+    instance: &Arc<Instance>,
+) -> BackendResult<PhysicalDevice> {
+    let mut devices = enumerate_physical_devices(instance)?;
+
+    devices.sort_by_key(|device| match device.device_type() {
+        vk::PhysicalDeviceType::DISCRETE_GPU => 0,
+        vk::PhysicalDeviceType::INTEGRATED_GPU => 1,
+        vk::PhysicalDeviceType::VIRTUAL_GPU => 2,
+        vk::PhysicalDeviceType::CPU => 3,
+        _ => 4,
+    });
+
+    devices.into_iter().next().ok_or(BackendError::NoSuitablePhysicalDevice)
+}
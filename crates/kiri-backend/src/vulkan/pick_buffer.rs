@@ -0,0 +1,122 @@
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::buffer::BufferDesc;
+use super::device::Device;
+use super::image::{Image, ImageDesc};
+
+/// A single-channel object-ID render target: a scene render pass draws
+/// each [`super::render_world::ProxyHandle`] into this instead of (or
+/// alongside) color, so an editor can resolve exactly what's under the
+/// cursor by reading back one texel instead of walking every proxy's
+/// geometry on the CPU. Populating it (an ID-writing shader variant bound
+/// per draw) is a render-pass concern this crate has no draw-submission
+/// code for yet — this only allocates the target and provides the
+/// texel readback, the same partial-infrastructure split
+/// [`super::hiz_cull::HiZPyramid`] takes with its own mip-chain build.
+pub struct PickIdBuffer {
+    pub image: Image,
+}
+
+impl Device {
+    pub fn create_pick_id_buffer(&self, extent: [u32; 2]) -> BackendResult<PickIdBuffer> {
+        let image = self.create_image(
+            ImageDesc::new_2d(vk::Format::R32_UINT, extent).usage(
+                vk::ImageUsageFlags::COLOR_ATTACHMENT
+                    | vk::ImageUsageFlags::TRANSFER_SRC
+                    | vk::ImageUsageFlags::TRANSFER_DST,
+            ),
+        )?;
+
+        Ok(PickIdBuffer { image })
+    }
+
+    /// Reads back the object ID at `pixel` from `pick_buffer`, which must
+    /// already have had a scene's ID pass rendered into it this frame and
+    /// currently sit in `COLOR_ATTACHMENT_OPTIMAL`. Round-trips through a
+    /// tiny host-visible staging buffer via [`Device::immediate_submit`],
+    /// so this is editor-tooling latency (a GPU stall waiting on the
+    /// fence), not something to call every frame for every pixel.
+    pub fn read_pick_id(&self, pick_buffer: &PickIdBuffer, pixel: [u32; 2]) -> BackendResult<u32> {
+        let staging = self.create_buffer(BufferDesc::new_cpu_to_gpu(
+            std::mem::size_of::<u32>(),
+            vk::BufferUsageFlags::TRANSFER_DST,
+        ))?;
+
+        let subresource_range = vk::ImageSubresourceRange {
+            aspect_mask: vk::ImageAspectFlags::COLOR,
+            base_mip_level: 0,
+            level_count: 1,
+            base_array_layer: 0,
+            layer_count: 1,
+        };
+
+        self.immediate_submit(|device, command_buffer| {
+            let to_transfer_src = vk::ImageMemoryBarrier::builder()
+                .old_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                .new_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL)
+                .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .dst_access_mask(vk::AccessFlags::TRANSFER_READ)
+                .image(pick_buffer.image.raw)
+                .subresource_range(subresource_range);
+
+            unsafe {
+                device.raw().cmd_pipeline_barrier(
+                    command_buffer,
+                    vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+                    vk::PipelineStageFlags::TRANSFER,
+                    vk::DependencyFlags::empty(),
+                    &[],
+                    &[],
+                    std::slice::from_ref(&to_transfer_src),
+                );
+            }
+
+            pick_buffer.image.record_copy_to_buffer(
+                device,
+                command_buffer,
+                staging.raw,
+                0,
+                0,
+                [pixel[0], pixel[1], 0],
+                [1, 1, 1],
+            );
+
+            let to_color_attachment = vk::ImageMemoryBarrier::builder()
+                .old_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL)
+                .new_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                .src_access_mask(vk::AccessFlags::TRANSFER_READ)
+                .dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .image(pick_buffer.image.raw)
+                .subresource_range(subresource_range);
+
+            unsafe {
+                device.raw().cmd_pipeline_barrier(
+                    command_buffer,
+                    vk::PipelineStageFlags::TRANSFER,
+                    vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+                    vk::DependencyFlags::empty(),
+                    &[],
+                    &[],
+                    std::slice::from_ref(&to_color_attachment),
+                );
+            }
+        })?;
+
+        let bytes = staging.read_at(self, 0, std::mem::size_of::<u32>() as u64)?;
+        self.queue_drop(staging.raw);
+        self.queue_drop(staging.memory);
+
+        Ok(u32::from_ne_bytes(bytes.try_into().unwrap_or_default()))
+    }
+}
+
+impl PickIdBuffer {
+    pub fn queue_drop(&self, device: &Device) {
+        device.queue_drop(self.image.raw);
+        device.queue_drop(self.image.memory);
+        device.queue_drop(self.image.view);
+        self.image.queue_drop_views(device);
+    }
+}
@@ -0,0 +1,86 @@
+use ash::vk;
+
+use kiri_assets::effect::{
+    DepthCompareOp, DepthStencilState, InputAssemblyState, PrimitiveTopology, StencilOp,
+    StencilState,
+};
+
+/// Builds the `vk::PipelineDepthStencilStateCreateInfo` for a baked
+/// [`DepthStencilState`]. Kept as a free function rather than a method on
+/// the asset type since `kiri-assets` has no Vulkan dependency.
+pub fn depth_stencil_create_info(
+    state: &DepthStencilState,
+) -> vk::PipelineDepthStencilStateCreateInfo {
+    let stencil_state = state
+        .stencil
+        .map(to_vk_stencil_op_state)
+        .unwrap_or_default();
+
+    vk::PipelineDepthStencilStateCreateInfo::builder()
+        .depth_test_enable(state.depth_test_enable)
+        .depth_write_enable(state.depth_write_enable)
+        .depth_compare_op(to_vk_compare_op(state.compare_op))
+        .stencil_test_enable(state.stencil.is_some())
+        .front(stencil_state)
+        .back(stencil_state)
+        .build()
+}
+
+/// Builds the `vk::PipelineInputAssemblyStateCreateInfo` for a baked
+/// [`InputAssemblyState`].
+pub fn input_assembly_create_info(
+    state: &InputAssemblyState,
+) -> vk::PipelineInputAssemblyStateCreateInfo {
+    vk::PipelineInputAssemblyStateCreateInfo::builder()
+        .topology(to_vk_primitive_topology(state.topology))
+        .primitive_restart_enable(state.primitive_restart_enable)
+        .build()
+}
+
+fn to_vk_primitive_topology(topology: PrimitiveTopology) -> vk::PrimitiveTopology {
+    match topology {
+        PrimitiveTopology::TriangleList => vk::PrimitiveTopology::TRIANGLE_LIST,
+        PrimitiveTopology::TriangleStrip => vk::PrimitiveTopology::TRIANGLE_STRIP,
+        PrimitiveTopology::LineList => vk::PrimitiveTopology::LINE_LIST,
+        PrimitiveTopology::LineStrip => vk::PrimitiveTopology::LINE_STRIP,
+        PrimitiveTopology::PointList => vk::PrimitiveTopology::POINT_LIST,
+    }
+}
+
+fn to_vk_stencil_op_state(state: StencilState) -> vk::StencilOpState {
+    vk::StencilOpState {
+        fail_op: to_vk_stencil_op(state.fail_op),
+        pass_op: to_vk_stencil_op(state.pass_op),
+        depth_fail_op: to_vk_stencil_op(state.depth_fail_op),
+        compare_op: to_vk_compare_op(state.compare_op),
+        compare_mask: state.compare_mask,
+        write_mask: state.write_mask,
+        reference: state.reference,
+    }
+}
+
+fn to_vk_compare_op(op: DepthCompareOp) -> vk::CompareOp {
+    match op {
+        DepthCompareOp::Never => vk::CompareOp::NEVER,
+        DepthCompareOp::Less => vk::CompareOp::LESS,
+        DepthCompareOp::Equal => vk::CompareOp::EQUAL,
+        DepthCompareOp::LessOrEqual => vk::CompareOp::LESS_OR_EQUAL,
+        DepthCompareOp::Greater => vk::CompareOp::GREATER,
+        DepthCompareOp::NotEqual => vk::CompareOp::NOT_EQUAL,
+        DepthCompareOp::GreaterOrEqual => vk::CompareOp::GREATER_OR_EQUAL,
+        DepthCompareOp::Always => vk::CompareOp::ALWAYS,
+    }
+}
+
+fn to_vk_stencil_op(op: StencilOp) -> vk::StencilOp {
+    match op {
+        StencilOp::Keep => vk::StencilOp::KEEP,
+        StencilOp::Zero => vk::StencilOp::ZERO,
+        StencilOp::Replace => vk::StencilOp::REPLACE,
+        StencilOp::IncrementClamp => vk::StencilOp::INCREMENT_AND_CLAMP,
+        StencilOp::DecrementClamp => vk::StencilOp::DECREMENT_AND_CLAMP,
+        StencilOp::Invert => vk::StencilOp::INVERT,
+        StencilOp::IncrementWrap => vk::StencilOp::INCREMENT_AND_WRAP,
+        StencilOp::DecrementWrap => vk::StencilOp::DECREMENT_AND_WRAP,
+    }
+}
@@ -0,0 +1,135 @@
+use super::frame_graph::{FrameGraph, PassHandle, ResourceHandle};
+
+/// A quality/cost tradeoff shared by every post-process node in this
+/// module — higher tiers spend more samples for less noise/banding.
+/// Kept as one enum rather than a per-pass sample count so a HUD-driven
+/// "low/medium/high" quality setting can plumb straight through without
+/// each pass needing its own mapping.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PostFxQuality {
+    Off,
+    #[default]
+    Low,
+    Medium,
+    High,
+}
+
+impl PostFxQuality {
+    /// Gather-kernel tap count for [`DepthOfFieldPass`]'s scatter-as-gather
+    /// blur. `Off` returns 0 so callers can skip recording the pass
+    /// entirely rather than running a degenerate 0-tap blur.
+    pub fn dof_gather_taps(self) -> u32 {
+        match self {
+            PostFxQuality::Off => 0,
+            PostFxQuality::Low => 8,
+            PostFxQuality::Medium => 16,
+            PostFxQuality::High => 32,
+        }
+    }
+
+    /// Sample count along the per-pixel velocity vector for
+    /// [`MotionBlurPass`]. Same `Off` convention as
+    /// [`PostFxQuality::dof_gather_taps`].
+    pub fn motion_blur_samples(self) -> u32 {
+        match self {
+            PostFxQuality::Off => 0,
+            PostFxQuality::Low => 4,
+            PostFxQuality::Medium => 8,
+            PostFxQuality::High => 16,
+        }
+    }
+}
+
+/// Depth of field parameters derived from the camera's physical lens
+/// model, matching how a real lens computes circle of confusion rather
+/// than an artist-tuned near/far blur distance — plugging in a camera's
+/// focal length and f-stop gives believable DoF without hand-tuning per
+/// shot.
+#[derive(Clone, Copy, Debug)]
+pub struct DepthOfFieldDesc {
+    pub focus_distance: f32,
+    pub focal_length_mm: f32,
+    pub f_stop: f32,
+    pub quality: PostFxQuality,
+}
+
+impl DepthOfFieldDesc {
+    /// Circle-of-confusion diameter (in the same units as `focus_distance`)
+    /// a point at `scene_depth` blurs to, via the standard thin-lens
+    /// approximation. Zero at `focus_distance` itself, growing in both
+    /// directions — DoF blur, unlike motion blur, isn't directional.
+    pub fn circle_of_confusion(&self, scene_depth: f32) -> f32 {
+        if scene_depth <= 0.0 {
+            return 0.0;
+        }
+
+        let focal_length_m = self.focal_length_mm / 1000.0;
+        let aperture_diameter = focal_length_m / self.f_stop;
+
+        (aperture_diameter * focal_length_m * (self.focus_distance - scene_depth).abs())
+            / (scene_depth * (self.focus_distance - focal_length_m).max(f32::EPSILON))
+    }
+}
+
+/// A scatter-as-gather depth of field pass: rather than scattering each
+/// pixel's contribution outward (the "scatter" half of the name, expensive
+/// to do without overdraw artifacts), each output pixel gathers from a
+/// disc of neighbors sized by its own CoC — cheaper and easier to bound the
+/// cost of on a fixed tap budget from [`PostFxQuality::dof_gather_taps`].
+pub struct DepthOfFieldPass {
+    pub desc: DepthOfFieldDesc,
+}
+
+impl DepthOfFieldPass {
+    pub fn new(desc: DepthOfFieldDesc) -> Self {
+        Self { desc }
+    }
+
+    /// Registers the pass reading `scene_color`/`depth` and writing back
+    /// to `scene_color`. Returns `None` without touching the graph at
+    /// `PostFxQuality::Off`, so a disabled pass costs nothing — not even a
+    /// no-op node — in the graph dump used for pass-order debugging.
+    pub fn register(
+        &self,
+        graph: &mut FrameGraph,
+        scene_color: ResourceHandle,
+        depth: ResourceHandle,
+    ) -> Option<PassHandle> {
+        if self.desc.quality == PostFxQuality::Off {
+            return None;
+        }
+
+        Some(graph.pass("depth_of_field", &[scene_color, depth], &[scene_color]))
+    }
+}
+
+/// Camera + per-object motion blur, sampling along the TAA velocity
+/// buffer's per-pixel vector (see `skinning.rs`'s previous/current vertex
+/// buffer swap, which is what populates it for skinned meshes) rather than
+/// reprojecting from a single camera-only velocity — this is what gives
+/// correct blur on a moving character in an otherwise static shot.
+pub struct MotionBlurPass {
+    pub quality: PostFxQuality,
+}
+
+impl MotionBlurPass {
+    pub fn new(quality: PostFxQuality) -> Self {
+        Self { quality }
+    }
+
+    /// Registers the pass reading `scene_color`/`velocity` and writing
+    /// back to `scene_color`. `None` at `PostFxQuality::Off`, same
+    /// convention as [`DepthOfFieldPass::register`].
+    pub fn register(
+        &self,
+        graph: &mut FrameGraph,
+        scene_color: ResourceHandle,
+        velocity: ResourceHandle,
+    ) -> Option<PassHandle> {
+        if self.quality == PostFxQuality::Off {
+            return None;
+        }
+
+        Some(graph.pass("motion_blur", &[scene_color, velocity], &[scene_color]))
+    }
+}
@@ -0,0 +1,103 @@
+//! Host-visible readback ring: a fixed set of small staging buffers a
+//! GPU pass copies into this frame, read back a fixed number of frames
+//! later once the GPU is guaranteed to be done writing them — auto-exposure
+//! histograms, GPU picking results, occlusion query stats, streaming
+//! feedback, ... anything that wants "what did the GPU write N frames
+//! ago" without the [`Device::immediate_submit`]-style fence wait
+//! [`super::pick_buffer::Device::read_pick_id`] pays for the same thing
+//! today.
+//!
+//! Unlike [`super::uniforms::PersistentUniformRing`], which guards
+//! against this frame's write stomping a still-in-flight previous
+//! frame's read, this ring's whole point is reading back what the GPU
+//! already finished writing — callers trade a frame or two of latency
+//! for never stalling on a fence.
+
+use std::marker::PhantomData;
+
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::buffer::{Buffer, BufferDesc};
+use super::device::Device;
+
+/// A typed ring of host-visible staging buffers. `T` is whatever POD
+/// value the GPU writes into a slot — a histogram, a pick ID, a stats
+/// struct.
+pub struct ReadbackRing<T> {
+    slots: Vec<Buffer>,
+    latency_frames: usize,
+    _marker: PhantomData<T>,
+}
+
+impl Device {
+    /// Creates a [`ReadbackRing<T>`] with `latency_frames + 1` slots of
+    /// `size_of::<T>()` bytes each — one slot the GPU is currently
+    /// writing, plus `latency_frames` more so the write side never
+    /// catches back up to a slot a caller hasn't read yet within one
+    /// cycle of the ring.
+    pub fn create_readback_ring<T: Copy>(&self, latency_frames: usize) -> BackendResult<ReadbackRing<T>> {
+        let slot_count = latency_frames + 1;
+        let slots = (0..slot_count)
+            .map(|_| {
+                self.create_buffer(BufferDesc::new_cpu_to_gpu(
+                    std::mem::size_of::<T>(),
+                    vk::BufferUsageFlags::TRANSFER_DST,
+                ))
+            })
+            .collect::<BackendResult<Vec<_>>>()?;
+
+        Ok(ReadbackRing {
+            slots,
+            latency_frames,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T: Copy> ReadbackRing<T> {
+    /// The buffer this frame's GPU write should land in — pass to
+    /// [`super::image::Image::record_copy_to_buffer`] or bind as a
+    /// compute shader's output storage buffer.
+    pub fn write_target(&self, frame_index: usize) -> vk::Buffer {
+        self.slots[frame_index % self.slots.len()].raw
+    }
+
+    /// Reads back the slot written `latency_frames` frames ago, or
+    /// `None` for the first `latency_frames` frames, before any slot has
+    /// made a full round trip yet.
+    pub fn try_read(&self, device: &Device, frame_index: usize) -> BackendResult<Option<ReadbackSlot<T>>> {
+        let Some(written_frame) = frame_index.checked_sub(self.latency_frames) else {
+            return Ok(None);
+        };
+
+        let slot_index = written_frame % self.slots.len();
+        let bytes = self.slots[slot_index].read_at(device, 0, std::mem::size_of::<T>() as u64)?;
+
+        let mut value: T = unsafe { std::mem::zeroed() };
+        unsafe {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), &mut value as *mut T as *mut u8, std::mem::size_of::<T>());
+        }
+
+        Ok(Some(ReadbackSlot {
+            value,
+            frame_index: written_frame,
+        }))
+    }
+
+    pub fn queue_drop(&self, device: &Device) {
+        for slot in &self.slots {
+            device.queue_drop(slot.raw);
+            device.queue_drop(slot.memory);
+        }
+    }
+}
+
+/// A value read back from a [`ReadbackRing<T>`], tagged with the frame
+/// index the GPU wrote it on.
+#[derive(Clone, Copy, Debug)]
+pub struct ReadbackSlot<T> {
+    pub value: T,
+    pub frame_index: usize,
+}
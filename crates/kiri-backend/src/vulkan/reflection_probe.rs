@@ -0,0 +1,186 @@
+use ash::vk;
+
+use kiri_assets::reflection_probe::{ParallaxCorrectionBox, ProbeInfluence, ReflectionProbeAsset};
+
+use crate::BackendResult;
+
+use super::device::Device;
+use super::frame_graph::{FrameGraph, PassHandle, ResourceHandle};
+use super::image::{Image, ImageDesc};
+use super::texture_format::vk_format_for_asset;
+
+/// A baked [`kiri_assets::EnvironmentAsset`] uploaded as a cube image
+/// (`array_elements: 6` with `CUBE_COMPATIBLE`), plus the probe placement
+/// data needed to select and parallax-correct it at shading time.
+pub struct ReflectionProbe {
+    pub asset: ReflectionProbeAsset,
+    pub cubemap: Image,
+}
+
+impl Device {
+    /// Creates the cube image for `asset.environment`'s baked data.
+    /// `face_size`/`format`/`mip_count` come from the corresponding
+    /// [`kiri_assets::EnvironmentAsset`] — this only allocates the GPU
+    /// resource; uploading each face/mip is the caller's job via
+    /// [`super::image::Image::record_copy_from_buffer`], same as any
+    /// other baked texture.
+    pub fn create_reflection_probe(
+        &self,
+        asset: ReflectionProbeAsset,
+        face_size: u32,
+        format: kiri_assets::image::ImageFormat,
+        mip_count: u32,
+    ) -> BackendResult<ReflectionProbe> {
+        let cubemap = self.create_image(
+            ImageDesc::new_2d(vk_format_for_asset(format), [face_size, face_size])
+                .array_elements(6)
+                .mip_levels(mip_count)
+                .cube_compatible()
+                .usage(vk::ImageUsageFlags::SAMPLED | vk::ImageUsageFlags::TRANSFER_DST),
+        )?;
+
+        Ok(ReflectionProbe { asset, cubemap })
+    }
+}
+
+impl ReflectionProbe {
+    pub fn queue_drop(&self, device: &Device) {
+        device.queue_drop(self.cubemap.raw);
+        device.queue_drop(self.cubemap.memory);
+        device.queue_drop(self.cubemap.view);
+        self.cubemap.queue_drop_views(device);
+    }
+
+    /// Whether `point` falls inside this probe's influence volume.
+    pub fn contains(&self, point: [f32; 3]) -> bool {
+        let local = sub(point, self.asset.position);
+        match self.asset.influence {
+            ProbeInfluence::Sphere { radius } => dot(local, local) <= radius * radius,
+            ProbeInfluence::Box { half_extents } => {
+                local[0].abs() <= half_extents[0]
+                    && local[1].abs() <= half_extents[1]
+                    && local[2].abs() <= half_extents[2]
+            }
+        }
+    }
+
+    /// Parallax-corrects `reflection_dir` (from `shading_point`) against
+    /// this probe's correction box, per the standard box-intersection
+    /// technique: the reflection ray is projected onto the box's
+    /// surface, and re-aimed from the probe's capture position through
+    /// that point — so a reflection sampled from the probe's cubemap
+    /// lines up with nearby geometry instead of looking like it was
+    /// captured from infinitely far away.
+    ///
+    /// Returns `reflection_dir` unchanged when the probe has no
+    /// correction box configured.
+    pub fn parallax_corrected_direction(
+        &self,
+        shading_point: [f32; 3],
+        reflection_dir: [f32; 3],
+    ) -> [f32; 3] {
+        let Some(ParallaxCorrectionBox {
+            center,
+            half_extents,
+        }) = self.asset.parallax_correction
+        else {
+            return reflection_dir;
+        };
+
+        let local_point = sub(shading_point, center);
+
+        let mut t_max = f32::MAX;
+        for axis in 0..3 {
+            if reflection_dir[axis].abs() > f32::EPSILON {
+                let plane = if reflection_dir[axis] > 0.0 {
+                    half_extents[axis]
+                } else {
+                    -half_extents[axis]
+                };
+                let t = (plane - local_point[axis]) / reflection_dir[axis];
+                if t > 0.0 {
+                    t_max = t_max.min(t);
+                }
+            }
+        }
+
+        let intersection = add(shading_point, scale(reflection_dir, t_max));
+        normalize(sub(intersection, self.asset.position))
+    }
+}
+
+/// Picks the `max_probes` probes containing `point` with the highest
+/// [`ReflectionProbeAsset::priority`], for a shading point that falls
+/// inside more than one probe's influence volume — the caller blends
+/// between whatever this returns (equally, or weighted by distance to
+/// each probe's influence bound; kiri doesn't prescribe a falloff curve
+/// here, just the candidate set).
+pub fn select_probes_for_point<'a>(
+    probes: &'a [ReflectionProbe],
+    point: [f32; 3],
+    max_probes: usize,
+) -> Vec<&'a ReflectionProbe> {
+    let mut candidates: Vec<&ReflectionProbe> =
+        probes.iter().filter(|probe| probe.contains(point)).collect();
+    candidates.sort_by_key(|probe| std::cmp::Reverse(probe.asset.priority));
+    candidates.truncate(max_probes);
+    candidates
+}
+
+/// Every baked probe in the scene, registered as a single frame-graph pass
+/// that resolves specular reflections against `depth`/`normal` (from the
+/// GBuffer or a forward equivalent) and writes `scene_color`. Probe
+/// selection and parallax correction (see [`select_probes_for_point`],
+/// [`ReflectionProbe::parallax_corrected_direction`]) happen per-pixel
+/// inside that pass; the graph only needs to know its resource
+/// dependencies, not the selection logic.
+pub struct ReflectionProbeSet {
+    pub probes: Vec<ReflectionProbe>,
+}
+
+impl ReflectionProbeSet {
+    pub fn new(probes: Vec<ReflectionProbe>) -> Self {
+        Self { probes }
+    }
+
+    pub fn register(
+        &self,
+        graph: &mut FrameGraph,
+        depth: ResourceHandle,
+        normal: ResourceHandle,
+        scene_color: ResourceHandle,
+    ) -> PassHandle {
+        graph.pass("reflection_probe_resolve", &[depth, normal], &[scene_color])
+    }
+
+    pub fn queue_drop(&self, device: &Device) {
+        for probe in &self.probes {
+            probe.queue_drop(device);
+        }
+    }
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn add(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(a: [f32; 3], s: f32) -> [f32; 3] {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn normalize(a: [f32; 3]) -> [f32; 3] {
+    let len = dot(a, a).sqrt();
+    if len > f32::EPSILON {
+        scale(a, 1.0 / len)
+    } else {
+        a
+    }
+}
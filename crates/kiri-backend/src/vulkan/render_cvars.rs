@@ -0,0 +1,28 @@
+use kiri_core::cvar::{CvarRange, CvarRegistry, CvarValue};
+
+/// Registers the renderer's runtime-tunable knobs. Neither a temporal
+/// anti-aliasing pass nor a shadow-map pass exists in this crate yet
+/// (`postfx.rs`'s velocity buffer is used today only for motion blur) —
+/// these are registered ahead of that work so the console/config-file
+/// plumbing doesn't need to change once those passes land; until then
+/// nothing consults the values yet.
+pub fn register_render_cvars(registry: &mut CvarRegistry) {
+    registry.register(
+        "r.taa_enabled",
+        CvarValue::Bool(true),
+        None,
+        "Enables temporal anti-aliasing.",
+    );
+
+    registry.register(
+        "r.shadow_resolution",
+        CvarValue::Enum(2),
+        Some(CvarRange::Enum(vec![
+            "512".to_string(),
+            "1024".to_string(),
+            "2048".to_string(),
+            "4096".to_string(),
+        ])),
+        "Shadow map resolution per cascade.",
+    );
+}
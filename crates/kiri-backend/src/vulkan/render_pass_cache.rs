@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::device::Device;
+
+/// Compatibility key for a `vk::RenderPass`: two render passes with the
+/// same key are interchangeable per the Vulkan render pass compatibility
+/// rules kiri actually relies on (same attachment formats and sample
+/// count), so [`RenderPassCache`] only ever creates one `vk::RenderPass`
+/// per distinct key.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RenderPassKey {
+    pub color_formats: Vec<vk::Format>,
+    pub depth_format: Option<vk::Format>,
+    pub samples: vk::SampleCountFlags,
+}
+
+/// Key for a cached imageless `vk::Framebuffer`. Includes `extent` (unlike
+/// [`RenderPassKey`]) since a framebuffer, imageless or not, bakes in the
+/// render area it was created for.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FramebufferKey {
+    pub render_pass: vk::RenderPass,
+    pub color_formats: Vec<vk::Format>,
+    pub depth_format: Option<vk::Format>,
+    pub extent: [u32; 2],
+    pub layers: u32,
+}
+
+/// A compatibility-keyed cache of `vk::RenderPass` and imageless
+/// `vk::Framebuffer` objects, for the render-pass code path kiri falls
+/// back to on drivers without dynamic rendering.
+///
+/// Render passes are keyed on attachment formats and sample count, so they
+/// survive a resize untouched. Framebuffers additionally bake in the
+/// render area, so [`RenderPassCache::notify_resize`] queues every cached
+/// framebuffer for deferred destruction and starts the framebuffer cache
+/// over whenever the extent changes.
+pub struct RenderPassCache {
+    render_passes: HashMap<RenderPassKey, vk::RenderPass>,
+    framebuffers: HashMap<FramebufferKey, vk::Framebuffer>,
+    extent: [u32; 2],
+}
+
+impl Device {
+    /// Creates an empty [`RenderPassCache`] sized for `extent`. Pass the
+    /// same extent [`RenderPassCache::notify_resize`] is later called with
+    /// on swapchain recreation.
+    pub fn create_render_pass_cache(&self, extent: [u32; 2]) -> RenderPassCache {
+        RenderPassCache {
+            render_passes: HashMap::new(),
+            framebuffers: HashMap::new(),
+            extent,
+        }
+    }
+}
+
+impl RenderPassCache {
+    /// Returns the cached `vk::RenderPass` for `key`, creating it on first
+    /// use.
+    pub fn render_pass(&mut self, device: &Device, key: &RenderPassKey) -> BackendResult<vk::RenderPass> {
+        if let Some(&render_pass) = self.render_passes.get(key) {
+            return Ok(render_pass);
+        }
+
+        let render_pass = create_render_pass(device, key)?;
+        self.render_passes.insert(key.clone(), render_pass);
+        Ok(render_pass)
+    }
+
+    /// Returns the cached imageless `vk::Framebuffer` for `key`, creating
+    /// it on first use. `key.render_pass` must be one previously returned
+    /// by [`RenderPassCache::render_pass`] on this same cache.
+    pub fn framebuffer(&mut self, device: &Device, key: &FramebufferKey) -> BackendResult<vk::Framebuffer> {
+        if let Some(&framebuffer) = self.framebuffers.get(key) {
+            return Ok(framebuffer);
+        }
+
+        let framebuffer = create_imageless_framebuffer(device, key)?;
+        self.framebuffers.insert(key.clone(), framebuffer);
+        Ok(framebuffer)
+    }
+
+    /// Call after recreating the swapchain (or any other resize of the
+    /// render target) with the new extent. A no-op if `new_extent` matches
+    /// what the cache was already sized for. Otherwise every cached
+    /// framebuffer is queued on `device`'s deferred destroy list and the
+    /// framebuffer cache starts empty again; cached render passes are
+    /// unaffected, since they aren't keyed on extent.
+    pub fn notify_resize(&mut self, device: &Device, new_extent: [u32; 2]) {
+        if new_extent == self.extent {
+            return;
+        }
+
+        for (_, framebuffer) in self.framebuffers.drain() {
+            device.queue_drop(framebuffer);
+        }
+        self.extent = new_extent;
+    }
+
+    /// Destroys every cached render pass and framebuffer immediately.
+    ///
+    /// # Safety
+    /// No cached handle may still be referenced by in-flight GPU work.
+    pub unsafe fn destroy(&mut self, device: &ash::Device) {
+        for (_, framebuffer) in self.framebuffers.drain() {
+            device.destroy_framebuffer(framebuffer, None);
+        }
+        for (_, render_pass) in self.render_passes.drain() {
+            device.destroy_render_pass(render_pass, None);
+        }
+    }
+}
+
+fn create_render_pass(device: &Device, key: &RenderPassKey) -> BackendResult<vk::RenderPass> {
+    let mut attachments = Vec::new();
+    let mut color_refs = Vec::new();
+
+    for &format in &key.color_formats {
+        color_refs.push(vk::AttachmentReference {
+            attachment: attachments.len() as u32,
+            layout: vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+        });
+        attachments.push(
+            vk::AttachmentDescription::builder()
+                .format(format)
+                .samples(key.samples)
+                .load_op(vk::AttachmentLoadOp::CLEAR)
+                .store_op(vk::AttachmentStoreOp::STORE)
+                .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+                .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+                .initial_layout(vk::ImageLayout::UNDEFINED)
+                .final_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                .build(),
+        );
+    }
+
+    let depth_ref = key.depth_format.map(|format| {
+        let reference = vk::AttachmentReference {
+            attachment: attachments.len() as u32,
+            layout: vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL,
+        };
+        attachments.push(
+            vk::AttachmentDescription::builder()
+                .format(format)
+                .samples(key.samples)
+                .load_op(vk::AttachmentLoadOp::CLEAR)
+                .store_op(vk::AttachmentStoreOp::STORE)
+                .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+                .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+                .initial_layout(vk::ImageLayout::UNDEFINED)
+                .final_layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL)
+                .build(),
+        );
+        reference
+    });
+
+    let mut subpass = vk::SubpassDescription::builder()
+        .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+        .color_attachments(&color_refs);
+
+    if let Some(depth_ref) = depth_ref.as_ref() {
+        subpass = subpass.depth_stencil_attachment(depth_ref);
+    }
+
+    let create_info = vk::RenderPassCreateInfo::builder()
+        .attachments(&attachments)
+        .subpasses(std::slice::from_ref(&subpass));
+
+    Ok(unsafe { device.raw().create_render_pass(&create_info, None)? })
+}
+
+fn create_imageless_framebuffer(device: &Device, key: &FramebufferKey) -> BackendResult<vk::Framebuffer> {
+    let usage_for = |format: vk::Format| {
+        if super::image::aspect_mask_for_format(format).contains(vk::ImageAspectFlags::COLOR) {
+            vk::ImageUsageFlags::COLOR_ATTACHMENT
+        } else {
+            vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT
+        }
+    };
+
+    let formats: Vec<vk::Format> = key
+        .color_formats
+        .iter()
+        .copied()
+        .chain(key.depth_format)
+        .collect();
+
+    let attachment_infos: Vec<vk::FramebufferAttachmentImageInfo> = formats
+        .iter()
+        .map(|&format| {
+            vk::FramebufferAttachmentImageInfo::builder()
+                .usage(usage_for(format))
+                .width(key.extent[0])
+                .height(key.extent[1])
+                .layer_count(key.layers)
+                .view_formats(std::slice::from_ref(&format))
+                .build()
+        })
+        .collect();
+
+    let mut attachments_create_info =
+        vk::FramebufferAttachmentsCreateInfo::builder().attachment_image_infos(&attachment_infos);
+
+    let create_info = vk::FramebufferCreateInfo::builder()
+        .flags(vk::FramebufferCreateFlags::IMAGELESS)
+        .render_pass(key.render_pass)
+        .width(key.extent[0])
+        .height(key.extent[1])
+        .layers(key.layers)
+        .attachment_count(attachment_infos.len() as u32)
+        .push_next(&mut attachments_create_info);
+
+    Ok(unsafe { device.raw().create_framebuffer(&create_info, None)? })
+}
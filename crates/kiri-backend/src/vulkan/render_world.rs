@@ -0,0 +1,289 @@
+use std::collections::HashMap;
+
+use glam::{Mat4, Vec3};
+
+use kiri_assets::model::Aabb;
+use kiri_assets::AssetRef;
+
+/// Per-proxy toggles that change how culling/draw submission treats a
+/// [`RenderProxy`] without needing a separate list per case.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ProxyFlags(u32);
+
+impl ProxyFlags {
+    pub const NONE: ProxyFlags = ProxyFlags(0);
+    /// Excluded from shadow-map draw passes (e.g. a first-person view model
+    /// that shouldn't cast a shadow onto itself).
+    pub const CASTS_NO_SHADOW: ProxyFlags = ProxyFlags(1 << 0);
+    /// Excluded from the main color pass but still visible to shadow passes
+    /// (an occluder-only stand-in for expensive geometry).
+    pub const SHADOW_ONLY: ProxyFlags = ProxyFlags(1 << 1);
+    /// Skipped by frustum/occlusion culling and always submitted — for
+    /// proxies whose bounds are unreliable (skinned meshes mid animation
+    /// blend) or that are cheap enough not to bother.
+    pub const ALWAYS_VISIBLE: ProxyFlags = ProxyFlags(1 << 2);
+
+    pub const fn empty() -> ProxyFlags {
+        ProxyFlags(0)
+    }
+
+    pub fn contains(&self, other: ProxyFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for ProxyFlags {
+    type Output = ProxyFlags;
+
+    fn bitor(self, rhs: ProxyFlags) -> ProxyFlags {
+        ProxyFlags(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for ProxyFlags {
+    fn bitor_assign(&mut self, rhs: ProxyFlags) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl Default for ProxyFlags {
+    fn default() -> Self {
+        ProxyFlags::NONE
+    }
+}
+
+/// A sphere bounding a [`RenderProxy`] in world space, used for
+/// frustum/occlusion culling before any per-vertex work happens.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoundingSphere {
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+/// Transforms an object-space [`Aabb`] (from [`kiri_assets::model::Mesh::bounds`]
+/// or [`kiri_assets::model::ModelAsset::bounds`]) by `transform` into the
+/// world-space [`BoundingSphere`] a [`RenderProxy`] should be populated
+/// with. Scale and rotation are folded in by transforming all eight
+/// corners rather than just the center and half-extents, since a rotated
+/// non-uniform scale can otherwise underestimate the box; the extra work
+/// only happens once per proxy per transform change, not per frame.
+pub fn transform_bounds(aabb: Aabb, transform: Mat4) -> BoundingSphere {
+    let min = Vec3::from(aabb.min);
+    let max = Vec3::from(aabb.max);
+
+    let corners = [
+        Vec3::new(min.x, min.y, min.z),
+        Vec3::new(max.x, min.y, min.z),
+        Vec3::new(min.x, max.y, min.z),
+        Vec3::new(max.x, max.y, min.z),
+        Vec3::new(min.x, min.y, max.z),
+        Vec3::new(max.x, min.y, max.z),
+        Vec3::new(min.x, max.y, max.z),
+        Vec3::new(max.x, max.y, max.z),
+    ]
+    .map(|corner| transform.transform_point3(corner));
+
+    let center = corners.iter().fold(Vec3::ZERO, |sum, &corner| sum + corner) / corners.len() as f32;
+    let radius = corners
+        .iter()
+        .map(|&corner| corner.distance(center))
+        .fold(0.0f32, f32::max);
+
+    BoundingSphere { center, radius }
+}
+
+/// The renderer's view of one drawable game-side object: what to draw, how
+/// to shade it, where it is, and how to cull it. `RenderProxy`s are kept in
+/// [`RenderWorld`] independently of whatever component/entity layout the
+/// simulation side uses, so a switch in ECS or scene-graph representation
+/// doesn't ripple into culling or draw submission.
+///
+/// `material_slots` mirrors the [`kiri_assets::model::ModelAsset`]
+/// `mesh` points at — one base material per
+/// [`kiri_assets::model::Mesh::material_slot`]. `material_overrides`
+/// lets one instance swap specific slots (a damage-state skin, a team
+/// color) without cloning the whole model asset just to change a
+/// material; [`RenderProxy::effective_material`] is what draw submission
+/// should call rather than indexing `material_slots` directly.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderProxy {
+    pub mesh: AssetRef,
+    pub material_slots: Vec<AssetRef>,
+    pub material_overrides: HashMap<u32, AssetRef>,
+    pub transform: Mat4,
+    pub bounds: BoundingSphere,
+    pub flags: ProxyFlags,
+}
+
+impl RenderProxy {
+    /// `material_overrides[slot]` if set, otherwise `material_slots[slot]`,
+    /// or `None` if `slot` is out of range for both.
+    pub fn effective_material(&self, slot: u32) -> Option<AssetRef> {
+        self.material_overrides
+            .get(&slot)
+            .copied()
+            .or_else(|| self.material_slots.get(slot as usize).copied())
+    }
+}
+
+/// Identifies one proxy registered with a [`RenderWorld`]. Stable for the
+/// proxy's lifetime and stale after [`RenderWorld::remove`];
+/// [`RenderWorld::insert`] draws every handle from an ever-increasing
+/// counter, so a removed handle is never handed out again, same as
+/// [`super::geometry_arena::BufferHandle`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ProxyHandle(u32);
+
+enum ProxyChange {
+    Insert(ProxyHandle, RenderProxy),
+    Update(ProxyHandle, RenderProxy),
+    Remove(ProxyHandle),
+    SetMaterialOverride(ProxyHandle, u32, AssetRef),
+    ClearMaterialOverride(ProxyHandle, u32),
+}
+
+/// GPU-facing arrays of [`RenderProxy`]s, decoupled from the game side's
+/// own data layout: gameplay/ECS code calls [`RenderWorld::insert`],
+/// [`RenderWorld::update`], and [`RenderWorld::remove`] freely as objects
+/// spawn, move, and despawn, and none of that touches `proxies` directly —
+/// it only appends to `pending`. [`RenderWorld::apply_pending_changes`]
+/// then drains the queue once per frame, at a point the renderer controls,
+/// so culling and draw submission always see a consistent snapshot rather
+/// than a world mutating mid-pass.
+#[derive(Default)]
+pub struct RenderWorld {
+    proxies: HashMap<u32, RenderProxy>,
+    next_handle: u32,
+    pending: Vec<ProxyChange>,
+}
+
+impl RenderWorld {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a new proxy for insertion, returning the handle it will be
+    /// addressable by once [`RenderWorld::apply_pending_changes`] runs.
+    /// The handle is valid to hand to [`RenderWorld::update`] or
+    /// [`RenderWorld::remove`] immediately, even before the queue is
+    /// flushed.
+    pub fn insert(&mut self, proxy: RenderProxy) -> ProxyHandle {
+        let handle = ProxyHandle(self.next_handle);
+        self.next_handle += 1;
+        self.pending.push(ProxyChange::Insert(handle, proxy));
+        handle
+    }
+
+    /// Queues a full replacement of `handle`'s proxy data (e.g. a new
+    /// transform after the game side moved the object this frame).
+    pub fn update(&mut self, handle: ProxyHandle, proxy: RenderProxy) {
+        self.pending.push(ProxyChange::Update(handle, proxy));
+    }
+
+    pub fn remove(&mut self, handle: ProxyHandle) {
+        self.pending.push(ProxyChange::Remove(handle));
+    }
+
+    /// Queues a per-instance override of `handle`'s material slot `slot`
+    /// — a damage state or team color, say — without touching the
+    /// underlying model asset or any other proxy sharing it.
+    pub fn set_material_override(&mut self, handle: ProxyHandle, slot: u32, material: AssetRef) {
+        self.pending.push(ProxyChange::SetMaterialOverride(handle, slot, material));
+    }
+
+    /// Queues clearing a slot override set via
+    /// [`RenderWorld::set_material_override`], reverting it back to the
+    /// proxy's base `material_slots` entry.
+    pub fn clear_material_override(&mut self, handle: ProxyHandle, slot: u32) {
+        self.pending.push(ProxyChange::ClearMaterialOverride(handle, slot));
+    }
+
+    /// Drains the change queue built up since the last call, applying every
+    /// insert/update/remove to `proxies` in submission order. Call once per
+    /// frame before culling reads [`RenderWorld::proxies`].
+    pub fn apply_pending_changes(&mut self) {
+        for change in self.pending.drain(..) {
+            match change {
+                ProxyChange::Insert(handle, proxy) | ProxyChange::Update(handle, proxy) => {
+                    self.proxies.insert(handle.0, proxy);
+                }
+                ProxyChange::Remove(handle) => {
+                    self.proxies.remove(&handle.0);
+                }
+                ProxyChange::SetMaterialOverride(handle, slot, material) => {
+                    if let Some(proxy) = self.proxies.get_mut(&handle.0) {
+                        proxy.material_overrides.insert(slot, material);
+                    }
+                }
+                ProxyChange::ClearMaterialOverride(handle, slot) => {
+                    if let Some(proxy) = self.proxies.get_mut(&handle.0) {
+                        proxy.material_overrides.remove(&slot);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The current, already-flushed proxy set — what culling and draw
+    /// submission should iterate. Reflects the state as of the last
+    /// [`RenderWorld::apply_pending_changes`] call, not any changes queued
+    /// since.
+    pub fn proxies(&self) -> impl Iterator<Item = (ProxyHandle, &RenderProxy)> {
+        self.proxies.iter().map(|(&id, proxy)| (ProxyHandle(id), proxy))
+    }
+
+    pub fn get(&self, handle: ProxyHandle) -> Option<&RenderProxy> {
+        self.proxies.get(&handle.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.proxies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.proxies.is_empty()
+    }
+
+    /// Coarse ray-cast pick against every proxy's [`BoundingSphere`],
+    /// returning the handle and hit distance of the closest one the ray
+    /// enters, or `None` if it misses everything. This is the broad phase
+    /// only — `RenderWorld` doesn't own mesh geometry, just an
+    /// [`kiri_assets::AssetRef`] pointing at it, so an editor after an
+    /// exact per-triangle hit should resolve the winning proxy's `mesh`
+    /// ref to its [`kiri_assets::model::ModelAsset`] and re-test with
+    /// [`kiri_assets::picking::ray_model_intersect`] against a ray brought
+    /// into the proxy's object space via its inverse `transform`.
+    pub fn pick(&self, ray_origin: Vec3, ray_direction: Vec3) -> Option<(ProxyHandle, f32)> {
+        let ray_direction = ray_direction.normalize_or_zero();
+        if ray_direction == Vec3::ZERO {
+            return None;
+        }
+
+        self.proxies()
+            .filter_map(|(handle, proxy)| {
+                ray_sphere_intersect(ray_origin, ray_direction, proxy.bounds).map(|t| (handle, t))
+            })
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+    }
+}
+
+/// Nearest ray-parameter `t` at which a ray enters `sphere`, or `None` if
+/// it misses (or the origin is already past the sphere's far side).
+fn ray_sphere_intersect(ray_origin: Vec3, ray_direction: Vec3, sphere: BoundingSphere) -> Option<f32> {
+    let to_center = sphere.center - ray_origin;
+    let projection = to_center.dot(ray_direction);
+    let closest_approach_sq = to_center.length_squared() - projection * projection;
+    let radius_sq = sphere.radius * sphere.radius;
+    if closest_approach_sq > radius_sq {
+        return None;
+    }
+
+    let half_chord = (radius_sq - closest_approach_sq).sqrt();
+    let t_near = projection - half_chord;
+    let t_far = projection + half_chord;
+    if t_far < 0.0 {
+        return None;
+    }
+
+    Some(t_near.max(0.0))
+}
@@ -0,0 +1,32 @@
+use std::sync::mpsc;
+
+use super::drop_list::ToDrop;
+
+/// Cloneable handle that lets any thread schedule a GPU handle for
+/// destruction without holding a `&Device` — a gameplay thread unloading
+/// an asset, or a callback running on an executor with no device
+/// reference threaded through it, can still call [`ResourceDestroyer::destroy`].
+///
+/// The request just goes over a channel; [`super::device::Device::collect_garbage`]
+/// (and `Device`'s own `Drop`) are what actually drain it into the
+/// [`super::drop_list::DropList`] and eventually free it, on whichever
+/// thread owns the `Device`.
+#[derive(Clone)]
+pub struct ResourceDestroyer {
+    sender: mpsc::Sender<ToDrop>,
+}
+
+impl ResourceDestroyer {
+    pub(crate) fn new(sender: mpsc::Sender<ToDrop>) -> Self {
+        Self { sender }
+    }
+
+    /// Queues `item` for destruction. A send failing (the owning `Device`
+    /// has already been dropped, so nothing is left to drain the channel)
+    /// is silently ignored rather than panicking — there is nothing left
+    /// to free the handle into by that point, and a background thread
+    /// racing shutdown shouldn't crash over it.
+    pub fn destroy(&self, item: impl Into<ToDrop>) {
+        let _ = self.sender.send(item.into());
+    }
+}
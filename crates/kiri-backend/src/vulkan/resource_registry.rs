@@ -0,0 +1,97 @@
+//! Tracks live GPU resources by name/size, independent of
+//! `VK_EXT_debug_utils` — [`Device::set_debug_name`] only reaches the
+//! validation layer and GPU capture tools, so a leak on a machine without
+//! validation enabled (most CI containers, most players' machines) only
+//! ever shows up as a shutdown-time validation message on someone else's
+//! machine. [`Device::dump_resources`] gives the same information from
+//! inside the process itself, on demand, with no validation layer
+//! required.
+//!
+//! Registration is opt-in via [`Device::create_image_named`]/
+//! [`Device::create_buffer_named`] rather than baked into every
+//! `create_image`/`create_buffer` call, so the (debug-build-only, but
+//! still non-free) cost of capturing a creation backtrace is only paid
+//! for the resources a caller actually wants to be able to find later.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourceKind {
+    Image,
+    Buffer,
+    /// Not registered by anything yet — pipeline creation doesn't go
+    /// through one central `Device` method the way images and buffers do
+    /// (see `pipeline.rs`), so wiring this up means touching each of its
+    /// call sites individually rather than one shared constructor.
+    Pipeline,
+}
+
+/// One entry in a [`Device::dump_resources`] snapshot.
+#[derive(Clone, Debug)]
+pub struct ResourceRecord {
+    pub kind: ResourceKind,
+    pub name: String,
+    pub size_bytes: u64,
+    /// Captured with [`std::backtrace::Backtrace::force_capture`] at
+    /// registration time when `debug_assertions` is on; `None` in a
+    /// release build, where the cost isn't worth paying by default.
+    pub creation_stack: Option<String>,
+}
+
+#[derive(Default)]
+pub struct ResourceRegistry {
+    entries: Mutex<HashMap<u64, ResourceRecord>>,
+}
+
+impl ResourceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&self, handle: u64, kind: ResourceKind, name: String, size_bytes: u64) {
+        let creation_stack = cfg!(debug_assertions)
+            .then(|| std::backtrace::Backtrace::force_capture().to_string());
+
+        self.entries.lock().unwrap().insert(
+            handle,
+            ResourceRecord {
+                kind,
+                name,
+                size_bytes,
+                creation_stack,
+            },
+        );
+    }
+
+    pub fn unregister(&self, handle: u64) {
+        self.entries.lock().unwrap().remove(&handle);
+    }
+
+    /// Every resource currently registered — a leak shows up here as an
+    /// entry that's still present long after the caller expected it to be
+    /// gone.
+    pub fn dump(&self) -> Vec<ResourceRecord> {
+        self.entries.lock().unwrap().values().cloned().collect()
+    }
+}
+
+impl super::device::Device {
+    /// Every named resource created via
+    /// [`Device::create_image_named`]/[`Device::create_buffer_named`] that
+    /// hasn't been [`Device::forget_resource`]d yet — call this from a
+    /// debug console command or at shutdown to see what's still alive
+    /// instead of waiting for the validation layer to complain.
+    pub fn dump_resources(&self) -> Vec<ResourceRecord> {
+        self.resource_registry.dump()
+    }
+
+    /// Removes `handle` from the resource registry. Callers that named a
+    /// resource at creation and later queue it for destruction (see
+    /// [`super::drop_list::DropList`]) should call this alongside
+    /// [`Device::queue_drop`] so a destroyed-but-still-registered entry
+    /// doesn't look like a leak in [`Device::dump_resources`].
+    pub fn forget_resource<H: ash::vk::Handle>(&self, handle: H) {
+        self.resource_registry.unregister(handle.as_raw());
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::{BTreeSet, HashMap};
+use std::mem::size_of;
+
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::buffer::{Buffer, BufferDesc};
+use super::device::Device;
+use super::render_world::ProxyHandle;
+
+/// Packed per-object data a shader reads directly out of [`SceneBuffer`]'s
+/// storage buffer — one slot per live `RenderProxy`, indexed by whatever
+/// integer the draw already carries (an instance index, a push constant),
+/// which this struct doesn't own.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ObjectGpuData {
+    pub transform: [[f32; 4]; 4],
+    pub bounds: [f32; 4],
+    pub flags: u32,
+    pub _pad: [u32; 3],
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SceneBufferDesc {
+    pub max_objects: u32,
+}
+
+/// A persistent storage buffer mirroring [`super::render_world::RenderWorld`]'s
+/// proxies, one [`ObjectGpuData`] slot per handle, sized once for up to
+/// `max_objects` live proxies rather than reallocated as the scene grows
+/// and shrinks.
+///
+/// [`SceneBuffer::set`]/[`SceneBuffer::remove`] only touch a host-side
+/// shadow copy and mark the affected slot dirty; [`SceneBuffer::flush`]
+/// merges dirty slots into contiguous byte ranges and uploads just those,
+/// so a frame where a handful of objects moved out of 100k costs a
+/// handful of small copies instead of a full re-upload.
+pub struct SceneBuffer {
+    buffer: Buffer,
+    shadow: Vec<ObjectGpuData>,
+    slot_of: HashMap<ProxyHandle, u32>,
+    free_slots: Vec<u32>,
+    next_slot: u32,
+    dirty_slots: BTreeSet<u32>,
+}
+
+impl Device {
+    pub fn create_scene_buffer(&self, desc: &SceneBufferDesc) -> BackendResult<SceneBuffer> {
+        let stride = size_of::<ObjectGpuData>();
+        let buffer = self.create_buffer(BufferDesc::new_cpu_to_gpu(
+            desc.max_objects as usize * stride,
+            vk::BufferUsageFlags::STORAGE_BUFFER,
+        ))?;
+
+        Ok(SceneBuffer {
+            buffer,
+            shadow: vec![zeroed_object(); desc.max_objects as usize],
+            slot_of: HashMap::new(),
+            free_slots: Vec::new(),
+            next_slot: 0,
+            dirty_slots: BTreeSet::new(),
+        })
+    }
+}
+
+impl SceneBuffer {
+    pub fn raw_buffer(&self) -> vk::Buffer {
+        self.buffer.raw
+    }
+
+    /// Writes `data` into `handle`'s slot, allocating one (from the free
+    /// list, or bumping `next_slot`) if this is the first time `handle` has
+    /// been seen. Panics if every slot is in use — the caller sized
+    /// [`SceneBufferDesc::max_objects`] too small for its scene.
+    pub fn set(&mut self, handle: ProxyHandle, data: ObjectGpuData) {
+        let slot = *self.slot_of.entry(handle).or_insert_with(|| {
+            self.free_slots.pop().unwrap_or_else(|| {
+                let slot = self.next_slot;
+                self.next_slot += 1;
+                slot
+            })
+        });
+
+        assert!(
+            (slot as usize) < self.shadow.len(),
+            "SceneBuffer is out of slots for {} max_objects",
+            self.shadow.len()
+        );
+
+        self.shadow[slot as usize] = data;
+        self.dirty_slots.insert(slot);
+    }
+
+    /// Frees `handle`'s slot for reuse and zeroes it out so a stale entry
+    /// never gets sampled as if it were still live.
+    pub fn remove(&mut self, handle: ProxyHandle) {
+        if let Some(slot) = self.slot_of.remove(&handle) {
+            self.shadow[slot as usize] = zeroed_object();
+            self.dirty_slots.insert(slot);
+            self.free_slots.push(slot);
+        }
+    }
+
+    pub fn slot_of(&self, handle: ProxyHandle) -> Option<u32> {
+        self.slot_of.get(&handle).copied()
+    }
+
+    /// Uploads every dirty slot since the last flush, coalescing
+    /// consecutive slot indices into single `write_at` calls instead of one
+    /// call per slot.
+    pub fn flush(&mut self, device: &Device) -> BackendResult<()> {
+        let stride = size_of::<ObjectGpuData>() as u64;
+
+        let mut dirty = self.dirty_slots.iter().copied();
+        let Some(mut range_start) = dirty.next() else {
+            return Ok(());
+        };
+        let mut range_end = range_start;
+
+        for slot in dirty {
+            if slot == range_end + 1 {
+                range_end = slot;
+                continue;
+            }
+            self.flush_range(device, range_start, range_end, stride)?;
+            range_start = slot;
+            range_end = slot;
+        }
+        self.flush_range(device, range_start, range_end, stride)?;
+
+        self.dirty_slots.clear();
+        Ok(())
+    }
+
+    fn flush_range(
+        &self,
+        device: &Device,
+        first_slot: u32,
+        last_slot: u32,
+        stride: u64,
+    ) -> BackendResult<()> {
+        let objects = &self.shadow[first_slot as usize..=last_slot as usize];
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                objects.as_ptr() as *const u8,
+                objects.len() * stride as usize,
+            )
+        };
+        self.buffer.write_at(device, first_slot as u64 * stride, bytes)
+    }
+}
+
+fn zeroed_object() -> ObjectGpuData {
+    ObjectGpuData {
+        transform: [[0.0; 4]; 4],
+        bounds: [0.0; 4],
+        flags: 0,
+        _pad: [0; 3],
+    }
+}
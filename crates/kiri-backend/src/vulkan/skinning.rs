@@ -0,0 +1,141 @@
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::buffer::{Buffer, BufferDesc};
+use super::device::Device;
+
+/// Vertices skinned per compute workgroup; matches the `local_size_x`
+/// declared in the skinning compute shader.
+const SKINNING_WORKGROUP_SIZE: u32 = 256;
+
+/// A compute pipeline that skins vertices (bone indices + weights from a
+/// mesh's skinning stream, against this frame's bone matrices) into a
+/// per-frame output buffer. CPU skinning doesn't scale to crowds, so this
+/// is the only skinning path the renderer has.
+pub struct SkinningPass {
+    pub descriptor_set_layout: vk::DescriptorSetLayout,
+    pub pipeline_layout: vk::PipelineLayout,
+    pub pipeline: vk::Pipeline,
+}
+
+impl Device {
+    /// Builds the skinning compute pipeline from already-compiled SPIR-V
+    /// (produced by the baker from the skinning compute shader) with a
+    /// fixed 3-binding layout: input vertices, bone matrices, output
+    /// vertices, all storage buffers.
+    pub fn create_skinning_pass(&self, spirv: &[u8]) -> BackendResult<SkinningPass> {
+        let shader_module = self.create_shader_module(spirv)?;
+
+        let bindings = [
+            storage_buffer_binding(0), // input vertices (bind pose + skin weights)
+            storage_buffer_binding(1), // bone matrices, this frame
+            storage_buffer_binding(2), // output vertices
+        ];
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(&bindings);
+        let descriptor_set_layout =
+            unsafe { self.raw().create_descriptor_set_layout(&layout_info, None)? };
+
+        let set_layouts = [descriptor_set_layout];
+        let pipeline_layout_info =
+            vk::PipelineLayoutCreateInfo::builder().set_layouts(&set_layouts);
+        let pipeline_layout =
+            unsafe { self.raw().create_pipeline_layout(&pipeline_layout_info, None)? };
+
+        let entry_point = std::ffi::CString::new("main").unwrap();
+        let stage = vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::COMPUTE)
+            .module(shader_module)
+            .name(&entry_point);
+
+        let create_info = vk::ComputePipelineCreateInfo::builder()
+            .stage(*stage)
+            .layout(pipeline_layout);
+
+        let pipeline = unsafe {
+            self.raw()
+                .create_compute_pipelines(vk::PipelineCache::null(), &[*create_info], None)
+                .map_err(|(_, result)| result)?[0]
+        };
+
+        unsafe {
+            self.raw().destroy_shader_module(shader_module, None);
+        }
+
+        Ok(SkinningPass {
+            descriptor_set_layout,
+            pipeline_layout,
+            pipeline,
+        })
+    }
+
+    /// Records a dispatch that skins `vertex_count` vertices. The caller
+    /// binds the descriptor set pointing at the input vertex/bone-matrix
+    /// buffers and this frame's [`SkinnedMeshBuffers::current`] before
+    /// calling this.
+    pub fn cmd_dispatch_skinning(
+        &self,
+        command_buffer: vk::CommandBuffer,
+        pass: &SkinningPass,
+        vertex_count: u32,
+    ) {
+        let workgroup_count =
+            (vertex_count + SKINNING_WORKGROUP_SIZE - 1) / SKINNING_WORKGROUP_SIZE;
+        unsafe {
+            self.raw()
+                .cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::COMPUTE, pass.pipeline);
+            self.raw().cmd_dispatch(command_buffer, workgroup_count, 1, 1);
+        }
+    }
+}
+
+fn storage_buffer_binding(binding: u32) -> vk::DescriptorSetLayoutBinding {
+    vk::DescriptorSetLayoutBinding::builder()
+        .binding(binding)
+        .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+        .descriptor_count(1)
+        .stage_flags(vk::ShaderStageFlags::COMPUTE)
+        .build()
+}
+
+/// Double-buffered skinning output for one skinned mesh instance:
+/// `current` holds this frame's skinned positions, `previous` holds last
+/// frame's, so the velocity pass can diff the two for TAA without
+/// re-skinning or keeping a separate velocity buffer around. Call
+/// [`SkinnedMeshBuffers::swap`] once per frame, after the velocity pass has
+/// read `previous`.
+pub struct SkinnedMeshBuffers {
+    buffers: [Buffer; 2],
+    current_index: usize,
+    pub vertex_count: u32,
+}
+
+impl SkinnedMeshBuffers {
+    pub fn current(&self) -> &Buffer {
+        &self.buffers[self.current_index]
+    }
+
+    pub fn previous(&self) -> &Buffer {
+        &self.buffers[1 - self.current_index]
+    }
+
+    pub fn swap(&mut self) {
+        self.current_index = 1 - self.current_index;
+    }
+}
+
+impl Device {
+    pub fn create_skinned_mesh_buffers(&self, vertex_count: u32) -> BackendResult<SkinnedMeshBuffers> {
+        let size = vertex_count as usize * std::mem::size_of::<[f32; 3]>();
+        let usage = vk::BufferUsageFlags::STORAGE_BUFFER;
+
+        Ok(SkinnedMeshBuffers {
+            buffers: [
+                self.create_buffer(BufferDesc::new_gpu_only(size, usage))?,
+                self.create_buffer(BufferDesc::new_gpu_only(size, usage))?,
+            ],
+            current_index: 0,
+            vertex_count,
+        })
+    }
+}
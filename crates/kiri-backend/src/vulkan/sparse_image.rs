@@ -0,0 +1,134 @@
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::device::Device;
+use super::image::ImageDesc;
+
+/// A sparsely-resident image: the `vk::Image` and its virtual address space
+/// are created up front at full mip count, but no memory is bound to any
+/// page until pages staged with [`SparseImage::stage_page_bind`] are
+/// submitted by [`Device::flush_sparse_bindings`].
+///
+/// This backs compute-driven texture streaming: the streaming system
+/// decides which mip tail pages are worth residency based on what showed
+/// up in last frame's feedback, and only pages it bound are safe to sample
+/// from (everything else reads as the sparse "unbound" default: zero when
+/// the device reports `residencyNonResidentStrict`, undefined otherwise).
+pub struct SparseImage {
+    pub raw: vk::Image,
+    pub desc: ImageDesc,
+    pub sparse_memory_requirements: Vec<vk::SparseImageMemoryRequirements>,
+    bound_pages: Vec<BoundPage>,
+}
+
+struct BoundPage {
+    region: vk::SparseImageMemoryBind,
+    memory: vk::DeviceMemory,
+}
+
+impl Device {
+    pub fn create_sparse_image(&self, desc: ImageDesc) -> BackendResult<SparseImage> {
+        let create_info = vk::ImageCreateInfo::builder()
+            .image_type(desc.image_type)
+            .format(desc.format)
+            .extent(vk::Extent3D {
+                width: desc.extent[0],
+                height: desc.extent[1],
+                depth: desc.extent[2],
+            })
+            .mip_levels(desc.mip_levels)
+            .array_layers(desc.array_elements)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .usage(desc.usage)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .flags(vk::ImageCreateFlags::SPARSE_BINDING | vk::ImageCreateFlags::SPARSE_RESIDENCY);
+
+        let raw = unsafe { self.raw().create_image(&create_info, None)? };
+        let sparse_memory_requirements =
+            unsafe { self.raw().get_image_sparse_memory_requirements(raw) };
+
+        Ok(SparseImage {
+            raw,
+            desc,
+            sparse_memory_requirements,
+            bound_pages: Vec::new(),
+        })
+    }
+}
+
+impl SparseImage {
+    /// Allocates memory for, and binds, a single mip/layer page. Queued up
+    /// with other pages and flushed via [`Device::flush_sparse_bindings`] —
+    /// sparse binds are a queue operation, not a direct device call.
+    pub fn stage_page_bind(
+        &mut self,
+        device: &Device,
+        offset: vk::Offset3D,
+        extent: vk::Extent3D,
+        subresource: vk::ImageSubresource,
+        page_size: u64,
+    ) -> BackendResult<()> {
+        let requirements = vk::MemoryRequirements {
+            size: page_size,
+            alignment: self
+                .sparse_memory_requirements
+                .first()
+                .map(|r| r.format_properties.image_granularity.width as u64)
+                .unwrap_or(1),
+            memory_type_bits: u32::MAX,
+        };
+        let memory = device.allocate_device_local(requirements)?;
+
+        self.bound_pages.push(BoundPage {
+            region: vk::SparseImageMemoryBind {
+                subresource,
+                offset,
+                extent,
+                memory,
+                memory_offset: 0,
+                flags: vk::SparseMemoryBindFlags::empty(),
+            },
+            memory,
+        });
+
+        Ok(())
+    }
+
+    /// Number of pages staged via [`SparseImage::stage_page_bind`] so far,
+    /// flushed or not, used by the streaming budget to know how much
+    /// device memory this texture currently holds.
+    pub fn resident_page_count(&self) -> usize {
+        self.bound_pages.len()
+    }
+}
+
+impl Device {
+    /// Submits every page staged via [`SparseImage::stage_page_bind`] so
+    /// far as a single `vkQueueBindSparse`. Staged pages are kept after a
+    /// flush, so pages an earlier flush already bound are bound again.
+    pub fn flush_sparse_bindings(&self, image: &SparseImage) -> BackendResult<()> {
+        if image.bound_pages.is_empty() {
+            return Ok(());
+        }
+
+        let binds: Vec<vk::SparseImageMemoryBind> =
+            image.bound_pages.iter().map(|page| page.region).collect();
+
+        let image_bind = vk::SparseImageMemoryBindInfo::builder()
+            .image(image.raw)
+            .binds(&binds);
+
+        let bind_info = vk::BindSparseInfo::builder()
+            .image_binds(std::slice::from_ref(&image_bind));
+
+        unsafe {
+            self.raw()
+                .queue_bind_sparse(self.queue, std::slice::from_ref(&bind_info), vk::Fence::null())?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use ash::vk;
+
+use super::dynamic_state::DynamicStateValues;
+
+/// Splits a render target into up to 4 equal viewports for local
+/// multiplayer, laid out the way every split-screen game lays them out:
+/// 1 player is the full screen, 2 is a horizontal split, 3 and 4 are a
+/// 2x2 grid (with 3 players, the bottom-right cell is left black).
+///
+/// This renders each player's view with its own `vkCmdSetViewport`/
+/// `vkCmdSetScissor` pair rather than Vulkan's `multiViewport` feature:
+/// splitscreen cameras need genuinely different view/projection matrices
+/// per draw, which `multiViewport` (same draw call, N viewports via
+/// `gl_ViewportIndex`) doesn't help with, so there is no reason to take
+/// on the extra device feature requirement.
+pub struct SplitScreenLayout {
+    pub viewports: Vec<DynamicStateValues>,
+}
+
+impl SplitScreenLayout {
+    pub fn new(extent: [u32; 2], player_count: usize) -> Self {
+        let [width, height] = extent;
+        let cells: &[[u32; 4]] = match player_count {
+            0 | 1 => &[[0, 0, width, height]],
+            2 => &[
+                [0, 0, width / 2, height],
+                [width / 2, 0, width - width / 2, height],
+            ],
+            _ => &[
+                [0, 0, width / 2, height / 2],
+                [width / 2, 0, width - width / 2, height / 2],
+                [0, height / 2, width / 2, height - height / 2],
+                [
+                    width / 2,
+                    height / 2,
+                    width - width / 2,
+                    height - height / 2,
+                ],
+            ],
+        };
+
+        let viewports = cells
+            .iter()
+            .take(player_count.max(1))
+            .map(|&[x, y, w, h]| viewport_for_cell(x, y, w, h))
+            .collect();
+
+        Self { viewports }
+    }
+}
+
+fn viewport_for_cell(x: u32, y: u32, width: u32, height: u32) -> DynamicStateValues {
+    DynamicStateValues {
+        viewport: vk::Viewport {
+            x: x as f32,
+            y: y as f32,
+            width: width as f32,
+            height: height as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        },
+        scissor: vk::Rect2D {
+            offset: vk::Offset2D {
+                x: x as i32,
+                y: y as i32,
+            },
+            extent: vk::Extent2D { width, height },
+        },
+        line_width: 1.0,
+        blend_constants: [0.0, 0.0, 0.0, 0.0],
+    }
+}
@@ -0,0 +1,73 @@
+use ash::vk;
+use glam::Mat4;
+
+/// The screen rotation relative to the swapchain images' natural
+/// orientation, as reported by `vk::SurfaceCapabilitiesKHR::current_transform`.
+/// Every desktop platform kiri targets reports `Identity`; Android is the
+/// practical case where this matters, since its compositor expects the app
+/// to pre-rotate its output rather than paying a compositor blit every
+/// frame to do it for you.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SurfaceTransform {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+impl SurfaceTransform {
+    /// Converts `current_transform` from `vk::SurfaceCapabilitiesKHR`. A
+    /// mirrored transform (rare outside oddball embedded compositors)
+    /// falls back to `Identity` with a warning rather than silently
+    /// mis-rotating every frame, since kiri's projection layer has no
+    /// mirroring support to pair with it.
+    pub fn from_vk(transform: vk::SurfaceTransformFlagsKHR) -> Self {
+        match transform {
+            vk::SurfaceTransformFlagsKHR::IDENTITY => SurfaceTransform::Identity,
+            vk::SurfaceTransformFlagsKHR::ROTATE_90 => SurfaceTransform::Rotate90,
+            vk::SurfaceTransformFlagsKHR::ROTATE_180 => SurfaceTransform::Rotate180,
+            vk::SurfaceTransformFlagsKHR::ROTATE_270 => SurfaceTransform::Rotate270,
+            other => {
+                log::warn!(
+                    "Surface transform {other:?} has no pre-rotation support, falling back to identity"
+                );
+                SurfaceTransform::Identity
+            }
+        }
+    }
+
+    /// The `vk::SurfaceTransformFlagsKHR` to request as `pre_transform`
+    /// when creating a swapchain for this transform.
+    pub fn to_vk(self) -> vk::SurfaceTransformFlagsKHR {
+        match self {
+            SurfaceTransform::Identity => vk::SurfaceTransformFlagsKHR::IDENTITY,
+            SurfaceTransform::Rotate90 => vk::SurfaceTransformFlagsKHR::ROTATE_90,
+            SurfaceTransform::Rotate180 => vk::SurfaceTransformFlagsKHR::ROTATE_180,
+            SurfaceTransform::Rotate270 => vk::SurfaceTransformFlagsKHR::ROTATE_270,
+        }
+    }
+
+    /// Whether the swapchain's image extent needs its width and height
+    /// swapped relative to the window's logical size — true for a 90/270
+    /// degree pre-rotation, since the buffer is allocated in the
+    /// pre-rotated (physical panel) orientation.
+    pub fn swaps_extent_dimensions(self) -> bool {
+        matches!(self, SurfaceTransform::Rotate90 | SurfaceTransform::Rotate270)
+    }
+
+    /// The rotation the projection/camera layer must concatenate onto its
+    /// projection matrix so on-screen output looks upright. This is what
+    /// makes requesting a non-identity `pre_transform` correct instead of
+    /// just producing a sideways image: telling the presentation engine
+    /// "my content is already rotated" only avoids the compositor's blit
+    /// if the content actually is.
+    pub fn pre_rotation_matrix(self) -> Mat4 {
+        let angle = match self {
+            SurfaceTransform::Identity => 0.0,
+            SurfaceTransform::Rotate90 => -std::f32::consts::FRAC_PI_2,
+            SurfaceTransform::Rotate180 => -std::f32::consts::PI,
+            SurfaceTransform::Rotate270 => -3.0 * std::f32::consts::FRAC_PI_2,
+        };
+        Mat4::from_rotation_z(angle)
+    }
+}
@@ -1,3 +1,409 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:f020610312f9e9f98156cb964777d7f3588e62041f58976d6691aef0ee5ba30c
-size 11019
+use std::sync::Arc;
+
+use ash::extensions::khr;
+use ash::vk;
+use glam::Mat4;
+
+use crate::BackendResult;
+
+use super::device::Device;
+use super::instance::Instance;
+use super::surface_transform::SurfaceTransform;
+
+/// How aggressively the presentation engine is allowed to get ahead of
+/// the GPU. `Mailbox` trades a bit of extra memory for lower latency than
+/// `Fifo` when the platform supports it; [`Device::create_swapchain`]
+/// falls back to `Fifo` (always supported) otherwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PresentMode {
+    Fifo,
+    Mailbox,
+}
+
+/// Bounds [`Device::create_swapchain`] clamps `SwapchainDesc::image_count`
+/// into, before further clamping to whatever the surface actually
+/// supports. Below 2, there's no double-buffering; above 8 there's no
+/// realistic present mode that benefits from it and it just costs memory.
+pub const MIN_SWAPCHAIN_IMAGES: u32 = 2;
+pub const MAX_SWAPCHAIN_IMAGES: u32 = 8;
+
+#[derive(Clone, Copy, Debug)]
+pub struct SwapchainDesc {
+    pub extent: vk::Extent2D,
+    pub present_mode: PresentMode,
+    pub image_count: u32,
+}
+
+impl Default for SwapchainDesc {
+    fn default() -> Self {
+        Self {
+            extent: vk::Extent2D { width: 1, height: 1 },
+            present_mode: PresentMode::Fifo,
+            image_count: 3,
+        }
+    }
+}
+
+impl SwapchainDesc {
+    /// The fewest images and lowest-latency present mode that still avoid
+    /// tearing when the platform supports it: 2 images (the minimum
+    /// double-buffering needs) and `Mailbox`, which lets the GPU keep
+    /// rendering ahead without queuing up presents the way `Fifo` does.
+    /// [`Device::create_swapchain`] falls back to `Fifo` if `Mailbox`
+    /// isn't supported, same as any other `SwapchainDesc`.
+    pub fn low_latency(extent: vk::Extent2D) -> Self {
+        Self {
+            extent,
+            present_mode: PresentMode::Mailbox,
+            image_count: MIN_SWAPCHAIN_IMAGES,
+        }
+    }
+}
+
+/// A swapchain and the per-image views onto it. Doesn't own `surface` —
+/// the caller (`kiri-app`, typically) creates the surface once for the
+/// window's lifetime and passes it to every [`Device::create_swapchain`]
+/// call, including the ones made to recreate the swapchain on resize.
+pub struct Swapchain {
+    device: ash::Device,
+    swapchain_loader: khr::Swapchain,
+    pub raw: vk::SwapchainKHR,
+    pub format: vk::Format,
+    pub extent: vk::Extent2D,
+    pub images: Vec<vk::Image>,
+    pub image_views: Vec<vk::ImageView>,
+    /// `format`'s UNORM/SRGB sibling, and a view of each swapchain image
+    /// in it, when the driver supports viewing this swapchain's images as
+    /// both (see [`super::texture_format::mutable_format_sibling`]).
+    /// Render into these instead of `image_views` to get automatic
+    /// linear-to-sRGB conversion on store regardless of which of the pair
+    /// `format` itself ended up being — `pick_surface_format` prefers an
+    /// SRGB format already, but some platforms (mobile, mainly) only
+    /// reliably expose the UNORM one as presentable.
+    pub srgb_format: Option<vk::Format>,
+    pub srgb_image_views: Vec<vk::ImageView>,
+    /// The pre-rotation the presentation engine expects the image content
+    /// to already have applied, per [`SwapchainDesc`]'s surface at
+    /// creation time (`Identity` on every platform but Android in
+    /// practice). Pass [`Swapchain::pre_rotation_matrix`] to the
+    /// projection/camera layer so what's actually rendered matches what
+    /// this swapchain told the compositor to expect.
+    pub transform: SurfaceTransform,
+}
+
+impl Device {
+    /// Creates a swapchain for `surface`, sized to `desc.extent`.
+    ///
+    /// `desc.extent` must be non-zero in both dimensions — a minimized
+    /// window or an about-to-be-resized surface reports a zero-sized
+    /// extent, and creating a swapchain for one is invalid. Rather than
+    /// panicking or asking the driver to fail this for us, we reject it
+    /// up front: callers (`kiri-app`'s frame loop) are expected to skip
+    /// calling this — and keep presenting nothing — until the surface
+    /// reports a real size again.
+    pub fn create_swapchain(
+        &self,
+        instance: &Arc<Instance>,
+        surface: vk::SurfaceKHR,
+        desc: &SwapchainDesc,
+    ) -> BackendResult<Swapchain> {
+        if desc.extent.width == 0 || desc.extent.height == 0 {
+            return Err(crate::BackendError::Other(
+                "Can't create a swapchain for a zero-sized surface".to_string(),
+            ));
+        }
+
+        let surface_loader = khr::Surface::new(instance.entry(), instance.raw());
+        let swapchain_loader = khr::Swapchain::new(instance.raw(), self.raw());
+
+        let surface_formats = unsafe {
+            surface_loader.get_physical_device_surface_formats(self.physical_device.raw, surface)?
+        };
+        let surface_format = pick_surface_format(&surface_formats);
+
+        let surface_capabilities = unsafe {
+            surface_loader
+                .get_physical_device_surface_capabilities(self.physical_device.raw, surface)?
+        };
+        let requested_image_count = desc.image_count.clamp(MIN_SWAPCHAIN_IMAGES, MAX_SWAPCHAIN_IMAGES);
+        let image_count = requested_image_count.max(surface_capabilities.min_image_count).min(
+            if surface_capabilities.max_image_count == 0 {
+                u32::MAX
+            } else {
+                surface_capabilities.max_image_count
+            },
+        );
+
+        let present_modes = unsafe {
+            surface_loader
+                .get_physical_device_surface_present_modes(self.physical_device.raw, surface)?
+        };
+        let present_mode = pick_present_mode(&present_modes, desc.present_mode);
+
+        let srgb_sibling = super::texture_format::mutable_format_sibling(surface_format.format);
+        let view_formats = [surface_format.format, srgb_sibling.unwrap_or(surface_format.format)];
+        let mut format_list = vk::ImageFormatListCreateInfo::builder().view_formats(&view_formats);
+
+        let transform = SurfaceTransform::from_vk(surface_capabilities.current_transform);
+        // When the swapchain is pre-rotated (Android, typically), the
+        // buffer is allocated in the physical panel's orientation, which
+        // has width/height swapped relative to the window's logical size
+        // for a 90/270 degree rotation.
+        let image_extent = if transform.swaps_extent_dimensions() {
+            vk::Extent2D {
+                width: desc.extent.height,
+                height: desc.extent.width,
+            }
+        } else {
+            desc.extent
+        };
+
+        let mut create_info = vk::SwapchainCreateInfoKHR::builder()
+            .surface(surface)
+            .min_image_count(image_count)
+            .image_format(surface_format.format)
+            .image_color_space(surface_format.color_space)
+            .image_extent(image_extent)
+            .image_array_layers(1)
+            .image_usage(vk::ImageUsageFlags::COLOR_ATTACHMENT)
+            .image_sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .pre_transform(transform.to_vk())
+            .composite_alpha(vk::CompositeAlphaFlagsKHR::OPAQUE)
+            .present_mode(present_mode)
+            .clipped(true);
+
+        if srgb_sibling.is_some() {
+            create_info = create_info
+                .flags(vk::SwapchainCreateFlagsKHR::MUTABLE_FORMAT)
+                .push_next(&mut format_list);
+        }
+
+        let raw = unsafe { swapchain_loader.create_swapchain(&create_info, None)? };
+        let images = unsafe { swapchain_loader.get_swapchain_images(raw)? };
+        let image_views = images
+            .iter()
+            .map(|&image| self.create_swapchain_image_view(image, surface_format.format))
+            .collect::<BackendResult<Vec<_>>>()?;
+        // Whichever of the pair is the SRGB one, so rendering through
+        // `srgb_image_views` always encodes on store.
+        let srgb_format = srgb_sibling.map(|sibling| match surface_format.format {
+            vk::Format::B8G8R8A8_SRGB | vk::Format::R8G8B8A8_SRGB => surface_format.format,
+            _ => sibling,
+        });
+        let srgb_image_views = match srgb_format {
+            Some(srgb_format) => images
+                .iter()
+                .map(|&image| self.create_swapchain_image_view(image, srgb_format))
+                .collect::<BackendResult<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+
+        Ok(Swapchain {
+            device: self.raw().clone(),
+            swapchain_loader,
+            raw,
+            format: surface_format.format,
+            extent: image_extent,
+            srgb_format,
+            srgb_image_views,
+            transform,
+            images,
+            image_views,
+        })
+    }
+
+    fn create_swapchain_image_view(
+        &self,
+        image: vk::Image,
+        format: vk::Format,
+    ) -> BackendResult<vk::ImageView> {
+        let create_info = vk::ImageViewCreateInfo::builder()
+            .image(image)
+            .view_type(vk::ImageViewType::TYPE_2D)
+            .format(format)
+            .subresource_range(vk::ImageSubresourceRange {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                base_mip_level: 0,
+                level_count: 1,
+                base_array_layer: 0,
+                layer_count: 1,
+            });
+
+        Ok(unsafe { self.raw().create_image_view(&create_info, None)? })
+    }
+}
+
+impl Swapchain {
+    /// Signals `semaphore` when the returned image index is ready to be
+    /// rendered into. `u64::MAX` timeout: the frame loop has nothing
+    /// better to do while waiting, since there's no other image to
+    /// render into meanwhile.
+    ///
+    /// The spec makes no guarantee about the order in which image indices
+    /// come back — nothing ties them to call order or to the semaphore
+    /// passed in. Don't assume the returned index cycles predictably;
+    /// use [`SwapchainSync`] to manage acquire/finished semaphores
+    /// correctly regardless of what order this returns.
+    pub fn acquire_next_image(&self, semaphore: vk::Semaphore) -> BackendResult<u32> {
+        let (index, _suboptimal) = unsafe {
+            self.swapchain_loader
+                .acquire_next_image(self.raw, u64::MAX, semaphore, vk::Fence::null())?
+        };
+        Ok(index)
+    }
+
+    /// The rotation the projection/camera layer must concatenate onto its
+    /// projection matrix to compensate for [`Swapchain::transform`], so
+    /// the compositor doesn't have to rotate the presented image itself.
+    /// Identity on every platform kiri targets except Android.
+    pub fn pre_rotation_matrix(&self) -> Mat4 {
+        self.transform.pre_rotation_matrix()
+    }
+
+    /// Fires `device`'s registered before-submit/after-present frame
+    /// hooks (see [`Device::register_before_submit_hook`]/
+    /// [`Device::register_after_present_hook`]) immediately before and
+    /// after the present call — the only choke point this crate owns
+    /// that every frame necessarily passes through, since command buffer
+    /// recording and submission otherwise happen entirely in caller code.
+    pub fn present(
+        &self,
+        device: &Device,
+        queue: vk::Queue,
+        image_index: u32,
+        wait_semaphore: vk::Semaphore,
+    ) -> BackendResult<()> {
+        let wait_semaphores = [wait_semaphore];
+        let swapchains = [self.raw];
+        let image_indices = [image_index];
+
+        let present_info = vk::PresentInfoKHR::builder()
+            .wait_semaphores(&wait_semaphores)
+            .swapchains(&swapchains)
+            .image_indices(&image_indices);
+
+        device.run_before_submit_hooks();
+        unsafe {
+            self.swapchain_loader.queue_present(queue, &present_info)?;
+        }
+        device.run_after_present_hooks();
+        Ok(())
+    }
+}
+
+impl Drop for Swapchain {
+    fn drop(&mut self) {
+        unsafe {
+            for &view in self.image_views.iter().chain(&self.srgb_image_views) {
+                self.device.destroy_image_view(view, None);
+            }
+            self.swapchain_loader.destroy_swapchain(self.raw, None);
+        }
+    }
+}
+
+/// Owns the semaphores an acquire/render/present cycle needs, keyed
+/// correctly for a swapchain that may hand back image indices in an
+/// arbitrary order:
+///
+/// - one acquire semaphore per frame-in-flight slot, rotated round-robin
+///   on every [`SwapchainSync::acquire_next_image`] call — the image index
+///   isn't known until acquire returns, so this can't be keyed by it;
+/// - one render-finished semaphore per swapchain image, indexed by the
+///   image index the submission actually rendered into — this is what
+///   [`Swapchain::present`] waits on, and reusing a frame-slot semaphore
+///   for it is what lets a present race a still-in-flight submission from
+///   a previous use of the same slot when acquisition order isn't purely
+///   sequential.
+pub struct SwapchainSync {
+    device: ash::Device,
+    acquire_semaphores: Vec<vk::Semaphore>,
+    render_finished_semaphores: Vec<vk::Semaphore>,
+    next_acquire: usize,
+}
+
+impl Device {
+    /// Creates a [`SwapchainSync`] for `swapchain`, with `frames_in_flight`
+    /// acquire semaphores and one render-finished semaphore per swapchain
+    /// image.
+    pub fn create_swapchain_sync(
+        &self,
+        swapchain: &Swapchain,
+        frames_in_flight: usize,
+    ) -> BackendResult<SwapchainSync> {
+        let create_semaphore = || unsafe {
+            self.raw().create_semaphore(&vk::SemaphoreCreateInfo::default(), None)
+        };
+
+        let acquire_semaphores = (0..frames_in_flight)
+            .map(|_| create_semaphore())
+            .collect::<Result<Vec<_>, _>>()?;
+        let render_finished_semaphores = (0..swapchain.images.len())
+            .map(|_| create_semaphore())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(SwapchainSync {
+            device: self.raw().clone(),
+            acquire_semaphores,
+            render_finished_semaphores,
+            next_acquire: 0,
+        })
+    }
+}
+
+impl SwapchainSync {
+    /// Acquires the next image from `swapchain`, rotating through this
+    /// sync's acquire semaphores round-robin. Returns the acquired image
+    /// index and the semaphore that will be signaled once it's safe to
+    /// render into — pass both the index and this semaphore on to the
+    /// submission that renders this frame.
+    pub fn acquire_next_image(&mut self, swapchain: &Swapchain) -> BackendResult<(u32, vk::Semaphore)> {
+        let acquire_semaphore = self.acquire_semaphores[self.next_acquire];
+        self.next_acquire = (self.next_acquire + 1) % self.acquire_semaphores.len();
+
+        let image_index = swapchain.acquire_next_image(acquire_semaphore)?;
+        Ok((image_index, acquire_semaphore))
+    }
+
+    /// The semaphore the submission rendering into `image_index` should
+    /// signal, and [`Swapchain::present`] should wait on. Indexed by
+    /// swapchain image, not frame-in-flight slot, since that's what
+    /// `present` actually needs to be correct.
+    pub fn render_finished_semaphore(&self, image_index: u32) -> vk::Semaphore {
+        self.render_finished_semaphores[image_index as usize]
+    }
+}
+
+impl Drop for SwapchainSync {
+    fn drop(&mut self) {
+        unsafe {
+            for &semaphore in self.acquire_semaphores.iter().chain(&self.render_finished_semaphores) {
+                self.device.destroy_semaphore(semaphore, None);
+            }
+        }
+    }
+}
+
+fn pick_surface_format(formats: &[vk::SurfaceFormatKHR]) -> vk::SurfaceFormatKHR {
+    formats
+        .iter()
+        .find(|format| {
+            format.format == vk::Format::B8G8R8A8_SRGB
+                && format.color_space == vk::ColorSpaceKHR::SRGB_NONLINEAR
+        })
+        .copied()
+        .or_else(|| formats.first().copied())
+        .unwrap_or(vk::SurfaceFormatKHR {
+            format: vk::Format::B8G8R8A8_SRGB,
+            color_space: vk::ColorSpaceKHR::SRGB_NONLINEAR,
+        })
+}
+
+fn pick_present_mode(available: &[vk::PresentModeKHR], preferred: PresentMode) -> vk::PresentModeKHR {
+    let wants_mailbox = preferred == PresentMode::Mailbox;
+    if wants_mailbox && available.contains(&vk::PresentModeKHR::MAILBOX) {
+        vk::PresentModeKHR::MAILBOX
+    } else {
+        vk::PresentModeKHR::FIFO
+    }
+}
@@ -0,0 +1,147 @@
+//! Fence and semaphore recycling, so code that needs a one-off sync
+//! primitive (an immediate submit, a compute pass waiting on a previous
+//! one, ...) doesn't have to create and destroy a fresh `vk::Fence` or
+//! `vk::Semaphore` every time — see [`super::frame::Frame`] and
+//! [`super::swapchain::SwapchainSync`] for the ad hoc creation this is
+//! meant to offer an alternative to for new code.
+//!
+//! [`GpuFuture`] wraps a pooled fence as a "this work is done" handle a
+//! caller can poll or block on, then hand back to the pool instead of
+//! destroying.
+
+use ash::vk;
+
+use crate::{BackendError, BackendResult};
+
+use super::device::Device;
+
+#[derive(Default)]
+pub(crate) struct SyncPool {
+    free_fences: Vec<vk::Fence>,
+    free_semaphores: Vec<vk::Semaphore>,
+}
+
+impl Device {
+    /// Pops a fence off the pool, resetting it to the unsignaled state, or
+    /// creates a new one if the pool is empty. `name` is set via
+    /// [`Device::set_debug_name`] when given — useful since a pooled
+    /// fence's name would otherwise still say whatever the last borrower
+    /// called it.
+    pub fn acquire_fence(&self, name: Option<&str>) -> BackendResult<vk::Fence> {
+        let mut pool = self.sync_pool.lock().unwrap();
+
+        let fence = match pool.free_fences.pop() {
+            Some(fence) => {
+                unsafe { self.raw().reset_fences(&[fence])? };
+                fence
+            }
+            None => unsafe { self.raw().create_fence(&vk::FenceCreateInfo::default(), None)? },
+        };
+
+        if let Some(name) = name {
+            self.set_debug_name(fence, name);
+        }
+
+        Ok(fence)
+    }
+
+    /// Returns a fence acquired via [`Device::acquire_fence`] to the pool
+    /// for reuse. The fence must not be in use by any pending submission.
+    pub fn release_fence(&self, fence: vk::Fence) {
+        self.sync_pool.lock().unwrap().free_fences.push(fence);
+    }
+
+    /// Pops a semaphore off the pool, or creates a new one if the pool is
+    /// empty. Binary semaphores need no reset between uses — their
+    /// signaled state is consumed by the next wait on them.
+    pub fn acquire_semaphore(&self, name: Option<&str>) -> BackendResult<vk::Semaphore> {
+        let mut pool = self.sync_pool.lock().unwrap();
+
+        let semaphore = match pool.free_semaphores.pop() {
+            Some(semaphore) => semaphore,
+            None => unsafe {
+                self.raw()
+                    .create_semaphore(&vk::SemaphoreCreateInfo::default(), None)?
+            },
+        };
+
+        if let Some(name) = name {
+            self.set_debug_name(semaphore, name);
+        }
+
+        Ok(semaphore)
+    }
+
+    /// Returns a semaphore acquired via [`Device::acquire_semaphore`] to
+    /// the pool for reuse. The semaphore must not be in use by any pending
+    /// submission or wait.
+    pub fn release_semaphore(&self, semaphore: vk::Semaphore) {
+        self.sync_pool.lock().unwrap().free_semaphores.push(semaphore);
+    }
+
+    /// Destroys every pooled fence and semaphore. Called on device
+    /// teardown — `ToDrop` has no fence/semaphore variants, so unlike
+    /// queued resources these are destroyed directly rather than via the
+    /// drop list, which is fine since nothing in the pool is in flight by
+    /// the time the device is dropped.
+    pub fn clear_sync_pool(&self) {
+        let mut pool = self.sync_pool.lock().unwrap();
+        unsafe {
+            for fence in pool.free_fences.drain(..) {
+                self.raw().destroy_fence(fence, None);
+            }
+            for semaphore in pool.free_semaphores.drain(..) {
+                self.raw().destroy_semaphore(semaphore, None);
+            }
+        }
+    }
+}
+
+/// A handle to submitted GPU work that a caller can poll or block on,
+/// backed by a fence acquired from [`Device::acquire_fence`]. Call
+/// [`GpuFuture::release`] once the work is known done (after a successful
+/// [`GpuFuture::wait`], or a [`GpuFuture::poll`] returning `true`) to
+/// return the fence to the pool instead of leaking it.
+pub struct GpuFuture {
+    fence: vk::Fence,
+}
+
+impl GpuFuture {
+    /// Wraps a fence that's been passed to `queue_submit` (or is about to
+    /// be) as a `GpuFuture`. The caller is responsible for actually
+    /// submitting the work this fence signals.
+    pub fn new(fence: vk::Fence) -> Self {
+        Self { fence }
+    }
+
+    /// The raw fence, for passing to `queue_submit`.
+    pub fn raw(&self) -> vk::Fence {
+        self.fence
+    }
+
+    /// Non-blocking check for whether the work is done.
+    pub fn poll(&self, device: &Device) -> BackendResult<bool> {
+        match unsafe { device.raw().get_fence_status(self.fence) } {
+            Ok(signaled) => Ok(signaled),
+            Err(vk::Result::NOT_READY) => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Blocks until the work is done or `timeout_ns` nanoseconds have
+    /// passed, returning whether it finished in time.
+    pub fn wait(&self, device: &Device, timeout_ns: u64) -> BackendResult<bool> {
+        match unsafe { device.raw().wait_for_fences(&[self.fence], true, timeout_ns) } {
+            Ok(()) => Ok(true),
+            Err(vk::Result::TIMEOUT) => Ok(false),
+            Err(err) => Err(BackendError::from(err)),
+        }
+    }
+
+    /// Returns this future's fence to `device`'s sync pool. The work must
+    /// already be done — [`GpuFuture::wait`] or a `true` result from
+    /// [`GpuFuture::poll`] first.
+    pub fn release(self, device: &Device) {
+        device.release_fence(self.fence);
+    }
+}
@@ -0,0 +1,200 @@
+use kiri_assets::image::{ImageAsset, ImageFormat};
+
+use super::device::Device;
+
+impl Device {
+    /// Whether this device can sample `format` directly, per
+    /// [`super::format_policy::FormatPolicy`]'s capability table.
+    pub fn supports_image_format(&self, format: ImageFormat) -> bool {
+        match format {
+            ImageFormat::Rgba8Unorm | ImageFormat::Rgba8Srgb => true,
+            ImageFormat::Bc7Unorm | ImageFormat::Bc7Srgb => self.format_policy.supports_bc7,
+            ImageFormat::Astc4x4Unorm | ImageFormat::Astc4x4Srgb => {
+                self.format_policy.supports_astc_4x4
+            }
+        }
+    }
+
+    /// Returns `asset` unchanged if this device can sample its baked
+    /// format, or a CPU-decompressed RGBA8 copy if not — the common case
+    /// being a mobile chip with no BC7 sampling support loading a
+    /// desktop-baked bundle, so one bundle still runs (at a memory and
+    /// bandwidth cost) instead of failing to load.
+    ///
+    /// Only BC7 has a decompressor today ([`decompress_bc7_to_rgba8`]).
+    /// An ASTC-baked bundle hitting a device without ASTC support has no
+    /// transcode path and is returned unchanged, which will fail upload —
+    /// ASTC is only ever baked for targets that already require it, so
+    /// this gap hasn't come up in practice.
+    pub fn ensure_supported_format(&self, asset: &ImageAsset) -> ImageAsset {
+        if self.supports_image_format(asset.format) {
+            return asset.clone();
+        }
+
+        match asset.format {
+            ImageFormat::Bc7Unorm => decompress_bc7_to_rgba8(asset, ImageFormat::Rgba8Unorm),
+            ImageFormat::Bc7Srgb => decompress_bc7_to_rgba8(asset, ImageFormat::Rgba8Srgb),
+            _ => asset.clone(),
+        }
+    }
+}
+
+/// Decompresses a BC7 [`ImageAsset`] to `rgba_format` (must be
+/// [`ImageFormat::Rgba8Unorm`] or [`ImageFormat::Rgba8Srgb`] — the
+/// decompressed bytes are identical either way, only the format tag the
+/// backend uploads them with differs).
+///
+/// Only BC7 mode 6 (single subset, per-endpoint P-bit, no partitioning or
+/// rotation) is decoded exactly; encoders overwhelmingly reach for the
+/// higher-partition-count modes only when a single subset can't represent
+/// a block well, so mode 6 covers the flatter regions of most textures.
+/// Blocks in any other mode decode to solid mid-gray, opaque — visibly
+/// wrong on high-frequency detail, but a correct decoder for every mode
+/// is a substantial chunk of the BC7 spec on its own and isn't justified
+/// until a real device without BC7 support actually needs this path.
+pub fn decompress_bc7_to_rgba8(asset: &ImageAsset, rgba_format: ImageFormat) -> ImageAsset {
+    debug_assert!(matches!(asset.format, ImageFormat::Bc7Unorm | ImageFormat::Bc7Srgb));
+    debug_assert!(matches!(rgba_format, ImageFormat::Rgba8Unorm | ImageFormat::Rgba8Srgb));
+
+    let mips = asset
+        .mips
+        .iter()
+        .enumerate()
+        .map(|(mip_level, blocks)| {
+            let extent = mip_extent(asset.extent, mip_level as u32);
+            decompress_bc7_mip(blocks, extent)
+        })
+        .collect();
+
+    ImageAsset {
+        extent: asset.extent,
+        format: rgba_format,
+        mips,
+    }
+}
+
+fn mip_extent(base_extent: [u32; 2], mip_level: u32) -> [u32; 2] {
+    [
+        (base_extent[0] >> mip_level).max(1),
+        (base_extent[1] >> mip_level).max(1),
+    ]
+}
+
+fn decompress_bc7_mip(blocks: &[u8], extent: [u32; 2]) -> Vec<u8> {
+    let blocks_wide = ((extent[0] + 3) / 4).max(1);
+    let blocks_high = ((extent[1] + 3) / 4).max(1);
+
+    let mut rgba = vec![0u8; extent[0] as usize * extent[1] as usize * 4];
+
+    for block_y in 0..blocks_high {
+        for block_x in 0..blocks_wide {
+            let block_index = (block_y * blocks_wide + block_x) as usize;
+            let block = &blocks[block_index * 16..block_index * 16 + 16];
+            let texels = decode_bc7_block(block);
+
+            for local_y in 0..4u32 {
+                let y = block_y * 4 + local_y;
+                if y >= extent[1] {
+                    continue;
+                }
+                for local_x in 0..4u32 {
+                    let x = block_x * 4 + local_x;
+                    if x >= extent[0] {
+                        continue;
+                    }
+                    let src = (local_y * 4 + local_x) as usize * 4;
+                    let dst = (y * extent[0] + x) as usize * 4;
+                    rgba[dst..dst + 4].copy_from_slice(&texels[src..src + 4]);
+                }
+            }
+        }
+    }
+
+    rgba
+}
+
+/// BC7 4-bit index interpolation weights out of 64, per the spec's Table 12.
+const INDEX_WEIGHTS_4BIT: [u32; 16] = [
+    0, 4, 9, 13, 17, 21, 26, 30, 34, 38, 43, 47, 51, 55, 60, 64,
+];
+
+fn decode_bc7_block(block: &[u8]) -> [u8; 4 * 4 * 4] {
+    let mode = block[0].trailing_zeros().min(8);
+
+    if mode != 6 {
+        // Unsupported mode — see `decompress_bc7_to_rgba8`'s doc comment.
+        let mut gray = [128u8; 64];
+        for texel in gray.chunks_exact_mut(4) {
+            texel[3] = 255;
+        }
+        return gray;
+    }
+
+    let mut reader = BitReader::new(block);
+    reader.consume(mode + 1); // mode's unary prefix, including the terminating 1 bit
+
+    let mut endpoints = [[0u32; 4]; 2]; // [endpoint][r,g,b,a]
+    for channel in 0..4 {
+        for endpoint in 0..2 {
+            endpoints[endpoint][channel] = reader.read(7);
+        }
+    }
+
+    let p_bits = [reader.read(1), reader.read(1)];
+
+    let mut endpoint_rgba = [[0u8; 4]; 2];
+    for endpoint in 0..2 {
+        for channel in 0..4 {
+            let value = (endpoints[endpoint][channel] << 1) | p_bits[endpoint];
+            endpoint_rgba[endpoint][channel] = value as u8;
+        }
+    }
+
+    let mut indices = [0u32; 16];
+    for (texel, index) in indices.iter_mut().enumerate() {
+        // The first index of the block is stored with one fewer bit — its
+        // implicit leading bit is always 0.
+        *index = reader.read(if texel == 0 { 3 } else { 4 });
+    }
+
+    let mut out = [0u8; 64];
+    for (texel, &index) in indices.iter().enumerate() {
+        let weight = INDEX_WEIGHTS_4BIT[index as usize];
+        for channel in 0..4 {
+            let a = endpoint_rgba[0][channel] as u32;
+            let b = endpoint_rgba[1][channel] as u32;
+            out[texel * 4 + channel] = (((64 - weight) * a + weight * b + 32) >> 6) as u8;
+        }
+    }
+
+    out
+}
+
+/// Reads fixed-width fields out of a byte slice LSB-first, the bit order
+/// every BC7 field is packed in.
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    bit_position: u32,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, bit_position: 0 }
+    }
+
+    fn consume(&mut self, bits: u32) {
+        self.bit_position += bits;
+    }
+
+    fn read(&mut self, bits: u32) -> u32 {
+        let mut value = 0u32;
+        for i in 0..bits {
+            let bit_index = self.bit_position + i;
+            let byte = self.bytes[(bit_index / 8) as usize];
+            let bit = (byte >> (bit_index % 8)) & 1;
+            value |= (bit as u32) << i;
+        }
+        self.bit_position += bits;
+        value
+    }
+}
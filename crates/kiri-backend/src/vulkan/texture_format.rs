@@ -0,0 +1,38 @@
+use ash::vk;
+
+use kiri_assets::image::ImageFormat;
+
+/// Maps a baked [`ImageFormat`] to the `vk::Format` that interprets its
+/// bytes exactly as `kiri-asset-pipe` baked them — respecting whichever
+/// sRGB/UNORM variant was chosen for that asset (albedo textures bake as
+/// `*Srgb`, data textures like normal/roughness maps bake as `*Unorm`).
+pub fn vk_format_for_asset(format: ImageFormat) -> vk::Format {
+    match format {
+        ImageFormat::Rgba8Unorm => vk::Format::R8G8B8A8_UNORM,
+        ImageFormat::Rgba8Srgb => vk::Format::R8G8B8A8_SRGB,
+        ImageFormat::Bc7Unorm => vk::Format::BC7_UNORM_BLOCK,
+        ImageFormat::Bc7Srgb => vk::Format::BC7_SRGB_BLOCK,
+        ImageFormat::Astc4x4Unorm => vk::Format::ASTC_4X4_UNORM_BLOCK,
+        ImageFormat::Astc4x4Srgb => vk::Format::ASTC_4X4_SRGB_BLOCK,
+    }
+}
+
+/// The UNORM/SRGB sibling of `format` — same bits, opposite color-space
+/// interpretation — or `None` if `format` has no such sibling. Used to
+/// build the `mutable_view_formats` list for a
+/// [`super::image::ImageDesc`], letting a texture (or the swapchain, see
+/// `swapchain.rs`) get a view in the other interpretation without
+/// reallocating the underlying image.
+pub fn mutable_format_sibling(format: vk::Format) -> Option<vk::Format> {
+    Some(match format {
+        vk::Format::R8G8B8A8_UNORM => vk::Format::R8G8B8A8_SRGB,
+        vk::Format::R8G8B8A8_SRGB => vk::Format::R8G8B8A8_UNORM,
+        vk::Format::B8G8R8A8_UNORM => vk::Format::B8G8R8A8_SRGB,
+        vk::Format::B8G8R8A8_SRGB => vk::Format::B8G8R8A8_UNORM,
+        vk::Format::BC7_UNORM_BLOCK => vk::Format::BC7_SRGB_BLOCK,
+        vk::Format::BC7_SRGB_BLOCK => vk::Format::BC7_UNORM_BLOCK,
+        vk::Format::ASTC_4X4_UNORM_BLOCK => vk::Format::ASTC_4X4_SRGB_BLOCK,
+        vk::Format::ASTC_4X4_SRGB_BLOCK => vk::Format::ASTC_4X4_UNORM_BLOCK,
+        _ => return None,
+    })
+}
@@ -0,0 +1,250 @@
+use ash::vk;
+
+use kiri_assets::image::ImageAsset;
+
+use crate::BackendResult;
+
+use super::buffer::{Buffer, BufferDesc};
+use super::device::Device;
+use super::image::{aspect_mask_for_format, Image, ImageDesc};
+use super::sync_pool::GpuFuture;
+use super::texture_format::vk_format_for_asset;
+
+/// A texture whose full-resolution data streams in behind an
+/// immediately-available coarse mip chain, so a level start (or a newly
+/// streamed-in object) shows a blurry texture rather than hitching on a
+/// full-resolution upload or flashing a
+/// [`kiri_assets::placeholder::checker_texture`] while it waits.
+///
+/// [`Device::create_streaming_texture`] uploads a
+/// [`kiri_assets::mip_streaming::tail_image`] synchronously — small
+/// enough that the stall is negligible — and
+/// [`StreamingTexture::begin_upgrade`] kicks off the full upload without
+/// blocking. [`StreamingTexture::poll_upgrade`], called once per frame,
+/// swaps `current` for it the first frame the upload's fence is
+/// signaled; [`StreamingTexture::view`] returns whichever image is
+/// current, so a descriptor re-bound after a swap starts sampling the
+/// upgraded texture with no special-casing at the call site.
+pub struct StreamingTexture {
+    current: Image,
+    pending: Option<PendingUpgrade>,
+}
+
+struct PendingUpgrade {
+    image: Image,
+    staging: Buffer,
+    command_pool: vk::CommandPool,
+    future: GpuFuture,
+}
+
+impl Device {
+    /// Uploads only `tail`'s mips synchronously via [`Device::upload_image`]
+    /// — pass it a [`kiri_assets::mip_streaming::tail_image`] of the full
+    /// texture so the upload is small, then call
+    /// [`StreamingTexture::begin_upgrade`] with the full [`ImageAsset`]
+    /// once it's available to stream the rest in behind it.
+    pub fn create_streaming_texture(&self, tail: &ImageAsset, name: &str) -> BackendResult<StreamingTexture> {
+        let current = self.upload_image(tail, name)?;
+        Ok(StreamingTexture { current, pending: None })
+    }
+
+    /// Builds `image`, stages `asset`'s mips into it, and submits the
+    /// upload without waiting — the non-blocking counterpart to
+    /// [`Device::upload_image`], used by [`StreamingTexture::begin_upgrade`]
+    /// so the upgrade doesn't stall the frame that kicks it off. The
+    /// returned [`GpuFuture`] must be polled (or waited) done before the
+    /// command pool backing the submission is safe to destroy — see
+    /// [`StreamingTexture::poll_upgrade`].
+    fn upload_image_async(&self, asset: &ImageAsset, name: &str) -> BackendResult<PendingUpgrade> {
+        let asset = self.ensure_supported_format(asset);
+        let format = vk_format_for_asset(asset.format);
+        let mip_count = asset.mip_count() as u32;
+
+        let image = self.create_image(
+            ImageDesc::new_2d(format, asset.extent)
+                .mip_levels(mip_count)
+                .usage(vk::ImageUsageFlags::SAMPLED | vk::ImageUsageFlags::TRANSFER_DST),
+        )?;
+        self.set_debug_name(image.raw, name);
+
+        let total_size: usize = asset.mips.iter().map(|mip| mip.len()).sum();
+        let staging = self.create_buffer(BufferDesc::new_cpu_to_gpu(
+            total_size,
+            vk::BufferUsageFlags::TRANSFER_SRC,
+        ))?;
+
+        let mut packed = Vec::with_capacity(total_size);
+        let mut mip_offsets = Vec::with_capacity(asset.mips.len());
+        for mip in &asset.mips {
+            mip_offsets.push(packed.len() as u64);
+            packed.extend_from_slice(mip);
+        }
+        staging.write(self, &packed)?;
+
+        let aspect_mask = aspect_mask_for_format(format);
+        let subresource_range = vk::ImageSubresourceRange {
+            aspect_mask,
+            base_mip_level: 0,
+            level_count: mip_count,
+            base_array_layer: 0,
+            layer_count: 1,
+        };
+
+        let command_pool = unsafe {
+            self.raw().create_command_pool(
+                &vk::CommandPoolCreateInfo::builder()
+                    .queue_family_index(self.queue_family_index)
+                    .flags(vk::CommandPoolCreateFlags::TRANSIENT),
+                None,
+            )?
+        };
+        let command_buffer = unsafe {
+            self.raw().allocate_command_buffers(
+                &vk::CommandBufferAllocateInfo::builder()
+                    .command_pool(command_pool)
+                    .level(vk::CommandBufferLevel::PRIMARY)
+                    .command_buffer_count(1),
+            )?[0]
+        };
+
+        unsafe {
+            self.raw().begin_command_buffer(
+                command_buffer,
+                &vk::CommandBufferBeginInfo::builder()
+                    .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT),
+            )?;
+
+            let to_transfer_dst = vk::ImageMemoryBarrier::builder()
+                .old_layout(vk::ImageLayout::UNDEFINED)
+                .new_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+                .src_access_mask(vk::AccessFlags::empty())
+                .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+                .image(image.raw)
+                .subresource_range(subresource_range);
+
+            self.raw().cmd_pipeline_barrier(
+                command_buffer,
+                vk::PipelineStageFlags::TOP_OF_PIPE,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[],
+                std::slice::from_ref(&to_transfer_dst),
+            );
+        }
+
+        for (mip_level, mip_offset) in mip_offsets.iter().enumerate() {
+            let mip_extent = [
+                (asset.extent[0] >> mip_level).max(1),
+                (asset.extent[1] >> mip_level).max(1),
+                1,
+            ];
+            image.record_copy_from_buffer(
+                self,
+                command_buffer,
+                staging.raw,
+                *mip_offset,
+                mip_level as u32,
+                [0, 0, 0],
+                mip_extent,
+            );
+        }
+
+        unsafe {
+            let to_shader_read = vk::ImageMemoryBarrier::builder()
+                .old_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+                .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+                .dst_access_mask(vk::AccessFlags::SHADER_READ)
+                .image(image.raw)
+                .subresource_range(subresource_range);
+
+            self.raw().cmd_pipeline_barrier(
+                command_buffer,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::PipelineStageFlags::FRAGMENT_SHADER,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[],
+                std::slice::from_ref(&to_shader_read),
+            );
+
+            self.raw().end_command_buffer(command_buffer)?;
+        }
+
+        let fence = self.acquire_fence(Some(name))?;
+        let submit_info = vk::SubmitInfo::builder().command_buffers(std::slice::from_ref(&command_buffer));
+        unsafe {
+            self.raw()
+                .queue_submit(self.queue, std::slice::from_ref(&submit_info), fence)?;
+        }
+
+        Ok(PendingUpgrade {
+            image,
+            staging,
+            command_pool,
+            future: GpuFuture::new(fence),
+        })
+    }
+}
+
+impl StreamingTexture {
+    /// The image view to bind this frame — whichever of the coarse
+    /// placeholder or the upgraded texture is currently resident.
+    pub fn view(&self) -> vk::ImageView {
+        self.current.view
+    }
+
+    pub fn is_upgrading(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Starts uploading `full`'s complete mip chain in the background. A
+    /// no-op if an upgrade is already in flight, since this only tracks
+    /// one pending upgrade at a time — callers driving finer-grained
+    /// progressive streaming should wait for
+    /// [`StreamingTexture::poll_upgrade`] to resolve the current one
+    /// before queuing the next.
+    pub fn begin_upgrade(&mut self, device: &Device, full: &ImageAsset, name: &str) -> BackendResult<()> {
+        if self.pending.is_some() {
+            return Ok(());
+        }
+
+        self.pending = Some(device.upload_image_async(full, name)?);
+        Ok(())
+    }
+
+    /// Checks whether an in-flight upgrade has finished and, if so, swaps
+    /// it in as `current` — queuing the old image and the upgrade's
+    /// staging buffer for deferred destruction the same way any other
+    /// retired GPU resource is, and destroying the upgrade's command pool
+    /// right away, since its submission's fence has already signaled.
+    /// Returns `true` the one
+    /// frame the swap happens, so a caller can re-bind descriptors that
+    /// reference [`StreamingTexture::view`] only when it actually changed.
+    pub fn poll_upgrade(&mut self, device: &Device) -> BackendResult<bool> {
+        let ready = match &self.pending {
+            Some(pending) => pending.future.poll(device)?,
+            None => false,
+        };
+        if !ready {
+            return Ok(false);
+        }
+
+        let pending = self.pending.take().expect("just checked Some above");
+        pending.future.release(device);
+        device.queue_drop(pending.staging.raw);
+        device.queue_drop(pending.staging.memory);
+        unsafe {
+            device.raw().destroy_command_pool(pending.command_pool, None);
+        }
+
+        let old = std::mem::replace(&mut self.current, pending.image);
+        old.queue_drop_views(device);
+        device.queue_drop(old.raw);
+        device.queue_drop(old.memory);
+        device.queue_drop(old.view);
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::device::Device;
+use super::image::{Image, ImageDesc};
+
+/// Allocates transient, frame-scoped images out of a shared pool of
+/// `vk::DeviceMemory` blocks, letting two resources alias the same bytes.
+///
+/// This exists for pass-local scratch targets (blur ping-pong buffers,
+/// depth pre-pass targets that die before the next pass needs the memory,
+/// ...) where allocating real, separately-backed memory every frame would
+/// waste a large amount of VRAM for no benefit. Images are bucketed by
+/// (aligned size, memory type index), and every image requested from the
+/// same bucket is bound at offset 0 of that bucket's block; images of
+/// different sizes never share a block, and nothing here looks at the
+/// frame graph or at resource lifetimes. Callers must only request
+/// images from the same bucket whose lifetimes never overlap, and insert
+/// the matching `vk::MemoryBarrier`/image layout transitions themselves
+/// when a resource reuses aliased memory.
+pub struct TransientResourceAllocator {
+    /// One memory block per (size, memory_type_index) bucket allocated so
+    /// far; blocks are kept across frames.
+    blocks: HashMap<(u64, u32), vk::DeviceMemory>,
+    /// Offset the next image in each bucket binds at, cleared on
+    /// `begin_frame`. Always 0: every image in a bucket starts at the
+    /// beginning of that bucket's block.
+    cursor: HashMap<(u64, u32), u64>,
+}
+
+impl TransientResourceAllocator {
+    pub fn new() -> Self {
+        Self {
+            blocks: HashMap::new(),
+            cursor: HashMap::new(),
+        }
+    }
+
+    /// Resets allocation cursors, making every previously-claimed byte
+    /// available for aliasing again. The backing `vk::DeviceMemory` blocks
+    /// themselves are kept around and reused across frames.
+    pub fn begin_frame(&mut self) {
+        self.cursor.clear();
+    }
+
+    /// Creates an image whose memory is carved out of (and may alias) this
+    /// frame's transient pool, rather than being freshly allocated.
+    pub fn create_aliased_image(
+        &mut self,
+        device: &Device,
+        desc: ImageDesc,
+    ) -> BackendResult<Image> {
+        let create_info = vk::ImageCreateInfo::builder()
+            .image_type(desc.image_type)
+            .format(desc.format)
+            .extent(vk::Extent3D {
+                width: desc.extent[0],
+                height: desc.extent[1],
+                depth: desc.extent[2],
+            })
+            .mip_levels(desc.mip_levels)
+            .array_layers(desc.array_elements)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .usage(desc.usage)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .initial_layout(vk::ImageLayout::UNDEFINED);
+
+        let raw = unsafe { device.raw().create_image(&create_info, None)? };
+        let requirements = unsafe { device.raw().get_image_memory_requirements(raw) };
+
+        let memory_type_index = device.device_local_memory_type_index(requirements.memory_type_bits);
+        let aligned_size = align_up(requirements.size, requirements.alignment);
+        let key = (aligned_size.max(requirements.size), memory_type_index);
+
+        let block = match self.blocks.get(&key) {
+            Some(block) => *block,
+            None => {
+                let block = unsafe {
+                    device.raw().allocate_memory(
+                        &vk::MemoryAllocateInfo::builder()
+                            .allocation_size(key.0)
+                            .memory_type_index(key.1),
+                        None,
+                    )?
+                };
+                self.blocks.insert(key, block);
+                block
+            }
+        };
+
+        let offset = *self.cursor.get(&key).unwrap_or(&0);
+        self.cursor.insert(key, 0); // every request this frame aliases offset 0 of its bucket
+
+        unsafe {
+            device.raw().bind_image_memory(raw, block, offset)?;
+        }
+
+        let aspect_mask = super::image::aspect_mask_for_format(desc.format);
+        let view = unsafe {
+            device.raw().create_image_view(
+                &vk::ImageViewCreateInfo::builder()
+                    .image(raw)
+                    .view_type(vk::ImageViewType::TYPE_2D)
+                    .format(desc.format)
+                    .subresource_range(vk::ImageSubresourceRange {
+                        aspect_mask,
+                        base_mip_level: 0,
+                        level_count: desc.mip_levels,
+                        base_array_layer: 0,
+                        layer_count: desc.array_elements,
+                    }),
+                None,
+            )?
+        };
+
+        Ok(Image {
+            raw,
+            memory: block,
+            view,
+            desc,
+        })
+    }
+}
+
+fn align_up(value: u64, alignment: u64) -> u64 {
+    (value + alignment - 1) & !(alignment - 1)
+}
+
+impl Device {
+    pub(crate) fn device_local_memory_type_index(&self, memory_type_bits: u32) -> u32 {
+        let memory_properties = &self.physical_device.memory_properties;
+        (0..memory_properties.memory_type_count)
+            .find(|&index| {
+                let suitable = (memory_type_bits & (1 << index)) != 0;
+                let is_device_local = memory_properties.memory_types[index as usize]
+                    .property_flags
+                    .contains(vk::MemoryPropertyFlags::DEVICE_LOCAL);
+                suitable && is_device_local
+            })
+            .unwrap_or(0)
+    }
+}
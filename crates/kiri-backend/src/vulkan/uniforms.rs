@@ -1,3 +1,259 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:c4311043efa684b94bdf9783f3259b12ce3d4b44b9b1ff6bb6ee838ce98342d3
-size 6809
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::sync::{Arc, Mutex};
+
+use ash::vk;
+
+use crate::{BackendError, BackendResult};
+
+use super::buffer::{Buffer, BufferDesc};
+use super::device::Device;
+
+/// A ring of host-visible memory for small uniform values (transforms,
+/// material constants) that would otherwise each need their own
+/// `vk::Buffer`. Backed by a single buffer and a best-fit free list, so
+/// short-lived allocations get reused instead of bumping the cursor
+/// forever.
+///
+/// Handles freed via `UniformHandle`'s `Drop` aren't immediately safe to
+/// reuse — a previous frame's GPU work might still be reading that byte
+/// range — so frees land in a pending queue first; call
+/// [`UniformStorage::recycle`] once you know it's safe (typically once a
+/// frame, after waiting on that frame-in-flight's fence) to fold them
+/// back into the free list.
+pub struct UniformStorage {
+    buffer: Buffer,
+    capacity: u32,
+    cursor: u32,
+    min_alignment: u32,
+    free_list: Vec<(u32, u32)>,
+    pending_free: Arc<Mutex<Vec<(u32, u32)>>>,
+}
+
+impl Device {
+    /// Creates a [`UniformStorage`] ring backed by `capacity` bytes of
+    /// host-visible memory, respecting this device's
+    /// `minUniformBufferOffsetAlignment`.
+    pub fn create_uniform_storage(&self, capacity: u32) -> BackendResult<UniformStorage> {
+        let min_alignment = self
+            .physical_device
+            .properties
+            .limits
+            .min_uniform_buffer_offset_alignment
+            .max(1) as u32;
+
+        let buffer = self.create_buffer(BufferDesc::new_cpu_to_gpu(
+            capacity as usize,
+            vk::BufferUsageFlags::UNIFORM_BUFFER,
+        ))?;
+
+        Ok(UniformStorage {
+            buffer,
+            capacity,
+            cursor: 0,
+            min_alignment,
+            free_list: Vec::new(),
+            pending_free: Arc::new(Mutex::new(Vec::new())),
+        })
+    }
+}
+
+impl UniformStorage {
+    /// Uploads `value` and returns a [`UniformHandle<T>`] that frees its
+    /// slot automatically when dropped. This is the API almost everything
+    /// should use instead of [`UniformStorage::push`].
+    pub fn push_typed<T: Copy>(&mut self, device: &Device, value: &T) -> BackendResult<UniformHandle<T>> {
+        let size = size_of::<T>() as u32;
+        let bytes =
+            unsafe { std::slice::from_raw_parts(value as *const T as *const u8, size as usize) };
+        let offset = self.push(device, bytes)?;
+
+        Ok(UniformHandle {
+            offset,
+            size,
+            pending_free: self.pending_free.clone(),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Bump- or free-list-allocates room for `data` (aligned to this
+    /// storage's uniform buffer offset alignment) and copies it in,
+    /// returning the raw byte offset. Prefer [`UniformStorage::push_typed`]
+    /// unless you specifically need the untyped offset.
+    pub fn push(&mut self, device: &Device, data: &[u8]) -> BackendResult<u32> {
+        let size = data.len() as u32;
+
+        let offset = if let Some(offset) = self.take_free_block(size) {
+            offset
+        } else {
+            let offset = align_up(self.cursor, self.min_alignment);
+            if offset.checked_add(size).map_or(true, |end| end > self.capacity) {
+                return Err(BackendError::Other(
+                    "UniformStorage ring is out of space".to_string(),
+                ));
+            }
+            self.cursor = offset + size;
+            offset
+        };
+
+        self.buffer.write_at(device, offset as u64, data)?;
+        Ok(offset)
+    }
+
+    /// Finds the smallest free block that fits `size`, splitting off any
+    /// leftover space back into the free list.
+    fn take_free_block(&mut self, size: u32) -> Option<u32> {
+        let (index, _) = self
+            .free_list
+            .iter()
+            .enumerate()
+            .filter(|(_, &(_, block_size))| block_size >= size)
+            .min_by_key(|(_, &(_, block_size))| block_size)?;
+
+        let (offset, block_size) = self.free_list.remove(index);
+        // Claim a whole number of alignment units so a split-off remainder
+        // starts on an aligned offset too.
+        let claimed = align_up(size, self.min_alignment).min(block_size);
+        if block_size > claimed {
+            self.free_list.push((offset + claimed, block_size - claimed));
+        }
+        Some(offset)
+    }
+
+    /// Folds slots freed by dropped [`UniformHandle`]s back into the free
+    /// list, making them available for reuse.
+    ///
+    /// # Safety
+    /// Only safe once none of the pending-freed ranges are still being
+    /// read by in-flight GPU work.
+    pub unsafe fn recycle(&mut self) {
+        let mut pending = self.pending_free.lock().unwrap();
+        self.free_list.append(&mut pending);
+    }
+
+    pub fn raw_buffer(&self) -> vk::Buffer {
+        self.buffer.raw
+    }
+}
+
+/// A typed, RAII handle to one [`UniformStorage::push_typed`] allocation.
+/// Encodes `T`'s size so callers can't accidentally read it back with the
+/// wrong type or stride, and frees its ring slot automatically on drop
+/// instead of requiring the caller to remember to release it.
+pub struct UniformHandle<T> {
+    offset: u32,
+    size: u32,
+    pending_free: Arc<Mutex<Vec<(u32, u32)>>>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> UniformHandle<T> {
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Builds the `vk::DescriptorBufferInfo` for binding this uniform in
+    /// a descriptor set write — pass this directly as one of
+    /// `vk::WriteDescriptorSet::buffer_info`'s entries.
+    pub fn descriptor_buffer_info(&self, storage: &UniformStorage) -> vk::DescriptorBufferInfo {
+        vk::DescriptorBufferInfo::builder()
+            .buffer(storage.raw_buffer())
+            .offset(self.offset as u64)
+            .range(self.size as u64)
+            .build()
+    }
+}
+
+impl<T> Drop for UniformHandle<T> {
+    fn drop(&mut self) {
+        self.pending_free.lock().unwrap().push((self.offset, self.size));
+    }
+}
+
+/// How many frames' worth of GPU work can be in flight at once. Matches
+/// the number of [`super::frame::Frame`] slots the frame loop cycles
+/// through; every [`PersistentUniformRing`] keeps this many copies of its
+/// data so writing this frame's value never stomps the copy a previous,
+/// still-executing frame is reading.
+pub const FRAMES_IN_FLIGHT: usize = 2;
+
+/// Per-object uniform storage for values that change every frame (a
+/// transform, a per-instance material tweak) and need to persist across
+/// frames rather than being reallocated from [`UniformStorage`] each one.
+/// Keeps [`FRAMES_IN_FLIGHT`] copies in one small buffer and picks the
+/// right one from the frame index passed to [`PersistentUniformRing::write`],
+/// so callers can't accidentally overwrite data the GPU is still reading
+/// from the previous frame, and never need to think about freeing a slot
+/// at all.
+pub struct PersistentUniformRing<T> {
+    buffer: Buffer,
+    stride: u32,
+    _marker: PhantomData<T>,
+}
+
+impl Device {
+    /// Creates a [`PersistentUniformRing<T>`] sized for `FRAMES_IN_FLIGHT`
+    /// copies of `T`, each padded to this device's
+    /// `minUniformBufferOffsetAlignment`.
+    pub fn create_persistent_uniform_ring<T: Copy>(&self) -> BackendResult<PersistentUniformRing<T>> {
+        let alignment = self
+            .physical_device
+            .properties
+            .limits
+            .min_uniform_buffer_offset_alignment
+            .max(1) as u32;
+        let stride = align_up(size_of::<T>() as u32, alignment);
+        let capacity = stride * FRAMES_IN_FLIGHT as u32;
+
+        let buffer = self.create_buffer(BufferDesc::new_cpu_to_gpu(
+            capacity as usize,
+            vk::BufferUsageFlags::UNIFORM_BUFFER,
+        ))?;
+
+        Ok(PersistentUniformRing {
+            buffer,
+            stride,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T: Copy> PersistentUniformRing<T> {
+    /// Writes `value` into this frame's copy (`frame_index % FRAMES_IN_FLIGHT`)
+    /// and returns its byte offset into [`PersistentUniformRing::raw_buffer`].
+    pub fn write(&self, device: &Device, frame_index: usize, value: &T) -> BackendResult<u32> {
+        let offset = self.offset_for_frame(frame_index);
+        let bytes = unsafe {
+            std::slice::from_raw_parts(value as *const T as *const u8, size_of::<T>())
+        };
+        self.buffer.write_at(device, offset as u64, bytes)?;
+        Ok(offset)
+    }
+
+    /// The byte offset this ring uses for `frame_index`, without writing
+    /// anything — useful when the value was already written this frame
+    /// and a caller just needs the offset again to build a descriptor.
+    pub fn offset_for_frame(&self, frame_index: usize) -> u32 {
+        (frame_index % FRAMES_IN_FLIGHT) as u32 * self.stride
+    }
+
+    pub fn descriptor_buffer_info(&self, frame_index: usize) -> vk::DescriptorBufferInfo {
+        vk::DescriptorBufferInfo::builder()
+            .buffer(self.buffer.raw)
+            .offset(self.offset_for_frame(frame_index) as u64)
+            .range(size_of::<T>() as u64)
+            .build()
+    }
+
+    pub fn raw_buffer(&self) -> vk::Buffer {
+        self.buffer.raw
+    }
+}
+
+fn align_up(value: u32, align: u32) -> u32 {
+    (value + align - 1) / align * align
+}
@@ -0,0 +1,69 @@
+use ash::vk;
+
+use crate::BackendResult;
+
+use super::device::Device;
+use super::image::{aspect_mask_for_format, ImageDesc};
+
+/// Integration points an OpenXR layer needs that a normal swapchain-driven
+/// app doesn't: the runtime picks the physical device and dictates the
+/// Vulkan instance/device extensions (via `xrGetVulkanGraphicsRequirementsKHR`
+/// and friends), and it owns the swapchain images itself rather than handing
+/// control to `vkAcquireNextImageKHR`.
+///
+/// kiri doesn't link OpenXR directly; this module is the seam a host
+/// application wires its own `openxr` crate usage through.
+pub struct XrGraphicsRequirements {
+    pub required_instance_extensions: Vec<String>,
+    pub required_device_extensions: Vec<String>,
+    pub min_api_version: (u32, u32, u32),
+}
+
+/// Wraps a `vk::Image` handed to us by the OpenXR runtime (via
+/// `xrEnumerateSwapchainImages`) so it can be used anywhere kiri expects an
+/// image view, without pretending kiri owns or can destroy the backing
+/// memory — the runtime does both.
+pub struct XrSwapchainImage {
+    pub raw: vk::Image,
+    pub view: vk::ImageView,
+}
+
+impl Device {
+    /// Builds a view over a runtime-owned swapchain image. Call once per
+    /// image returned by `xrEnumerateSwapchainImages`, not per frame.
+    pub fn create_xr_swapchain_image_view(
+        &self,
+        raw: vk::Image,
+        desc: ImageDesc,
+    ) -> BackendResult<XrSwapchainImage> {
+        let view = unsafe {
+            self.raw().create_image_view(
+                &vk::ImageViewCreateInfo::builder()
+                    .image(raw)
+                    .view_type(vk::ImageViewType::TYPE_2D_ARRAY)
+                    .format(desc.format)
+                    .subresource_range(vk::ImageSubresourceRange {
+                        aspect_mask: aspect_mask_for_format(desc.format),
+                        base_mip_level: 0,
+                        level_count: 1,
+                        base_array_layer: 0,
+                        layer_count: desc.array_elements,
+                    }),
+                None,
+            )?
+        };
+
+        Ok(XrSwapchainImage { raw, view })
+    }
+}
+
+impl XrSwapchainImage {
+    /// Runtime-owned images are destroyed by the runtime when its
+    /// swapchain is destroyed; only the view created on our side needs
+    /// cleanup.
+    pub fn destroy_view(&self, device: &Device) {
+        unsafe {
+            device.raw().destroy_image_view(self.view, None);
+        }
+    }
+}
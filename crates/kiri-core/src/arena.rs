@@ -0,0 +1,149 @@
+//! Chunked typed arenas: items are pushed into fixed-capacity chunks
+//! that, once allocated, are never moved or reallocated, so a reference
+//! handed back by [`Arena::alloc`] stays valid for the arena's whole
+//! lifetime even as later allocations grow it — unlike a plain `Vec`,
+//! whose reallocation on growth invalidates any reference into it.
+//! Complements the raw byte-offset allocators elsewhere in this crate
+//! ([`crate::memory`]) for callers that want typed, individually
+//! addressable values instead — a scene graph's nodes, a command list's
+//! entries, a frame's worth of temporary strings.
+//!
+//! [`FrameArena`] is the reset-per-frame variant: instead of freeing its
+//! chunks, [`FrameArena::reset`] drops every value in place and rewinds
+//! back to the first chunk, reusing every chunk's already-allocated
+//! capacity next frame instead of growing from scratch again.
+
+use std::cell::{Cell, RefCell};
+
+/// How many `T`s the first chunk of a new [`Arena`]/[`FrameArena`] holds,
+/// for callers that don't care and just call [`Arena::new`]. Each
+/// further chunk doubles the previous one's capacity, the same growth
+/// factor `Vec` itself uses.
+pub const DEFAULT_CHUNK_CAPACITY: usize = 128;
+
+/// A chunked arena of `T` with stable references: [`Arena::alloc`] never
+/// invalidates a reference returned by an earlier call, because it only
+/// ever appends to the current chunk or moves on to the next one — it
+/// never touches an already-allocated chunk's backing storage.
+pub struct Arena<T> {
+    chunks: RefCell<Vec<Vec<T>>>,
+    /// Index into `chunks` that [`Arena::alloc`] is currently appending
+    /// to. Separate from `chunks.len() - 1` so [`FrameArena::reset`] can
+    /// rewind this to `0` and reuse every already-allocated chunk in
+    /// order, instead of dropping them and regrowing from scratch.
+    current_chunk: Cell<usize>,
+    next_chunk_capacity: Cell<usize>,
+}
+
+impl<T> Arena<T> {
+    /// Creates an arena whose first chunk holds [`DEFAULT_CHUNK_CAPACITY`]
+    /// values.
+    pub fn new() -> Self {
+        Self::with_chunk_capacity(DEFAULT_CHUNK_CAPACITY)
+    }
+
+    /// Creates an arena whose first chunk holds `chunk_capacity` values,
+    /// for a caller that knows roughly how many it'll need up front and
+    /// wants to avoid the first few chunks' worth of reallocation.
+    pub fn with_chunk_capacity(chunk_capacity: usize) -> Self {
+        let chunk_capacity = chunk_capacity.max(1);
+        Self {
+            chunks: RefCell::new(vec![Vec::with_capacity(chunk_capacity)]),
+            current_chunk: Cell::new(0),
+            next_chunk_capacity: Cell::new(chunk_capacity),
+        }
+    }
+
+    /// Pushes `value` into the arena and returns a stable reference to
+    /// it. Moves on to the next chunk first (allocating one if none is
+    /// left to reuse) if the current one is full.
+    pub fn alloc(&self, value: T) -> &T {
+        let mut chunks = self.chunks.borrow_mut();
+
+        let index = self.current_chunk.get();
+        if chunks[index].len() == chunks[index].capacity() {
+            let index = index + 1;
+            if index == chunks.len() {
+                let next_capacity = self.next_chunk_capacity.get() * 2;
+                self.next_chunk_capacity.set(next_capacity);
+                chunks.push(Vec::with_capacity(next_capacity));
+            }
+            self.current_chunk.set(index);
+        }
+
+        let chunk = &mut chunks[self.current_chunk.get()];
+        chunk.push(value);
+        let ptr = chunk.last().expect("just pushed") as *const T;
+
+        // SAFETY: `ptr` points into a chunk's heap allocation, which is
+        // never reallocated or moved once created — growing the arena
+        // only ever pushes a new `Vec` onto `chunks`, so earlier chunks'
+        // storage, and this reference into one, stay valid for as long
+        // as `self` does.
+        unsafe { &*ptr }
+    }
+
+    /// Total number of values allocated so far, across every chunk.
+    pub fn len(&self) -> usize {
+        self.chunks.borrow().iter().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Like [`Arena`], but meant to be built and discarded once per frame:
+/// [`FrameArena::reset`] drops every allocated value and rewinds back to
+/// the first chunk, keeping every chunk's capacity so next frame's
+/// allocations don't pay to regrow it again.
+#[derive(Default)]
+pub struct FrameArena<T> {
+    inner: Arena<T>,
+}
+
+impl<T> FrameArena<T> {
+    pub fn new() -> Self {
+        Self { inner: Arena::new() }
+    }
+
+    pub fn with_chunk_capacity(chunk_capacity: usize) -> Self {
+        Self {
+            inner: Arena::with_chunk_capacity(chunk_capacity),
+        }
+    }
+
+    /// Pushes `value` into the arena and returns a reference valid until
+    /// the next [`FrameArena::reset`].
+    pub fn alloc(&self, value: T) -> &T {
+        self.inner.alloc(value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Drops every value allocated this frame and rewinds back to the
+    /// first chunk, keeping every chunk's capacity around for next
+    /// frame's allocations to reuse in order. Takes `&mut self` so the
+    /// borrow checker rejects this call while any reference from
+    /// [`FrameArena::alloc`] is still alive, the same way it would for
+    /// clearing a `Vec`.
+    pub fn reset(&mut self) {
+        let mut chunks = self.inner.chunks.borrow_mut();
+        for chunk in chunks.iter_mut() {
+            chunk.clear();
+        }
+        self.inner.current_chunk.set(0);
+    }
+}
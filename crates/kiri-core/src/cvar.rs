@@ -0,0 +1,449 @@
+//! A typed cvar registry, independent of any particular UI (the
+//! developer console in `kiri-app` is one consumer of this, not the
+//! other way around): subsystems register the knobs they want tunable
+//! at runtime — shadow resolution, TAA on/off, streaming budget, master
+//! volume — with an optional [`CvarRange`], and callers read/write them
+//! by name. [`CvarRegistry::save_config`]/[`CvarRegistry::load_config`]
+//! persist every registered cvar's current value to a flat text config
+//! file, hand-rolled rather than pulled in via `serde` to keep this
+//! crate dependency-free (see the crate doc comment).
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CvarError {
+    Unknown(String),
+    TypeMismatch { name: String },
+    OutOfRange { name: String },
+    /// A registered [`CvarValue::Enum`] index, or a name passed to
+    /// [`CvarRegistry::set_from_str`], that isn't one of the cvar's
+    /// [`CvarRange::Enum`] options.
+    NoSuchVariant { name: String },
+}
+
+impl std::fmt::Display for CvarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CvarError::Unknown(name) => write!(f, "unknown cvar: {name}"),
+            CvarError::TypeMismatch { name } => write!(f, "wrong value type for cvar {name}"),
+            CvarError::OutOfRange { name } => write!(f, "value out of range for cvar {name}"),
+            CvarError::NoSuchVariant { name } => write!(f, "no such enum variant for cvar {name}"),
+        }
+    }
+}
+
+impl std::error::Error for CvarError {}
+
+/// A cvar's current value. `Enum` stores the selected option's index into
+/// the cvar's [`CvarRange::Enum`] option list rather than the string
+/// itself, so comparing/matching on it doesn't need a string compare on
+/// every read.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CvarValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Enum(usize),
+}
+
+impl CvarValue {
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            CvarValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            CvarValue::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            CvarValue::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            CvarValue::String(value) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn as_enum_index(&self) -> Option<usize> {
+        match self {
+            CvarValue::Enum(index) => Some(*index),
+            _ => None,
+        }
+    }
+
+    fn kind_matches(&self, other: &CvarValue) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+/// Bounds a cvar's value beyond just its type — `Int`/`Float` clamp
+/// range checks, `Enum` is the fixed option list an `Enum` value indexes
+/// into (and also drives text parsing: `set_from_str` looks a name up in
+/// this list).
+#[derive(Clone, Debug)]
+pub enum CvarRange {
+    Int { min: i64, max: i64 },
+    Float { min: f64, max: f64 },
+    Enum(Vec<String>),
+}
+
+struct CvarEntry {
+    value: CvarValue,
+    range: Option<CvarRange>,
+    description: String,
+    on_change: Vec<Box<dyn FnMut(&CvarValue) + Send>>,
+}
+
+fn in_range(value: &CvarValue, range: &CvarRange) -> bool {
+    match (value, range) {
+        (CvarValue::Int(value), CvarRange::Int { min, max }) => (*min..=*max).contains(value),
+        (CvarValue::Float(value), CvarRange::Float { min, max }) => (*min..=*max).contains(value),
+        (CvarValue::Enum(index), CvarRange::Enum(options)) => *index < options.len(),
+        // `register` doesn't check that a range matches its cvar's type,
+        // so any other pairing (a `Float` cvar registered with an `Int`
+        // range, say) isn't range-checked at all. A value of the wrong
+        // type never gets here: `set` checks the type first.
+        _ => true,
+    }
+}
+
+/// Registered cvars, keyed by name. Nothing here is thread-safe on its
+/// own (`on_change` callbacks aren't `Sync`) — a game running subsystems
+/// on other threads is expected to own one registry on whichever thread
+/// applies tuning changes and hand values across via its own channel,
+/// the same way every other single-writer state in this engine works.
+#[derive(Default)]
+pub struct CvarRegistry {
+    entries: BTreeMap<String, CvarEntry>,
+}
+
+impl CvarRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `name` with `default`'s value and type. Re-registering
+    /// an existing name resets it to `default` (a subsystem re-running
+    /// its own setup code, e.g. after a device recreate, shouldn't end
+    /// up with two conflicting entries).
+    pub fn register(&mut self, name: &str, default: CvarValue, range: Option<CvarRange>, description: &str) {
+        self.entries.insert(
+            name.to_string(),
+            CvarEntry {
+                value: default,
+                range,
+                description: description.to_string(),
+                on_change: Vec::new(),
+            },
+        );
+    }
+
+    pub fn get(&self, name: &str) -> Option<&CvarValue> {
+        self.entries.get(name).map(|entry| &entry.value)
+    }
+
+    pub fn description(&self, name: &str) -> Option<&str> {
+        self.entries.get(name).map(|entry| entry.description.as_str())
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Sets `name` to `value`, rejecting a type or range mismatch, and
+    /// running every callback registered via [`CvarRegistry::on_change`]
+    /// afterwards.
+    pub fn set(&mut self, name: &str, value: CvarValue) -> Result<(), CvarError> {
+        let entry = self
+            .entries
+            .get_mut(name)
+            .ok_or_else(|| CvarError::Unknown(name.to_string()))?;
+
+        if !entry.value.kind_matches(&value) {
+            return Err(CvarError::TypeMismatch { name: name.to_string() });
+        }
+        if let Some(range) = &entry.range {
+            if !in_range(&value, range) {
+                return Err(match value {
+                    CvarValue::Enum(_) => CvarError::NoSuchVariant { name: name.to_string() },
+                    _ => CvarError::OutOfRange { name: name.to_string() },
+                });
+            }
+        }
+
+        entry.value = value;
+        for callback in &mut entry.on_change {
+            callback(&entry.value);
+        }
+        Ok(())
+    }
+
+    /// Parses `text` as `name`'s current type (or, for an `Enum` cvar,
+    /// looks `text` up in its option list) and sets it — the entry point
+    /// for a console line or a config file value, neither of which knows
+    /// a cvar's type ahead of time.
+    pub fn set_from_str(&mut self, name: &str, text: &str) -> Result<(), CvarError> {
+        let entry = self
+            .entries
+            .get(name)
+            .ok_or_else(|| CvarError::Unknown(name.to_string()))?;
+
+        let value = match &entry.value {
+            CvarValue::Bool(_) => text
+                .parse()
+                .map(CvarValue::Bool)
+                .map_err(|_| CvarError::TypeMismatch { name: name.to_string() })?,
+            CvarValue::Int(_) => text
+                .parse()
+                .map(CvarValue::Int)
+                .map_err(|_| CvarError::TypeMismatch { name: name.to_string() })?,
+            CvarValue::Float(_) => text
+                .parse()
+                .map(CvarValue::Float)
+                .map_err(|_| CvarError::TypeMismatch { name: name.to_string() })?,
+            CvarValue::String(_) => CvarValue::String(text.to_string()),
+            CvarValue::Enum(_) => {
+                let options = match &entry.range {
+                    Some(CvarRange::Enum(options)) => options,
+                    _ => return Err(CvarError::NoSuchVariant { name: name.to_string() }),
+                };
+                let index = options
+                    .iter()
+                    .position(|option| option == text)
+                    .ok_or_else(|| CvarError::NoSuchVariant { name: name.to_string() })?;
+                CvarValue::Enum(index)
+            }
+        };
+
+        self.set(name, value)
+    }
+
+    /// Registers `callback` to run every time `name` is changed via
+    /// [`CvarRegistry::set`]/[`CvarRegistry::set_from_str`] (including by
+    /// [`CvarRegistry::load_config`]) — how a subsystem reacts to its
+    /// tunables changing without polling them every frame.
+    pub fn on_change(
+        &mut self,
+        name: &str,
+        callback: impl FnMut(&CvarValue) + Send + 'static,
+    ) -> Result<(), CvarError> {
+        let entry = self
+            .entries
+            .get_mut(name)
+            .ok_or_else(|| CvarError::Unknown(name.to_string()))?;
+        entry.on_change.push(Box::new(callback));
+        Ok(())
+    }
+
+    /// Serializes every registered cvar as one `name = value` line, in
+    /// name order, so two saves of the same settings produce identical
+    /// files.
+    pub fn save_config(&self) -> String {
+        let mut out = String::new();
+        for (name, entry) in &self.entries {
+            let value_text = match &entry.value {
+                CvarValue::Bool(value) => value.to_string(),
+                CvarValue::Int(value) => value.to_string(),
+                CvarValue::Float(value) => value.to_string(),
+                CvarValue::String(value) => value.clone(),
+                CvarValue::Enum(index) => match &entry.range {
+                    Some(CvarRange::Enum(options)) => {
+                        options.get(*index).cloned().unwrap_or_default()
+                    }
+                    _ => index.to_string(),
+                },
+            };
+            out.push_str(name);
+            out.push_str(" = ");
+            out.push_str(&value_text);
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Applies every `name = value` line in `text` (blank lines and `#`
+    /// comments ignored) to already-registered cvars. A cvar named in
+    /// `text` that hasn't been registered yet is skipped rather than
+    /// erroring — a config file written by a newer build, opened by an
+    /// older one that doesn't know a given cvar, should still apply
+    /// everything it does recognize.
+    pub fn load_config(&mut self, text: &str) {
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((name, value)) = line.split_once('=') else {
+                continue;
+            };
+            let _ = self.set_from_str(name.trim(), value.trim());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_rejects_type_mismatch() {
+        let mut registry = CvarRegistry::new();
+        registry.register("r.msaa", CvarValue::Bool(false), None, "");
+        assert_eq!(
+            registry.set("r.msaa", CvarValue::Int(1)),
+            Err(CvarError::TypeMismatch { name: "r.msaa".to_string() })
+        );
+    }
+
+    #[test]
+    fn set_rejects_out_of_range_int() {
+        let mut registry = CvarRegistry::new();
+        registry.register(
+            "r.shadow_res",
+            CvarValue::Int(512),
+            Some(CvarRange::Int { min: 256, max: 2048 }),
+            "",
+        );
+        assert_eq!(
+            registry.set("r.shadow_res", CvarValue::Int(4096)),
+            Err(CvarError::OutOfRange { name: "r.shadow_res".to_string() })
+        );
+        assert!(registry.set("r.shadow_res", CvarValue::Int(2048)).is_ok());
+    }
+
+    #[test]
+    fn set_rejects_out_of_range_float() {
+        let mut registry = CvarRegistry::new();
+        registry.register(
+            "a.master_volume",
+            CvarValue::Float(1.0),
+            Some(CvarRange::Float { min: 0.0, max: 1.0 }),
+            "",
+        );
+        assert_eq!(
+            registry.set("a.master_volume", CvarValue::Float(1.5)),
+            Err(CvarError::OutOfRange { name: "a.master_volume".to_string() })
+        );
+    }
+
+    #[test]
+    fn set_enum_rejects_index_out_of_options() {
+        let mut registry = CvarRegistry::new();
+        registry.register(
+            "r.aa_mode",
+            CvarValue::Enum(0),
+            Some(CvarRange::Enum(vec!["off".to_string(), "taa".to_string()])),
+            "",
+        );
+        assert_eq!(
+            registry.set("r.aa_mode", CvarValue::Enum(2)),
+            Err(CvarError::NoSuchVariant { name: "r.aa_mode".to_string() })
+        );
+    }
+
+    #[test]
+    fn set_from_str_parses_every_type() {
+        let mut registry = CvarRegistry::new();
+        registry.register("r.msaa", CvarValue::Bool(false), None, "");
+        registry.register("r.shadow_res", CvarValue::Int(512), None, "");
+        registry.register("a.master_volume", CvarValue::Float(1.0), None, "");
+        registry.register("g.profile_name", CvarValue::String(String::new()), None, "");
+        registry.register(
+            "r.aa_mode",
+            CvarValue::Enum(0),
+            Some(CvarRange::Enum(vec!["off".to_string(), "taa".to_string()])),
+            "",
+        );
+
+        registry.set_from_str("r.msaa", "true").unwrap();
+        assert_eq!(registry.get("r.msaa"), Some(&CvarValue::Bool(true)));
+
+        registry.set_from_str("r.shadow_res", "1024").unwrap();
+        assert_eq!(registry.get("r.shadow_res"), Some(&CvarValue::Int(1024)));
+
+        registry.set_from_str("a.master_volume", "0.5").unwrap();
+        assert_eq!(registry.get("a.master_volume"), Some(&CvarValue::Float(0.5)));
+
+        registry.set_from_str("g.profile_name", "hello").unwrap();
+        assert_eq!(
+            registry.get("g.profile_name"),
+            Some(&CvarValue::String("hello".to_string()))
+        );
+
+        registry.set_from_str("r.aa_mode", "taa").unwrap();
+        assert_eq!(registry.get("r.aa_mode"), Some(&CvarValue::Enum(1)));
+    }
+
+    #[test]
+    fn set_from_str_rejects_unknown_enum_variant() {
+        let mut registry = CvarRegistry::new();
+        registry.register(
+            "r.aa_mode",
+            CvarValue::Enum(0),
+            Some(CvarRange::Enum(vec!["off".to_string(), "taa".to_string()])),
+            "",
+        );
+        assert_eq!(
+            registry.set_from_str("r.aa_mode", "fxaa"),
+            Err(CvarError::NoSuchVariant { name: "r.aa_mode".to_string() })
+        );
+    }
+
+    #[test]
+    fn set_from_str_rejects_unparseable_int() {
+        let mut registry = CvarRegistry::new();
+        registry.register("r.shadow_res", CvarValue::Int(512), None, "");
+        assert_eq!(
+            registry.set_from_str("r.shadow_res", "not a number"),
+            Err(CvarError::TypeMismatch { name: "r.shadow_res".to_string() })
+        );
+    }
+
+    #[test]
+    fn set_unknown_name_errors() {
+        let mut registry = CvarRegistry::new();
+        assert_eq!(
+            registry.set("r.nonexistent", CvarValue::Bool(true)),
+            Err(CvarError::Unknown("r.nonexistent".to_string()))
+        );
+    }
+
+    #[test]
+    fn load_config_skips_unregistered_names_and_comments() {
+        let mut registry = CvarRegistry::new();
+        registry.register("r.shadow_res", CvarValue::Int(512), None, "");
+        registry.load_config("# a comment\nr.shadow_res = 1024\nr.unregistered = 7\n");
+        assert_eq!(registry.get("r.shadow_res"), Some(&CvarValue::Int(1024)));
+        assert_eq!(registry.get("r.unregistered"), None);
+    }
+
+    #[test]
+    fn save_config_round_trips_through_load_config() {
+        let mut original = CvarRegistry::new();
+        original.register("r.shadow_res", CvarValue::Int(512), None, "");
+        original.register("a.master_volume", CvarValue::Float(0.75), None, "");
+        original.set("r.shadow_res", CvarValue::Int(2048)).unwrap();
+
+        let saved = original.save_config();
+
+        let mut reloaded = CvarRegistry::new();
+        reloaded.register("r.shadow_res", CvarValue::Int(512), None, "");
+        reloaded.register("a.master_volume", CvarValue::Float(0.75), None, "");
+        reloaded.load_config(&saved);
+
+        assert_eq!(reloaded.get("r.shadow_res"), original.get("r.shadow_res"));
+        assert_eq!(reloaded.get("a.master_volume"), original.get("a.master_volume"));
+    }
+}
@@ -0,0 +1,66 @@
+//! A lightweight multi-producer, frame-drained event channel for
+//! loosely coupled engine-wide notifications — swapchain recreated,
+//! asset reloaded, device lost, streaming completed — so a subsystem
+//! that cares can react without the one raising the event needing to
+//! know who's listening. Built on `std::sync::mpsc`, the same kind of
+//! channel `kiri-backend`'s deferred-destruction queue uses for a
+//! similar "fire now, handle later" shape.
+//!
+//! Every event type gets its own [`EventBus<T>`] rather than one bus of
+//! mixed event types to downcast out of, so a subscriber only pays for
+//! the events it actually cares about.
+
+use std::sync::mpsc;
+
+/// The producer half of an [`EventBus<T>`]. Cloning it is how multiple
+/// subsystems get to raise the same kind of event; every clone sends
+/// into the same queue.
+#[derive(Clone)]
+pub struct EventWriter<T> {
+    sender: mpsc::Sender<T>,
+}
+
+impl<T> EventWriter<T> {
+    pub fn send(&self, event: T) {
+        // A send only fails once the `EventBus`, which owns the
+        // receiving end, has been dropped — nothing is left to drain the
+        // event by then, so it's dropped rather than propagated.
+        let _ = self.sender.send(event);
+    }
+}
+
+/// A channel of `T` events, drained once a frame by whatever subsystem
+/// owns it. [`EventBus::writer`] hands out [`EventWriter<T>`]s for other
+/// subsystems to raise events through.
+pub struct EventBus<T> {
+    sender: mpsc::Sender<T>,
+    receiver: mpsc::Receiver<T>,
+}
+
+impl<T> EventBus<T> {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self { sender, receiver }
+    }
+
+    pub fn writer(&self) -> EventWriter<T> {
+        EventWriter {
+            sender: self.sender.clone(),
+        }
+    }
+
+    /// Drains every event sent since the last call to
+    /// [`EventBus::drain`], in send order. Typically called once a frame;
+    /// an event sent between drains and never drained before the bus is
+    /// dropped is simply lost, the same as any other frame-scoped queue
+    /// in this engine.
+    pub fn drain(&self) -> Vec<T> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+impl<T> Default for EventBus<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,158 @@
+//! A dense, hash-free map from a generational handle to a value —
+//! `HashMap<Handle, _>` showing up hot in a profile for associating
+//! external data (render proxy extras, residency info, ...) with an
+//! existing pool's entries is what this replaces: lookups index straight
+//! into a `Vec` instead of hashing and probing.
+//!
+//! [`HandleMap`] doesn't allocate handles itself — it attaches values to
+//! [`PoolHandle`]s some other pool (a slot map, an arena with its own
+//! generation counter, ...) already handed out, using the handle's
+//! index to place the value and its generation to detect a stale handle
+//! from a since-reused index.
+
+use std::marker::PhantomData;
+
+/// A generational reference to a `T`: `index` into whatever pool or
+/// [`HandleMap`] it's used with, plus a `generation` that must match for
+/// a lookup to succeed. `T` only marks what kind of thing this handle
+/// refers to and is never stored — two `PoolHandle<A>` and
+/// `PoolHandle<B>` with the same index/generation are still distinct
+/// types, so they can't be mixed up at a call site.
+pub struct PoolHandle<T> {
+    index: u32,
+    generation: u32,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> PoolHandle<T> {
+    pub fn new(index: u32, generation: u32) -> Self {
+        Self {
+            index,
+            generation,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+impl<T> Clone for PoolHandle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for PoolHandle<T> {}
+
+impl<T> PartialEq for PoolHandle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<T> Eq for PoolHandle<T> {}
+
+impl<T> std::fmt::Debug for PoolHandle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PoolHandle")
+            .field("index", &self.index)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+/// A value slot, tagged with the generation it was inserted under so a
+/// lookup through a stale [`PoolHandle`] (one whose index has since been
+/// reused by the owning pool) reads as absent rather than returning the
+/// new occupant's data.
+struct Slot<V> {
+    generation: u32,
+    value: V,
+}
+
+/// A dense `Vec`-backed map from [`PoolHandle<T>`] to `V`. Indexing is
+/// `O(1)` and allocation-free on the lookup path; the backing `Vec` grows
+/// to fit the largest handle index ever inserted, so this is a good fit
+/// when handle indices are themselves dense (as a slot-map-style pool's
+/// typically are), not for sparse or very large index ranges.
+pub struct HandleMap<T, V> {
+    slots: Vec<Option<Slot<V>>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T, V> HandleMap<T, V> {
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Associates `value` with `handle`, overwriting whatever was there
+    /// (at `handle`'s index, regardless of generation) and returning it.
+    pub fn insert(&mut self, handle: PoolHandle<T>, value: V) -> Option<V> {
+        let index = handle.index as usize;
+        if index >= self.slots.len() {
+            self.slots.resize_with(index + 1, || None);
+        }
+        self.slots[index]
+            .replace(Slot {
+                generation: handle.generation,
+                value,
+            })
+            .map(|slot| slot.value)
+    }
+
+    pub fn contains(&self, handle: PoolHandle<T>) -> bool {
+        self.get(handle).is_some()
+    }
+
+    pub fn get(&self, handle: PoolHandle<T>) -> Option<&V> {
+        self.slots
+            .get(handle.index as usize)?
+            .as_ref()
+            .filter(|slot| slot.generation == handle.generation)
+            .map(|slot| &slot.value)
+    }
+
+    pub fn get_mut(&mut self, handle: PoolHandle<T>) -> Option<&mut V> {
+        self.slots
+            .get_mut(handle.index as usize)?
+            .as_mut()
+            .filter(|slot| slot.generation == handle.generation)
+            .map(|slot| &mut slot.value)
+    }
+
+    /// Removes and returns `handle`'s value, or `None` if `handle` is
+    /// stale or nothing was ever inserted for it. A stale handle's slot
+    /// (reused by a newer generation) is left untouched rather than
+    /// cleared.
+    pub fn remove(&mut self, handle: PoolHandle<T>) -> Option<V> {
+        let slot = self.slots.get_mut(handle.index as usize)?;
+        if slot.as_ref().is_some_and(|slot| slot.generation == handle.generation) {
+            slot.take().map(|slot| slot.value)
+        } else {
+            None
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_some()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T, V> Default for HandleMap<T, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
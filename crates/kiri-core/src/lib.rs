@@ -1,3 +1,14 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:cebbcd6d94adfb9a9cd6bbcd87c9279b981ce9d8224e887a9c30cb41c7e06d87
-size 1791
+//! Small, dependency-free utilities shared across every other kiri crate
+//! (handles, arenas, timing, ...) — nothing here knows about Vulkan,
+//! assets, or any other higher-level concept.
+
+pub mod arena;
+pub mod chunky_list;
+pub mod cvar;
+pub mod event_bus;
+pub mod handle;
+pub mod handle_map;
+pub mod memory;
+pub mod name;
+pub mod save;
+pub mod time;
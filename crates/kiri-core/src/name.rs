@@ -0,0 +1,127 @@
+//! A hashed string for fast, allocation-free comparisons and map keys —
+//! pass names, material parameter names, bone names, technique names,
+//! anywhere a raw `String` key would otherwise hash its full bytes on
+//! every per-frame lookup. Two [`Name`]s built from the same text always
+//! compare equal in O(1) without touching the original bytes; the hash
+//! is FNV-1a 64-bit, chosen for being simple and fast on the short
+//! strings this is meant for rather than for cryptographic quality —
+//! collisions are possible in principle, the same tradeoff this engine
+//! already accepts in a few other places that trade hash quality for
+//! being dependency-free and fast.
+//!
+//! In a debug build, every string ever passed to [`Name::new`] is
+//! recorded in a global table so [`Name::debug_str`] can recover it for
+//! logging — the same "pay for the lookup table only in debug" tradeoff
+//! `kiri-backend`'s resource registry makes for creation backtraces.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+fn debug_table() -> &'static Mutex<HashMap<u64, String>> {
+    static TABLE: OnceLock<Mutex<HashMap<u64, String>>> = OnceLock::new();
+    TABLE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// A hashed string. See the module doc comment for what this trades off
+/// against a raw `String` key.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Name(u64);
+
+impl Name {
+    /// Hashes `text` into a `Name`. In a debug build, also records
+    /// `text` in a global table (first write wins on a collision) so
+    /// [`Name::debug_str`] can recover it later.
+    pub fn new(text: &str) -> Self {
+        let hash = fnv1a_64(text.as_bytes());
+        if cfg!(debug_assertions) {
+            debug_table()
+                .lock()
+                .unwrap()
+                .entry(hash)
+                .or_insert_with(|| text.to_string());
+        }
+        Self(hash)
+    }
+
+    /// The raw FNV-1a hash, for a caller that wants a stable numeric key
+    /// (a binary asset format, a GPU-side lookup) instead of a `Name`.
+    pub fn hash(&self) -> u64 {
+        self.0
+    }
+
+    /// The string recorded for this `Name`'s hash, if this is a debug
+    /// build — on a hash collision, whichever colliding text was recorded
+    /// first, which may not be the one this `Name` was built from. `None`
+    /// in a release build, or for a hash nothing was ever
+    /// registered under — debugging/display only, never something to
+    /// branch program behavior on.
+    pub fn debug_str(&self) -> Option<String> {
+        debug_table().lock().unwrap().get(&self.0).cloned()
+    }
+}
+
+impl From<&str> for Name {
+    fn from(text: &str) -> Self {
+        Name::new(text)
+    }
+}
+
+impl std::fmt::Debug for Name {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.debug_str() {
+            Some(text) => write!(f, "Name({text:?})"),
+            None => write!(f, "Name(0x{:016x})", self.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv1a_64_matches_reference_vectors() {
+        // From the FNV test suite (http://www.isthe.com/chongo/src/fnv/test_fnv.c).
+        assert_eq!(fnv1a_64(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a_64(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a_64(b"foobar"), 0x85944171f73967e8);
+    }
+
+    #[test]
+    fn same_text_hashes_equal() {
+        assert_eq!(Name::new("bone_upper_arm_l"), Name::new("bone_upper_arm_l"));
+    }
+
+    #[test]
+    fn different_text_hashes_differ() {
+        assert_ne!(Name::new("bone_upper_arm_l"), Name::new("bone_upper_arm_r"));
+    }
+
+    #[test]
+    fn from_str_matches_new() {
+        assert_eq!(Name::from("technique_default"), Name::new("technique_default"));
+    }
+
+    #[test]
+    fn hash_is_stable_across_instances() {
+        assert_eq!(Name::new("albedo").hash(), Name::new("albedo").hash());
+    }
+
+    #[test]
+    fn debug_str_recovers_original_text() {
+        let name = Name::new("kiri_name_debug_str_test_unique");
+        assert_eq!(name.debug_str(), Some("kiri_name_debug_str_test_unique".to_string()));
+    }
+}
@@ -0,0 +1,244 @@
+//! Versioned save-game / user-data serialization, plus resolving the
+//! platform-appropriate directory such data belongs in.
+//!
+//! [`SaveData`] is a small hand-rolled binary codec trait rather than a
+//! `serde` dependency, matching this crate's "no dependencies" rule
+//! (see the crate doc comment). Schema evolution is likewise left to
+//! each save type's own [`VersionedSave::read_versioned`] rather than
+//! attempted generically here — a type with save history is the only
+//! thing that actually knows how to upgrade its own older layouts.
+//! Compression is a caller-supplied hook for the same reason: this crate
+//! doesn't take an opinion on which compression crate a project uses.
+
+use std::io;
+use std::path::PathBuf;
+
+const SAVE_MAGIC: [u8; 4] = *b"KSAV";
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    BadMagic,
+    Truncated,
+    /// The save file's version is newer than this build knows how to
+    /// read (a save written by a newer version of the game, opened by
+    /// an older one).
+    VersionTooNew { found: u32, max_supported: u32 },
+}
+
+impl std::fmt::Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "save I/O error: {err}"),
+            SaveError::BadMagic => write!(f, "not a kiri save file"),
+            SaveError::Truncated => write!(f, "save data ended unexpectedly"),
+            SaveError::VersionTooNew { found, max_supported } => write!(
+                f,
+                "save version {found} is newer than the highest version this build supports ({max_supported})"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SaveError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SaveError {
+    fn from(err: io::Error) -> Self {
+        SaveError::Io(err)
+    }
+}
+
+/// A type that can encode itself to and decode itself from a flat byte
+/// buffer. Implemented here for the primitives and containers save data
+/// is built out of; application save structs implement it by writing
+/// their fields in order.
+pub trait SaveData: Sized {
+    fn write(&self, out: &mut Vec<u8>);
+    fn read(input: &mut &[u8]) -> Result<Self, SaveError>;
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], SaveError> {
+    if input.len() < len {
+        return Err(SaveError::Truncated);
+    }
+    let (head, tail) = input.split_at(len);
+    *input = tail;
+    Ok(head)
+}
+
+macro_rules! impl_save_data_for_int {
+    ($($ty:ty),*) => {
+        $(
+            impl SaveData for $ty {
+                fn write(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn read(input: &mut &[u8]) -> Result<Self, SaveError> {
+                    let bytes = take(input, std::mem::size_of::<$ty>())?;
+                    Ok(<$ty>::from_le_bytes(bytes.try_into().unwrap()))
+                }
+            }
+        )*
+    };
+}
+
+impl_save_data_for_int!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+impl SaveData for bool {
+    fn write(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+
+    fn read(input: &mut &[u8]) -> Result<Self, SaveError> {
+        Ok(u8::read(input)? != 0)
+    }
+}
+
+impl SaveData for String {
+    fn write(&self, out: &mut Vec<u8>) {
+        (self.len() as u32).write(out);
+        out.extend_from_slice(self.as_bytes());
+    }
+
+    fn read(input: &mut &[u8]) -> Result<Self, SaveError> {
+        let len = u32::read(input)? as usize;
+        let bytes = take(input, len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| SaveError::Truncated)
+    }
+}
+
+impl<T: SaveData> SaveData for Vec<T> {
+    fn write(&self, out: &mut Vec<u8>) {
+        (self.len() as u32).write(out);
+        for item in self {
+            item.write(out);
+        }
+    }
+
+    fn read(input: &mut &[u8]) -> Result<Self, SaveError> {
+        let len = u32::read(input)? as usize;
+        (0..len).map(|_| T::read(input)).collect()
+    }
+}
+
+impl<T: SaveData> SaveData for Option<T> {
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            Some(value) => {
+                out.push(1);
+                value.write(out);
+            }
+            None => out.push(0),
+        }
+    }
+
+    fn read(input: &mut &[u8]) -> Result<Self, SaveError> {
+        match u8::read(input)? {
+            0 => Ok(None),
+            _ => Ok(Some(T::read(input)?)),
+        }
+    }
+}
+
+/// A save type that knows its own current wire version and how to bring
+/// any older version's bytes forward to it. The default implementation
+/// only accepts `version == CURRENT_VERSION`; a type with save history
+/// overrides `read_versioned` to decode an older layout and upgrade it
+/// field by field.
+pub trait VersionedSave: SaveData {
+    const CURRENT_VERSION: u32;
+
+    fn read_versioned(version: u32, input: &mut &[u8]) -> Result<Self, SaveError> {
+        if version != Self::CURRENT_VERSION {
+            return Err(SaveError::VersionTooNew {
+                found: version,
+                max_supported: Self::CURRENT_VERSION,
+            });
+        }
+        Self::read(input)
+    }
+}
+
+/// Encodes `value` behind the magic + version header [`load`] expects.
+pub fn save<T: VersionedSave>(value: &T) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(&SAVE_MAGIC);
+    T::CURRENT_VERSION.write(&mut out);
+    value.write(&mut out);
+    out
+}
+
+/// Decodes a buffer written by [`save`], migrating forward via
+/// [`VersionedSave::read_versioned`] if it was written by an older
+/// version of `T`.
+pub fn load<T: VersionedSave>(bytes: &[u8]) -> Result<T, SaveError> {
+    let mut input = bytes;
+    let magic = take(&mut input, 4)?;
+    if magic != SAVE_MAGIC {
+        return Err(SaveError::BadMagic);
+    }
+    let version = u32::read(&mut input)?;
+    T::read_versioned(version, &mut input)
+}
+
+/// Prefixes `compress(raw)`'s output with `raw`'s uncompressed length, so
+/// [`decompress_payload`] can size its output buffer up front. `raw` is
+/// typically the output of [`save`]. This crate has no compression
+/// dependency of its own — the caller supplies whichever codec their
+/// project already depends on elsewhere (zstd, deflate, ...).
+pub fn compress_payload(raw: &[u8], compress: impl FnOnce(&[u8]) -> Vec<u8>) -> Vec<u8> {
+    let mut out = Vec::with_capacity(4 + raw.len());
+    (raw.len() as u32).write(&mut out);
+    out.extend(compress(raw));
+    out
+}
+
+/// Inverse of [`compress_payload`]: reads the uncompressed length prefix
+/// and hands the remaining bytes plus that length to `decompress`.
+pub fn decompress_payload(
+    bytes: &[u8],
+    decompress: impl FnOnce(&[u8], usize) -> io::Result<Vec<u8>>,
+) -> Result<Vec<u8>, SaveError> {
+    let mut input = bytes;
+    let raw_len = u32::read(&mut input)? as usize;
+    Ok(decompress(input, raw_len)?)
+}
+
+/// Resolves the platform-appropriate directory for `application`'s user
+/// data (save games, settings, ...), under `organization`, creating it
+/// if it doesn't exist yet. Hand-rolled rather than pulling in the
+/// `dirs` crate, in keeping with this crate depending on nothing else.
+///
+/// - Windows: `%APPDATA%\<organization>\<application>`
+/// - macOS: `~/Library/Application Support/<organization>/<application>`
+/// - Everything else: `$XDG_DATA_HOME/<organization>/<application>`,
+///   falling back to `~/.local/share/<organization>/<application>`
+pub fn user_data_dir(organization: &str, application: &str) -> io::Result<PathBuf> {
+    let not_found = || io::Error::new(
+        io::ErrorKind::NotFound,
+        "could not resolve a user data directory for this platform",
+    );
+
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_DATA_HOME").map(PathBuf::from).or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share"))
+        })
+    };
+
+    let dir = base.ok_or_else(not_found)?.join(organization).join(application);
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
@@ -1,3 +1,52 @@
-version https://git-lfs.github.com/spec/v1
-oid sha256:c02cf723c798016c20ced1aafe34304b86095a6630a1f8949f5a2dd3711fc01e
-size 2399
+use std::time::{Duration, Instant};
+
+/// Drives a fixed-timestep update loop alongside variable-rate rendering:
+/// `update` is called zero or more times per frame, always `dt` apart, so
+/// gameplay and physics never depend on frame rate; rendering still runs
+/// once per real frame and interpolates using [`FixedTimestepLoop::alpha`].
+pub struct FixedTimestepLoop {
+    dt: Duration,
+    accumulator: Duration,
+    last_tick: Instant,
+    /// Caps how much wall-clock time a single frame can contribute to the
+    /// accumulator, so a debugger pause or a slow load doesn't cause a
+    /// storm of catch-up updates afterwards.
+    max_frame_time: Duration,
+}
+
+impl FixedTimestepLoop {
+    pub fn new(dt: Duration) -> Self {
+        Self {
+            dt,
+            accumulator: Duration::ZERO,
+            last_tick: Instant::now(),
+            max_frame_time: Duration::from_millis(250),
+        }
+    }
+
+    /// Advances the accumulator by the time since the last call and runs
+    /// `update` once per whole `dt` now available, in order.
+    pub fn tick(&mut self, mut update: impl FnMut(Duration)) {
+        let now = Instant::now();
+        let elapsed = (now - self.last_tick).min(self.max_frame_time);
+        self.last_tick = now;
+
+        self.accumulator += elapsed;
+        while self.accumulator >= self.dt {
+            update(self.dt);
+            self.accumulator -= self.dt;
+        }
+    }
+
+    /// How far between the last completed fixed update and the next one
+    /// the current real frame falls, as a `0.0..1.0` fraction — for
+    /// interpolating rendered transforms between the last two simulated
+    /// states instead of visibly stepping at `dt`'s rate.
+    pub fn alpha(&self) -> f32 {
+        self.accumulator.as_secs_f32() / self.dt.as_secs_f32()
+    }
+
+    pub fn dt(&self) -> Duration {
+        self.dt
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bindings::InputBinding;
+use crate::state::InputState;
+
+/// One axis's contribution from a single binding: digital bindings (keys,
+/// buttons) contribute `scale` while held, `0.0` otherwise; analog
+/// bindings (gamepad sticks) contribute `scale * raw_value`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct AxisBinding {
+    pub binding: InputBinding,
+    pub scale: f32,
+}
+
+/// A rebindable set of named actions (digital) and axes (analog), each
+/// backed by one or more [`InputBinding`]s. Serialized as-is for the
+/// config system, so "rebind jump to Space" is just editing this struct
+/// and writing it back out.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ActionMap {
+    pub actions: HashMap<String, Vec<InputBinding>>,
+    pub axes: HashMap<String, Vec<AxisBinding>>,
+}
+
+impl ActionMap {
+    pub fn bind_action(&mut self, action: &str, binding: InputBinding) {
+        self.actions.entry(action.to_string()).or_default().push(binding);
+    }
+
+    pub fn bind_axis(&mut self, axis: &str, binding: InputBinding, scale: f32) {
+        self.axes
+            .entry(axis.to_string())
+            .or_default()
+            .push(AxisBinding { binding, scale });
+    }
+}
+
+/// Combines an [`ActionMap`] with live [`InputState`] to answer the
+/// queries games actually want: "was jump just pressed", "what's the
+/// current move-axis value", rather than reasoning about individual keys.
+pub struct Input {
+    pub map: ActionMap,
+    pub state: InputState,
+}
+
+impl Input {
+    pub fn new(map: ActionMap) -> Self {
+        Self {
+            map,
+            state: InputState::default(),
+        }
+    }
+
+    pub fn begin_frame(&mut self) {
+        self.state.begin_frame();
+    }
+
+    pub fn is_action_held(&self, action: &str) -> bool {
+        self.bindings_for_action(action)
+            .iter()
+            .any(|binding| self.state.is_held(*binding))
+    }
+
+    pub fn is_action_pressed(&self, action: &str) -> bool {
+        self.bindings_for_action(action)
+            .iter()
+            .any(|binding| self.state.is_pressed(*binding))
+    }
+
+    pub fn is_action_released(&self, action: &str) -> bool {
+        self.bindings_for_action(action)
+            .iter()
+            .any(|binding| self.state.is_released(*binding))
+    }
+
+    pub fn axis_value(&self, axis: &str) -> f32 {
+        let Some(bindings) = self.map.axes.get(axis) else {
+            return 0.0;
+        };
+
+        bindings
+            .iter()
+            .map(|axis_binding| {
+                let raw = match axis_binding.binding {
+                    InputBinding::GamepadAxis(_) => self.state.axis_raw(axis_binding.binding),
+                    digital => {
+                        if self.state.is_held(digital) {
+                            1.0
+                        } else {
+                            0.0
+                        }
+                    }
+                };
+                raw * axis_binding.scale
+            })
+            .sum()
+    }
+
+    fn bindings_for_action(&self, action: &str) -> &[InputBinding] {
+        self.map.actions.get(action).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
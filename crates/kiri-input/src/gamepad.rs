@@ -0,0 +1,77 @@
+use anyhow::Result;
+use gilrs::{Axis, Button, EventType, Gilrs};
+use winit::event::ElementState;
+
+use crate::bindings::{GamepadAxis, GamepadButton, InputBinding};
+use crate::state::InputState;
+
+/// Polls connected gamepads via `gilrs` and feeds their events into an
+/// [`InputState`] through the same digital/analog model keyboard and
+/// mouse use, so [`crate::actions::Input`] doesn't need to know gamepads
+/// exist at all.
+pub struct GamepadSource {
+    gilrs: Gilrs,
+}
+
+impl GamepadSource {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            gilrs: Gilrs::new().map_err(|err| anyhow::anyhow!("Failed to initialize gilrs: {err}"))?,
+        })
+    }
+
+    /// Drains every pending gamepad event and applies it to `state`. Call
+    /// once per frame, alongside pumping the window event loop.
+    pub fn poll_into(&mut self, state: &mut InputState) {
+        while let Some(event) = self.gilrs.next_event() {
+            match event.event {
+                EventType::ButtonPressed(button, _) => {
+                    if let Some(mapped) = map_button(button) {
+                        state.set_binding_state(InputBinding::GamepadButton(mapped), ElementState::Pressed);
+                    }
+                }
+                EventType::ButtonReleased(button, _) => {
+                    if let Some(mapped) = map_button(button) {
+                        state.set_binding_state(InputBinding::GamepadButton(mapped), ElementState::Released);
+                    }
+                }
+                EventType::AxisChanged(axis, value, _) => {
+                    if let Some(mapped) = map_axis(axis) {
+                        state.set_axis(InputBinding::GamepadAxis(mapped), value);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn map_button(button: Button) -> Option<GamepadButton> {
+    Some(match button {
+        Button::South => GamepadButton::South,
+        Button::East => GamepadButton::East,
+        Button::West => GamepadButton::West,
+        Button::North => GamepadButton::North,
+        Button::LeftTrigger => GamepadButton::LeftShoulder,
+        Button::RightTrigger => GamepadButton::RightShoulder,
+        Button::LeftTrigger2 => GamepadButton::LeftTrigger,
+        Button::RightTrigger2 => GamepadButton::RightTrigger,
+        Button::Start => GamepadButton::Start,
+        Button::Select => GamepadButton::Select,
+        Button::DPadUp => GamepadButton::DPadUp,
+        Button::DPadDown => GamepadButton::DPadDown,
+        Button::DPadLeft => GamepadButton::DPadLeft,
+        Button::DPadRight => GamepadButton::DPadRight,
+        _ => return None,
+    })
+}
+
+fn map_axis(axis: Axis) -> Option<GamepadAxis> {
+    Some(match axis {
+        Axis::LeftStickX => GamepadAxis::LeftStickX,
+        Axis::LeftStickY => GamepadAxis::LeftStickY,
+        Axis::RightStickX => GamepadAxis::RightStickX,
+        Axis::RightStickY => GamepadAxis::RightStickY,
+        _ => return None,
+    })
+}
@@ -0,0 +1,16 @@
+//! Input abstraction: keyboard, mouse, and gamepad events all land in one
+//! [`state::InputState`], and a rebindable [`actions::ActionMap`] turns
+//! that into the pressed/held/released/axis queries games actually want,
+//! instead of every sample re-matching `winit` events by hand.
+
+pub mod actions;
+pub mod bindings;
+pub mod gamepad;
+pub mod replay;
+pub mod state;
+
+pub use actions::{ActionMap, AxisBinding, Input};
+pub use bindings::{GamepadAxis, GamepadButton, InputBinding};
+pub use gamepad::GamepadSource;
+pub use replay::{ReplayPlayer, ReplayRecorder};
+pub use state::InputState;
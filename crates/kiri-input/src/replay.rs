@@ -0,0 +1,133 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use winit::event::ElementState;
+
+use crate::bindings::InputBinding;
+use crate::state::InputState;
+
+/// One input change captured during a recorded frame — replayed back
+/// through the same [`InputState`] entry points a live winit event would
+/// have gone through.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum RecordedInputEvent {
+    Binding {
+        binding: InputBinding,
+        state: ElementState,
+    },
+    Axis {
+        binding: InputBinding,
+        value: f32,
+    },
+    MouseDelta {
+        dx: f64,
+        dy: f64,
+    },
+}
+
+/// Everything that happened during one fixed-timestep tick: how long the
+/// tick was and every input change that occurred during it. Recording
+/// `dt` alongside the events, rather than assuming a fixed rate, is what
+/// keeps a replay deterministic even if the recording and playback runs
+/// happen on machines with different frame rates — playback always
+/// drives its own clock from these `dt`s, never from wall time.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    pub dt: Duration,
+    pub events: Vec<RecordedInputEvent>,
+}
+
+/// Captures input events and per-frame `dt` as they occur, for
+/// [`ReplayPlayer`] to play back later. A game calls
+/// [`ReplayRecorder::record_binding`]/[`ReplayRecorder::record_axis`]/
+/// [`ReplayRecorder::record_mouse_delta`] from the same call sites it
+/// already forwards events to [`InputState`] from, then
+/// [`ReplayRecorder::end_frame`] once per fixed tick.
+#[derive(Default)]
+pub struct ReplayRecorder {
+    frames: Vec<RecordedFrame>,
+    pending: Vec<RecordedInputEvent>,
+}
+
+impl ReplayRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_binding(&mut self, binding: InputBinding, state: ElementState) {
+        self.pending.push(RecordedInputEvent::Binding { binding, state });
+    }
+
+    pub fn record_axis(&mut self, binding: InputBinding, value: f32) {
+        self.pending.push(RecordedInputEvent::Axis { binding, value });
+    }
+
+    pub fn record_mouse_delta(&mut self, dx: f64, dy: f64) {
+        self.pending.push(RecordedInputEvent::MouseDelta { dx, dy });
+    }
+
+    /// Closes out the current frame with `dt` and starts a new one.
+    pub fn end_frame(&mut self, dt: Duration) {
+        let events = std::mem::take(&mut self.pending);
+        self.frames.push(RecordedFrame { dt, events });
+    }
+
+    pub fn write_to_file(&self, path: &Path) -> Result<()> {
+        let bytes =
+            bincode::serialize(&self.frames).context("Failed to serialize replay recording")?;
+        std::fs::write(path, bytes).with_context(|| format!("Failed to write replay to {:?}", path))
+    }
+}
+
+/// Plays back a recording written by [`ReplayRecorder`], one frame at a
+/// time: applies each frame's events to an [`InputState`] and hands back
+/// the recorded `dt` so the caller's fixed-timestep loop advances on
+/// exactly the schedule that was recorded, rather than on wall time —
+/// the part that makes replayed input deterministic regardless of how
+/// fast the machine replaying it actually runs.
+pub struct ReplayPlayer {
+    frames: std::vec::IntoIter<RecordedFrame>,
+}
+
+impl ReplayPlayer {
+    pub fn load(path: &Path) -> Result<Self> {
+        let bytes =
+            std::fs::read(path).with_context(|| format!("Failed to read replay {:?}", path))?;
+        let frames: Vec<RecordedFrame> =
+            bincode::deserialize(&bytes).context("Failed to parse replay recording")?;
+        Ok(Self {
+            frames: frames.into_iter(),
+        })
+    }
+
+    /// Applies the next recorded frame's events to `state` and returns
+    /// its `dt`, or `None` once the recording is exhausted.
+    pub fn advance(&mut self, state: &mut InputState) -> Option<Duration> {
+        let frame = self.frames.next()?;
+        state.begin_frame();
+        for event in frame.events {
+            match event {
+                RecordedInputEvent::Binding {
+                    binding,
+                    state: element_state,
+                } => {
+                    state.set_binding_state(binding, element_state);
+                }
+                RecordedInputEvent::Axis { binding, value } => {
+                    state.set_axis(binding, value);
+                }
+                RecordedInputEvent::MouseDelta { dx, dy } => {
+                    state.mouse_delta.0 += dx;
+                    state.mouse_delta.1 += dy;
+                }
+            }
+        }
+        Some(frame.dt)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frames.len() == 0
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::{HashMap, HashSet};
+
+use winit::event::{DeviceEvent, ElementState, WindowEvent};
+
+use crate::bindings::InputBinding;
+
+/// Raw, frame-coherent state for every physical input seen so far: what's
+/// currently held, what transitioned this frame, and the last known value
+/// of any analog axis. [`crate::actions::Input`] layers the rebindable
+/// action/axis names on top of this.
+#[derive(Default)]
+pub struct InputState {
+    down: HashSet<InputBinding>,
+    pressed_this_frame: HashSet<InputBinding>,
+    released_this_frame: HashSet<InputBinding>,
+    axis_values: HashMap<InputBinding, f32>,
+    pub mouse_delta: (f64, f64),
+}
+
+impl InputState {
+    /// Clears the per-frame transition sets and mouse delta. Call once at
+    /// the start of each frame, before pumping this frame's events.
+    pub fn begin_frame(&mut self) {
+        self.pressed_this_frame.clear();
+        self.released_this_frame.clear();
+        self.mouse_delta = (0.0, 0.0);
+    }
+
+    pub fn handle_window_event(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::KeyboardInput { input, .. } => {
+                if let Some(key) = input.virtual_keycode {
+                    self.set_binding_state(InputBinding::Key(key), input.state);
+                }
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                self.set_binding_state(InputBinding::MouseButton(*button), *state);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn handle_device_event(&mut self, event: &DeviceEvent) {
+        if let DeviceEvent::MouseMotion { delta } = event {
+            self.mouse_delta.0 += delta.0;
+            self.mouse_delta.1 += delta.1;
+        }
+    }
+
+    pub(crate) fn set_binding_state(&mut self, binding: InputBinding, state: ElementState) {
+        match state {
+            ElementState::Pressed => {
+                if self.down.insert(binding) {
+                    self.pressed_this_frame.insert(binding);
+                }
+            }
+            ElementState::Released => {
+                self.down.remove(&binding);
+                self.released_this_frame.insert(binding);
+            }
+        }
+    }
+
+    pub(crate) fn set_axis(&mut self, binding: InputBinding, value: f32) {
+        self.axis_values.insert(binding, value);
+    }
+
+    pub fn is_held(&self, binding: InputBinding) -> bool {
+        self.down.contains(&binding)
+    }
+
+    pub fn is_pressed(&self, binding: InputBinding) -> bool {
+        self.pressed_this_frame.contains(&binding)
+    }
+
+    pub fn is_released(&self, binding: InputBinding) -> bool {
+        self.released_this_frame.contains(&binding)
+    }
+
+    pub fn axis_raw(&self, binding: InputBinding) -> f32 {
+        self.axis_values.get(&binding).copied().unwrap_or(0.0)
+    }
+}
@@ -0,0 +1,220 @@
+//! Golden image regression harness: renders registered [`TestScene`]s
+//! headlessly (via [`kiri_backend::Device::create_offscreen_target`], no
+//! window or swapchain involved), reads the result back with
+//! [`kiri_backend::Buffer::read_at`], and compares it against a stored
+//! reference PNG within a perceptual tolerance rather than requiring a
+//! byte-exact match — GPU driver/shader-compiler differences produce
+//! low-single-digit-value dithering noise between runs that would make an
+//! exact comparison useless.
+//!
+//! No test scenes are registered by this tree today — nothing here
+//! renders any real content yet, only the render/readback/compare
+//! machinery a game's own scenes plug into via [`TestScene`].
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use image::{ImageBuffer, Rgba, RgbaImage};
+
+use kiri_backend::{BackendResult, Device, OffscreenTarget};
+
+/// One headless test case: renders itself into an [`OffscreenTarget`] and
+/// is identified by [`TestScene::name`], which doubles as the golden
+/// image's file stem (`<golden_dir>/<name>.png`).
+pub trait TestScene {
+    fn name(&self) -> &str;
+    fn render(&self, device: &Device) -> BackendResult<OffscreenTarget>;
+}
+
+/// How much a comparison is allowed to differ before a scene is reported
+/// as failed.
+#[derive(Clone, Copy, Debug)]
+pub struct ComparisonTolerance {
+    /// A per-channel absolute difference at or below this is treated as
+    /// noise rather than a real pixel difference.
+    pub max_channel_diff: u8,
+    /// Fraction (`0.0..=1.0`) of pixels allowed to exceed
+    /// `max_channel_diff` before the whole image is considered a
+    /// mismatch — a handful of dithering-noise pixels shouldn't fail a
+    /// scene that's otherwise identical.
+    pub max_differing_pixel_fraction: f32,
+}
+
+impl Default for ComparisonTolerance {
+    fn default() -> Self {
+        Self {
+            max_channel_diff: 2,
+            max_differing_pixel_fraction: 0.001,
+        }
+    }
+}
+
+pub struct ComparisonOutcome {
+    pub matches: bool,
+    pub differing_pixel_fraction: f32,
+    /// Per-pixel absolute difference, brightened so small differences are
+    /// actually visible when a human opens it — a raw diff of a 1-in-255
+    /// value change is indistinguishable from black otherwise.
+    pub diff_image: RgbaImage,
+}
+
+/// Compares `golden` against `actual` pixel by pixel. Dimension mismatches
+/// are always a failure (there's no meaningful pixel-by-pixel diff to
+/// draw), reported with an all-white diff image so it's visually obvious
+/// something more fundamental than color drift changed.
+pub fn compare_rgba(golden: &RgbaImage, actual: &RgbaImage, tolerance: ComparisonTolerance) -> ComparisonOutcome {
+    if golden.dimensions() != actual.dimensions() {
+        return ComparisonOutcome {
+            matches: false,
+            differing_pixel_fraction: 1.0,
+            diff_image: ImageBuffer::from_pixel(
+                actual.width().max(1),
+                actual.height().max(1),
+                Rgba([255, 255, 255, 255]),
+            ),
+        };
+    }
+
+    let mut diff_image = ImageBuffer::new(golden.width(), golden.height());
+    let mut differing_pixels = 0u64;
+
+    for (golden_pixel, actual_pixel, diff_pixel) in itertools_zip3(golden.pixels(), actual.pixels(), diff_image.pixels_mut()) {
+        let mut channel_diffs = [0u8; 4];
+        let mut max_diff = 0u8;
+        for channel in 0..4 {
+            let diff = golden_pixel.0[channel].abs_diff(actual_pixel.0[channel]);
+            channel_diffs[channel] = diff;
+            max_diff = max_diff.max(diff);
+        }
+
+        if max_diff > tolerance.max_channel_diff {
+            differing_pixels += 1;
+        }
+
+        *diff_pixel = Rgba([
+            channel_diffs[0].saturating_mul(8),
+            channel_diffs[1].saturating_mul(8),
+            channel_diffs[2].saturating_mul(8),
+            255,
+        ]);
+    }
+
+    let total_pixels = (golden.width() as u64 * golden.height() as u64).max(1);
+    let differing_pixel_fraction = differing_pixels as f32 / total_pixels as f32;
+
+    ComparisonOutcome {
+        matches: differing_pixel_fraction <= tolerance.max_differing_pixel_fraction,
+        differing_pixel_fraction,
+        diff_image,
+    }
+}
+
+/// Zips three same-length iterators without pulling in a crate just for
+/// this one call site.
+fn itertools_zip3<A, B, C>(
+    a: impl Iterator<Item = A>,
+    b: impl Iterator<Item = B>,
+    c: impl Iterator<Item = C>,
+) -> impl Iterator<Item = (A, B, C)> {
+    a.zip(b).zip(c).map(|((a, b), c)| (a, b, c))
+}
+
+/// Reads `target`'s readback buffer and decodes the bytes as RGBA8. This
+/// does not wait on anything: the caller must already have submitted the
+/// copy into the readback buffer (see
+/// [`OffscreenTarget::record_copy_to_readback`]) and waited for it to
+/// finish.
+pub fn read_offscreen_target(device: &Device, target: &OffscreenTarget) -> Result<RgbaImage> {
+    let byte_len = target.extent[0] as u64 * target.extent[1] as u64 * 4;
+    let bytes = target
+        .readback
+        .read_at(device, 0, byte_len)
+        .context("Failed to read back offscreen target")?;
+
+    ImageBuffer::from_raw(target.extent[0], target.extent[1], bytes)
+        .context("Offscreen target readback was the wrong size for its own extent")
+}
+
+#[derive(Debug)]
+pub enum TestStatus {
+    Passed,
+    Failed { differing_pixel_fraction: f32 },
+    /// No golden image existed for this scene yet — one was written from
+    /// the current render, and the scene is reported separately from a
+    /// pass/fail so a first run doesn't silently "pass" with nothing
+    /// actually verified.
+    RecordedNewGolden,
+}
+
+pub struct TestReport {
+    pub name: String,
+    pub status: TestStatus,
+}
+
+/// Drives [`TestScene::render`] for each of `scenes`, comparing (or
+/// recording, if missing) each one's golden image under `golden_dir`.
+/// On a mismatch, writes the actual render and a brightened diff image
+/// under `diff_dir` for a human to inspect.
+pub struct GoldenImageHarness {
+    pub golden_dir: PathBuf,
+    pub diff_dir: PathBuf,
+    pub tolerance: ComparisonTolerance,
+}
+
+impl GoldenImageHarness {
+    pub fn run(&self, device: &Device, scenes: &[Box<dyn TestScene>]) -> Result<Vec<TestReport>> {
+        std::fs::create_dir_all(&self.golden_dir)?;
+        std::fs::create_dir_all(&self.diff_dir)?;
+
+        let mut reports = Vec::with_capacity(scenes.len());
+        for scene in scenes {
+            reports.push(self.run_one(device, scene.as_ref())?);
+        }
+        Ok(reports)
+    }
+
+    fn run_one(&self, device: &Device, scene: &dyn TestScene) -> Result<TestReport> {
+        let target = scene
+            .render(device)
+            .with_context(|| format!("Failed to render test scene {:?}", scene.name()))?;
+        let actual = read_offscreen_target(device, &target)?;
+
+        let golden_path = self.golden_path(scene.name());
+        if !golden_path.exists() {
+            actual.save(&golden_path)?;
+            return Ok(TestReport {
+                name: scene.name().to_string(),
+                status: TestStatus::RecordedNewGolden,
+            });
+        }
+
+        let golden = image::open(&golden_path)
+            .with_context(|| format!("Failed to load golden image {:?}", golden_path))?
+            .to_rgba8();
+
+        let outcome = compare_rgba(&golden, &actual, self.tolerance);
+        if !outcome.matches {
+            actual.save(self.diff_path(scene.name(), "actual"))?;
+            outcome.diff_image.save(self.diff_path(scene.name(), "diff"))?;
+        }
+
+        Ok(TestReport {
+            name: scene.name().to_string(),
+            status: if outcome.matches {
+                TestStatus::Passed
+            } else {
+                TestStatus::Failed {
+                    differing_pixel_fraction: outcome.differing_pixel_fraction,
+                }
+            },
+        })
+    }
+
+    fn golden_path(&self, name: &str) -> PathBuf {
+        self.golden_dir.join(format!("{name}.png"))
+    }
+
+    fn diff_path(&self, name: &str, suffix: &str) -> PathBuf {
+        self.diff_dir.join(format!("{name}.{suffix}.png"))
+    }
+}
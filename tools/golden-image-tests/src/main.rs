@@ -0,0 +1,62 @@
+//! CLI entry point for the golden image harness. No test scenes are
+//! registered here today — this tree has no renderable scene content to
+//! plug in yet — so running this binary just reports that, rather than
+//! pretending to have exercised anything. A game adds coverage by
+//! building its own `Vec<Box<dyn TestScene>>` and calling
+//! [`golden_image_tests::GoldenImageHarness::run`] directly; this binary
+//! is a convenience wrapper around that for CI, not the only way to use
+//! the harness.
+
+use golden_image_tests::{GoldenImageHarness, TestScene, TestStatus};
+
+fn registered_scenes() -> Vec<Box<dyn TestScene>> {
+    Vec::new()
+}
+
+fn main() -> anyhow::Result<()> {
+    env_logger::init();
+
+    let scenes = registered_scenes();
+    if scenes.is_empty() {
+        log::warn!("golden-image-tests: no test scenes registered, nothing to run");
+        return Ok(());
+    }
+
+    let instance = kiri_backend::Instance::builder().build()?;
+    let physical_device = kiri_backend::enumerate_physical_devices(&instance)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("no Vulkan physical device available"))?;
+    let device = kiri_backend::Device::create_with_mode(&physical_device, kiri_backend::DeviceMode::ComputeOnly)?;
+    let harness = GoldenImageHarness {
+        golden_dir: "golden".into(),
+        diff_dir: "target/golden-image-diffs".into(),
+        tolerance: Default::default(),
+    };
+
+    let reports = harness.run(&device, &scenes)?;
+    let mut failed = false;
+    for report in reports {
+        match report.status {
+            TestStatus::Passed => log::info!("{}: passed", report.name),
+            TestStatus::RecordedNewGolden => {
+                log::info!("{}: recorded new golden image", report.name)
+            }
+            TestStatus::Failed {
+                differing_pixel_fraction,
+            } => {
+                failed = true;
+                log::error!(
+                    "{}: FAILED ({:.4}% of pixels differ)",
+                    report.name,
+                    differing_pixel_fraction * 100.0
+                );
+            }
+        }
+    }
+
+    if failed {
+        anyhow::bail!("one or more golden image comparisons failed");
+    }
+    Ok(())
+}